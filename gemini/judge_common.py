@@ -0,0 +1,158 @@
+# gemini/judge_common.py
+# 판정 스크립트(run.py, openai_run.py, ollama_run.py) 공통 부분: 프롬프트, 명령줄 규약, JSON 보정,
+# 이미지 목록과 결과 기록(--stream_out, USAGE 줄, out_json). 스크립트마다 모델 호출 하나만 구현
+import json, argparse, re
+from pathlib import Path
+
+# ===== Prompt =====
+SYSTEM_PROMPT = (
+    "You are an accessibility analysis AI. Analyze the provided image of a building entrance to determine if it is accessible for a lone wheelchair user.\n"
+    "Accessibility Rules:\n"
+    "1. There must be no steps or curbs between the ground and the entrance.\n"
+    "2. If there are steps or curbs, a ramp must connect the ground to the entrance.\n\n"
+    "Return ONLY valid JSON. Do not include any explanations, Markdown, or code fences.\n"
+    'JSON schema: {"accessible": boolean | null, "reason": string}\n'
+)
+
+# gemini does not support jpg. mapping jpg -> image/jpeg
+MIME_BY_EXT = {
+    ".jpg": "image/jpeg",
+    ".jpeg": "image/jpeg",
+    ".png": "image/png",
+    ".webp": "image/webp",
+    ".bmp": "image/bmp",
+}
+SUPPORTED_EXTS = set(MIME_BY_EXT.keys())
+
+def guess_mime(path: Path) -> str | None:
+    return MIME_BY_EXT.get(path.suffix.lower())
+
+def parse_args(default_model: str):
+    ap = argparse.ArgumentParser()
+    ap.add_argument("--images_dir", default="bbox_images", help="분석할 이미지 폴더")
+    ap.add_argument("--out_json",   default="results/result.json", help="결과 JSON 저장 경로")
+    ap.add_argument("--model",      default=default_model)
+    ap.add_argument("--timeout",    type=float, default=60.0)
+    ap.add_argument("--temperature", type=float, default=None, help="생성 temperature (미지정 시 모델 기본값)")
+    ap.add_argument("--max_tokens",  type=int,   default=None, help="최대 출력 토큰 수 (미지정 시 모델 기본값)")
+    ap.add_argument("--stream_out", default=None, help="이미지별 결과를 한 줄씩(JSONL) 추가 기록할 경로 (runner가 실시간으로 읽음)")
+    ap.add_argument("--file_list",  default=None, help="이 파일에 적힌 이미지 이름(한 줄에 하나)만 분석 (중단된 실행 이어하기용)")
+    ap.add_argument("--prompt_file", default=None, help="SYSTEM_PROMPT 대신 쓸 프롬프트 파일 (내용은 치환 없이 그대로 사용)")
+    return ap.parse_args()
+
+# -------- Robust JSON extraction --------
+JSON_BLOCK_RE = re.compile(r"```(?:json)?\s*(\{.*?\})\s*```", re.DOTALL)
+BRACE_SPAN_RE = re.compile(r"\{.*\}", re.DOTALL)
+
+def try_extract_json(text: str) -> str | None:
+    if not text:
+        return None
+    # 1) ```json ... ``` 우선
+    m = JSON_BLOCK_RE.search(text)
+    if m:
+        return m.group(1).strip()
+    # 2) 가장 바깥 {...} 블록 추정
+    #    (간단한 휴리스틱: 첫 '{'와 마지막 '}'를 찾아 자르기)
+    first = text.find("{")
+    last  = text.rfind("}")
+    if first != -1 and last != -1 and last > first:
+        candidate = text[first:last+1].strip()
+        # 후보가 JSON처럼 보이면 반환
+        if candidate.startswith("{") and candidate.endswith("}"):
+            return candidate
+    # 3) 폴백: 정규식으로 아무 {...} 매칭
+    m2 = BRACE_SPAN_RE.search(text)
+    if m2:
+        return m2.group(0).strip()
+    return None
+
+def safe_json(text: str) -> dict:
+    """모델 응답 텍스트에서 JSON을 최대한 추출/보정."""
+    # 0) 바로 파싱 시도
+    for candidate in (text, try_extract_json(text)):
+        if not candidate:
+            continue
+        try:
+            obj = json.loads(candidate)
+            if not isinstance(obj, dict):
+                raise ValueError("not a dict")
+            # 보정
+            acc = obj.get("accessible", None)
+            if acc not in (True, False, None):
+                acc = None
+            reason = obj.get("reason", "No reason provided.")
+            if not isinstance(reason, str):
+                reason = str(reason)
+            out = {"accessible": acc, "reason": reason}
+            # 선택 필드: 항목별 판정(criteria)과 확신도(confidence)는 형식이 맞을 때만 그대로 전달
+            criteria = obj.get("criteria")
+            if isinstance(criteria, dict):
+                out["criteria"] = {str(k): v for k, v in criteria.items() if v in (True, False, None)}
+            conf = obj.get("confidence")
+            if isinstance(conf, (int, float)) and not isinstance(conf, bool):
+                out["confidence"] = float(conf)
+            return out
+        except Exception:
+            pass
+    # 실패
+    return {"accessible": None, "reason": "Parse error: model did not return valid JSON."}
+
+def load_prompt(args) -> str:
+    if not args.prompt_file:
+        return SYSTEM_PROMPT
+    prompt = Path(args.prompt_file).read_text(encoding="utf-8")
+    if not prompt.strip():
+        raise RuntimeError(f"Prompt file is empty: {args.prompt_file}")
+    return prompt
+
+def run_batch(args, judge_one):
+    """judge_one(img_path, mime, img_bytes) -> (text, tokens_in, tokens_out); 토큰 수를 모르면 None."""
+    images_dir = Path(args.images_dir)
+    out_json = Path(args.out_json)
+    out_json.parent.mkdir(parents=True, exist_ok=True)
+
+    files = sorted([p for p in images_dir.iterdir()
+                    if p.is_file() and p.suffix.lower() in SUPPORTED_EXTS])
+    if args.file_list:
+        wanted = {line.strip() for line in Path(args.file_list).read_text(encoding="utf-8").splitlines() if line.strip()}
+        files = [p for p in files if p.name in wanted]
+    if not files:
+        raise RuntimeError(f"No images found under {images_dir} (supported: {sorted(SUPPORTED_EXTS)})")
+
+    results = []
+    stream = open(args.stream_out, "a", encoding="utf-8") if args.stream_out else None
+
+    def record(entry: dict):
+        results.append(entry)
+        if stream:
+            stream.write(json.dumps(entry, ensure_ascii=False) + "\n")
+            stream.flush()
+
+    for img_path in files:
+        try:
+            mime = guess_mime(img_path)
+            if not mime:
+                record({
+                    "image": img_path.name,
+                    "result": {"accessible": None, "reason": f"Unsupported extension: {img_path.suffix}"}
+                })
+                continue
+
+            text, tokens_in, tokens_out = judge_one(img_path, mime, img_path.read_bytes())
+
+            # runner가 이 줄을 모아 실제 비용을 계산 (USAGE tokens_in=.. tokens_out=..)
+            if tokens_in is not None or tokens_out is not None:
+                print(f"USAGE tokens_in={tokens_in or 0} tokens_out={tokens_out or 0}", flush=True)
+
+            record({"image": img_path.name, "result": safe_json((text or "").strip())})
+
+        except Exception as e:
+            # runner가 실패 항목으로 표시하고 재시도 대상으로 삼음
+            record({"image": img_path.name, "error": str(e)})
+
+    if stream:
+        stream.close()
+
+    payload = {"results": results}
+    out_json.write_text(json.dumps(payload, ensure_ascii=False, indent=2), encoding="utf-8")
+    print(f"[OK] Saved: {out_json.resolve()}")
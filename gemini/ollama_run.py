@@ -0,0 +1,60 @@
+#!/usr/bin/env python3
+# Ollama(로컬 LLaVA 등) 판정: run.py 와 같은 명령줄/출력 규약 (judge_common.py)
+# 키 없음. OLLAMA_HOST(기본 127.0.0.1:11434)의 /api/chat 을 표준 라이브러리로 호출
+import os, json, base64
+import urllib.request
+from dotenv import load_dotenv
+
+from judge_common import parse_args, load_prompt, run_batch
+
+def ollama_url(host: str) -> str:
+    # OLLAMA_HOST 는 "http://host:port/", "host" 또는 "host:port" 일 수 있음
+    h = host.strip().rstrip("/")
+    if not h.startswith(("http://", "https://")):
+        h = "http://" + h
+    scheme, _, rest = h.partition("://")
+    if ":" not in rest.split("/")[0]:
+        rest = rest + ":11434"
+    return f"{scheme}://{rest}"
+
+def main():
+    load_dotenv()
+    args = parse_args(os.environ.get("OLLAMA_MODEL", "llava"))
+
+    base_url = ollama_url(os.environ.get("OLLAMA_HOST") or "127.0.0.1:11434")
+    system_prompt = load_prompt(args)
+
+    def judge_one(img_path, mime, img_bytes):
+        body = {
+            "model": args.model,
+            "messages": [
+                {"role": "system", "content": system_prompt},
+                {"role": "user", "content": "Judge this entrance.", "images": [base64.b64encode(img_bytes).decode("ascii")]},
+            ],
+            "format": "json",
+            "stream": False,
+        }
+        options = {}
+        if args.temperature is not None:
+            options["temperature"] = args.temperature
+        if args.max_tokens is not None:
+            options["num_predict"] = args.max_tokens
+        if options:
+            body["options"] = options
+
+        req = urllib.request.Request(
+            f"{base_url}/api/chat",
+            data=json.dumps(body).encode("utf-8"),
+            headers={"Content-Type": "application/json"},
+        )
+        with urllib.request.urlopen(req, timeout=args.timeout) as r:
+            resp = json.loads(r.read().decode("utf-8"))
+
+        text = (resp.get("message") or {}).get("content") or ""
+        # 로컬 모델이라 비용은 없지만 토큰 수는 그대로 보고
+        return text, resp.get("prompt_eval_count"), resp.get("eval_count")
+
+    run_batch(args, judge_one)
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+# OpenAI(GPT-4o 등) 판정: run.py 와 같은 명령줄/출력 규약 (judge_common.py)
+# 추가 패키지 없이 표준 라이브러리로 Chat Completions API 를 호출
+import os, json, base64
+import urllib.request
+from dotenv import load_dotenv
+
+from judge_common import parse_args, load_prompt, run_batch
+
+def main():
+    load_dotenv()
+    args = parse_args(os.environ.get("OPENAI_MODEL", "gpt-4o"))
+
+    api_key = os.environ.get("OPENAI_API_KEY")
+    if not api_key:
+        raise RuntimeError("OPENAI_API_KEY is not set (.env).")
+    base_url = os.environ.get("OPENAI_BASE_URL", "https://api.openai.com/v1").rstrip("/")
+
+    system_prompt = load_prompt(args)
+
+    def judge_one(img_path, mime, img_bytes):
+        data_url = f"data:{mime};base64,{base64.b64encode(img_bytes).decode('ascii')}"
+        body = {
+            "model": args.model,
+            "messages": [
+                {"role": "system", "content": system_prompt},
+                {"role": "user", "content": [{"type": "image_url", "image_url": {"url": data_url}}]},
+            ],
+            # JSON 모드: 응답이 JSON 객체로 강제됨 (그래도 safe_json이 한 번 더 보정)
+            "response_format": {"type": "json_object"},
+        }
+        if args.temperature is not None:
+            body["temperature"] = args.temperature
+        if args.max_tokens is not None:
+            body["max_tokens"] = args.max_tokens
+
+        req = urllib.request.Request(
+            f"{base_url}/chat/completions",
+            data=json.dumps(body).encode("utf-8"),
+            headers={"Content-Type": "application/json", "Authorization": f"Bearer {api_key}"},
+        )
+        with urllib.request.urlopen(req, timeout=args.timeout) as r:
+            resp = json.loads(r.read().decode("utf-8"))
+
+        text = resp["choices"][0]["message"].get("content") or ""
+        usage = resp.get("usage")
+        if not usage:
+            return text, None, None
+        return text, usage.get("prompt_tokens", 0), usage.get("completion_tokens", 0)
+
+    run_batch(args, judge_one)
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,191 @@
+// 앱 데이터(zip): 설정(최근 파일 목록 포함), 실행 기록, results/ 의 사이드카(라벨, 메모, 검토/별표)를 묶어 다른 컴퓨터로 옮김
+// 결과 JSON 과 이미지는 넣지 않음 (그건 실행 번들). manifest.json 의 version 으로 형식을 구분해 이후 버전이 옛 번들을 옮겨 읽음
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{BufReader, Read},
+    path::{Component, Path, PathBuf},
+};
+use zip::ZipArchive;
+
+use crate::{
+    bundle::{self, ZipSource},
+    write_atomic,
+};
+
+pub const FORMAT: &str = "wheel_city_ai2.app_data";
+// bump when the layout changes, and teach `migrate` the old one
+pub const VERSION: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+// taken from the config folder; settings.json also holds the recent file lists
+const CONFIG_FILES: [&str; 2] = ["settings.json", "run_history.json"];
+const SIDECAR_SUFFIX: &str = ".sidecar.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub version: u32,
+    // the app that wrote the bundle
+    pub app_version: String,
+    // local time, "YYYY-MM-DD HH:MM:SS"
+    pub created: String,
+    // paths inside the bundle, e.g. "config/settings.json", "results/result_<ts>.sidecar.json"
+    pub files: Vec<String>,
+}
+
+// what an import does with a file that already exists here
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    Skip,
+    Overwrite,
+}
+
+#[derive(Debug, Default)]
+pub struct Restored {
+    pub written: Vec<PathBuf>,
+    // existing files left alone under `Conflict::Skip`
+    pub skipped: Vec<PathBuf>,
+}
+
+impl Restored {
+    pub fn wrote(&self, name: &str) -> bool {
+        self.written.iter().any(|p| p.file_name().is_some_and(|n| n == name))
+    }
+}
+
+// `results_dir` is None when no project root is set; only the config files go in then
+pub fn export(dest: &Path, config_dir: &Path, results_dir: Option<&Path>, created: String) -> Result<Manifest> {
+    let mut entries: Vec<(ZipSource, String)> = CONFIG_FILES.iter()
+        .map(|name| config_dir.join(name))
+        .filter(|p| p.is_file())
+        .map(|p| {
+            let name = format!("config/{}", p.file_name().unwrap_or_default().to_string_lossy());
+            (ZipSource::File(p), name)
+        })
+        .collect();
+    if let Some(dir) = results_dir.filter(|d| d.is_dir()) {
+        let mut sidecars: Vec<PathBuf> = fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.to_string_lossy().ends_with(SIDECAR_SUFFIX))
+            .collect();
+        sidecars.sort();
+        for p in sidecars {
+            let name = format!("results/{}", p.file_name().unwrap_or_default().to_string_lossy());
+            entries.push((ZipSource::File(p), name));
+        }
+    }
+    if entries.is_empty() { anyhow::bail!("no settings, history or sidecar files to export"); }
+    let manifest = Manifest {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created,
+        files: entries.iter().map(|(_, name)| name.clone()).collect(),
+    };
+    entries.insert(0, (ZipSource::Bytes(serde_json::to_vec_pretty(&manifest)?), MANIFEST.to_string()));
+    bundle::write_zip(&entries, dest, &|_| {})?;
+    Ok(manifest)
+}
+
+fn open(path: &Path) -> Result<ZipArchive<BufReader<File>>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    ZipArchive::new(BufReader::new(file)).with_context(|| format!("{} is not a zip file", path.display()))
+}
+
+// shown in the confirmation before anything is written
+pub fn read_manifest(path: &Path) -> Result<Manifest> {
+    let mut zip = open(path)?;
+    let mut data = String::new();
+    zip.by_name(MANIFEST).with_context(|| format!("{} is not an app data file (no {})", path.display(), MANIFEST))?
+        .read_to_string(&mut data)?;
+    let manifest: Manifest = serde_json::from_str(&data).with_context(|| format!("failed to parse {}", MANIFEST))?;
+    if manifest.format != FORMAT { anyhow::bail!("{} is not an app data file ({})", path.display(), manifest.format); }
+    migrate(manifest)
+}
+
+// older bundle versions are upgraded here one step at a time; there is only version 1 so far
+fn migrate(manifest: Manifest) -> Result<Manifest> {
+    if manifest.version > VERSION {
+        anyhow::bail!("the file was written by app version {} (format {}); update this app to import it",
+            manifest.app_version, manifest.version);
+    }
+    Ok(manifest)
+}
+
+// where a bundle entry goes; None for entries this version doesn't restore
+fn destination(name: &Path, config_dir: &Path, results_dir: &Path) -> Option<PathBuf> {
+    let parts: Vec<String> = name.components().filter_map(|c| match c {
+        Component::Normal(s) => Some(s.to_string_lossy().to_string()),
+        _ => None,
+    }).collect();
+    match parts.as_slice() {
+        [dir, file] if dir == "config" && CONFIG_FILES.contains(&file.as_str()) => Some(config_dir.join(file)),
+        [dir, file] if dir == "results" && file.ends_with(SIDECAR_SUFFIX) => Some(results_dir.join(file)),
+        _ => None,
+    }
+}
+
+pub fn import(path: &Path, config_dir: &Path, results_dir: &Path, conflict: Conflict) -> Result<Restored> {
+    read_manifest(path)?;
+    let mut zip = open(path)?;
+    let mut restored = Restored::default();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        // enclosed_name rejects absolute paths and `..`
+        let Some(dest) = entry.enclosed_name().and_then(|n| destination(&n, config_dir, results_dir)) else { continue; };
+        if dest.exists() && conflict == Conflict::Skip {
+            restored.skipped.push(dest);
+            continue;
+        }
+        let mut data = vec![];
+        entry.read_to_end(&mut data).with_context(|| format!("failed to read {} from the bundle", entry.name()))?;
+        if let Some(dir) = dest.parent() { fs::create_dir_all(dir)?; }
+        write_atomic(&dest, &data)?;
+        restored.written.push(dest);
+    }
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_honours_the_conflict_policy() {
+        let tmp = std::env::temp_dir().join(format!("wheel_city_appdata_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&tmp);
+        let (config, results) = (tmp.join("config"), tmp.join("project").join("results"));
+        fs::create_dir_all(&config).unwrap();
+        fs::create_dir_all(&results).unwrap();
+        fs::write(config.join("settings.json"), r#"{"lang":"ko"}"#).unwrap();
+        fs::write(config.join("unrelated.log"), "x").unwrap();
+        fs::write(results.join("result_20240101_120000.json"), "{}").unwrap();
+        fs::write(results.join("result_20240101_120000.sidecar.json"), r#"{"label":"survey"}"#).unwrap();
+
+        let zip = tmp.join("app_data.zip");
+        let manifest = export(&zip, &config, Some(&results), "2024-01-01 12:00:00".to_string()).unwrap();
+        assert_eq!(manifest.files, ["config/settings.json", "results/result_20240101_120000.sidecar.json"]);
+        assert_eq!(read_manifest(&zip).unwrap(), manifest);
+
+        let (config2, results2) = (tmp.join("config2"), tmp.join("project2").join("results"));
+        fs::create_dir_all(&config2).unwrap();
+        fs::write(config2.join("settings.json"), r#"{"lang":"en"}"#).unwrap();
+        let restored = import(&zip, &config2, &results2, Conflict::Skip).unwrap();
+        assert_eq!(restored.skipped, [config2.join("settings.json")]);
+        assert_eq!(restored.written, [results2.join("result_20240101_120000.sidecar.json")]);
+        assert_eq!(fs::read_to_string(config2.join("settings.json")).unwrap(), r#"{"lang":"en"}"#);
+        assert!(!results2.join("result_20240101_120000.json").exists());
+
+        let restored = import(&zip, &config2, &results2, Conflict::Overwrite).unwrap();
+        assert!(restored.wrote("settings.json") && restored.skipped.is_empty());
+        assert_eq!(fs::read_to_string(config2.join("settings.json")).unwrap(), r#"{"lang":"ko"}"#);
+
+        // a bundle from a newer app is refused before anything is written
+        let newer = Manifest { version: VERSION + 1, ..manifest };
+        bundle::write_zip(&[(ZipSource::Bytes(serde_json::to_vec(&newer).unwrap()), MANIFEST.to_string())], &zip, &|_| {}).unwrap();
+        assert!(import(&zip, &config2, &results2, Conflict::Overwrite).is_err());
+        let _ = fs::remove_dir_all(&tmp);
+    }
+}
@@ -0,0 +1,340 @@
+// 앱 로그: 줄 단위 항목(시간/레벨/출처)으로 보관하고, 개수 기준으로 오래된 항목을 버림
+use chrono::{DateTime, Local};
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+// oldest entries are dropped past this many lines
+pub const MAX_LOG_ENTRIES: usize = 5000;
+// a single runaway line (e.g. a base64 dump) is cut to this many chars
+const MAX_LINE_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    App,
+    Yolo,
+    Gemini,
+}
+
+impl LogSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            LogSource::App => "App",
+            LogSource::Yolo => "YOLO",
+            LogSource::Gemini => "Gemini",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: LogLevel,
+    pub source: LogSource,
+    // line without the source prefix
+    pub text: String,
+    // byte ranges of absolute paths inside `text`, found when the line is appended
+    pub paths: Vec<Range<usize>>,
+}
+
+impl LogEntry {
+    // "12:03:44 [YOLO] image 3/10 ..."
+    pub fn display(&self) -> String {
+        format!("{}{}", self.prefix(), self.text)
+    }
+
+    // the part of `display` before `text`
+    pub fn prefix(&self) -> String {
+        match self.source {
+            LogSource::App => format!("{} ", self.time.format("%H:%M:%S")),
+            src => format!("{} [{}] ", self.time.format("%H:%M:%S"), src.label()),
+        }
+    }
+
+    // `text` split into (segment, is_path) pieces for rendering
+    pub fn segments(&self) -> Vec<(&str, bool)> {
+        let mut out = vec![];
+        let mut at = 0;
+        for r in &self.paths {
+            if r.start > at { out.push((&self.text[at..r.start], false)); }
+            out.push((&self.text[r.clone()], true));
+            at = r.end;
+        }
+        if at < self.text.len() { out.push((&self.text[at..], false)); }
+        out
+    }
+
+    // case-insensitive; `needle` must already be lowercase
+    pub fn matches(&self, needle: &str) -> bool {
+        !needle.is_empty() && self.text.to_lowercase().contains(needle)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LogFilter {
+    pub info: bool,
+    pub warn: bool,
+    pub error: bool,
+    pub app: bool,
+    pub yolo: bool,
+    pub gemini: bool,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self { info: true, warn: true, error: true, app: true, yolo: true, gemini: true }
+    }
+}
+
+impl LogFilter {
+    pub fn accepts(&self, e: &LogEntry) -> bool {
+        let level = match e.level {
+            LogLevel::Info => self.info,
+            LogLevel::Warn => self.warn,
+            LogLevel::Error => self.error,
+        };
+        let source = match e.source {
+            LogSource::App => self.app,
+            LogSource::Yolo => self.yolo,
+            LogSource::Gemini => self.gemini,
+        };
+        level && source
+    }
+}
+
+#[derive(Default)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+}
+
+impl LogBuffer {
+    // `s` may hold several lines; each becomes its own entry. Returns how many were added.
+    pub fn push(&mut self, s: &str) -> usize {
+        let now = Local::now();
+        let mut added = 0;
+        for line in s.lines() {
+            if line.trim().is_empty() { continue; }
+            self.entries.push_back(parse_line(line, now));
+            added += 1;
+        }
+        while self.entries.len() > MAX_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        added.min(self.entries.len())
+    }
+
+    // the `n` most recent entries, oldest first
+    pub fn last(&self, n: usize) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().skip(self.entries.len().saturating_sub(n))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> Option<&LogEntry> {
+        self.entries.get(i)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+}
+
+// "Save log...": the entries with their date, since a saved log outlives the day it was written
+pub fn save(path: &Path, entries: &[LogEntry]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for e in entries {
+        writeln!(out, "{} {}", e.time.format("%Y-%m-%d"), e.display())?;
+    }
+    out.flush()
+}
+
+// per-run log file under .runner_work/logs; survives the in-memory cap and app crashes
+pub struct RunLogFile {
+    path: PathBuf,
+    out: BufWriter<File>,
+    last_flush: Instant,
+}
+
+impl RunLogFile {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        if let Some(dir) = path.parent() { std::fs::create_dir_all(dir)?; }
+        let out = BufWriter::new(File::create(path)?);
+        Ok(Self { path: path.to_path_buf(), out, last_flush: Instant::now() })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write<'a>(&mut self, entries: impl Iterator<Item = &'a LogEntry>) -> io::Result<()> {
+        let mut urgent = false;
+        for e in entries {
+            writeln!(self.out, "{}", e.display())?;
+            urgent |= e.level == LogLevel::Error;
+        }
+        // errors go straight to disk; everything else at most a second late
+        if urgent || self.last_flush.elapsed() >= Duration::from_secs(1) {
+            self.out.flush()?;
+            self.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str, time: DateTime<Local>) -> LogEntry {
+    let (source, rest) = if let Some(r) = line.strip_prefix("[YOLO] ") {
+        (LogSource::Yolo, r)
+    } else if let Some(r) = line.strip_prefix("[GEMINI] ") {
+        (LogSource::Gemini, r)
+    } else {
+        (LogSource::App, line)
+    };
+    let level = if rest.starts_with("[stderr]") || rest.starts_with("[ERROR]") || rest.starts_with("[TIMEOUT]") {
+        LogLevel::Error
+    } else if rest.starts_with("[WARN]") {
+        LogLevel::Warn
+    } else {
+        LogLevel::Info
+    };
+    let text = truncate_chars(rest, MAX_LINE_CHARS);
+    let paths = find_paths(&text);
+    LogEntry { time, level, source, text, paths }
+}
+
+// how many space-separated continuations are tried when a path may contain spaces
+const MAX_PATH_WORDS: usize = 8;
+
+// absolute paths ("/home/..", "C:\\..", "\\\\server\\..") in a log line. Paths may contain spaces,
+// so each candidate is extended word by word and the longest one that exists on disk wins;
+// if none exists the first word is used, so a deleted file still shows up as a (dead) link.
+pub fn find_paths(text: &str) -> Vec<Range<usize>> {
+    let mut out = vec![];
+    let mut pos = 0;
+    while pos < text.len() {
+        let Some(start) = next_path_start(text, pos) else { break; };
+        let mut ends = vec![];
+        let mut word_end = start;
+        for _ in 0..MAX_PATH_WORDS {
+            let rest = &text[word_end..];
+            let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            word_end += len;
+            let end = start + trim_trailing_punct(&text[start..word_end]).len();
+            if end > start { ends.push(end); }
+            if word_end >= text.len() { break; }
+            // skip the whitespace run and keep extending
+            let ws = text[word_end..].find(|c: char| !c.is_whitespace()).unwrap_or(text.len() - word_end);
+            if ws != 1 { break; }
+            word_end += ws;
+        }
+        let Some(&first) = ends.first() else { pos = start + 1; continue; };
+        let end = ends.iter().rev().copied()
+            .find(|&e| Path::new(&text[start..e]).exists())
+            .unwrap_or(first);
+        out.push(start..end);
+        pos = end;
+    }
+    out
+}
+
+fn next_path_start(text: &str, from: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    (from..text.len()).find(|&i| {
+        let boundary = i == 0 || matches!(bytes[i - 1], b' ' | b'\t' | b'(' | b'[' | b'\'' | b'"' | b'=' | b',');
+        if !boundary { return false; }
+        let rest = &bytes[i..];
+        match rest {
+            [b'/', c, ..] => *c != b'/' && !c.is_ascii_whitespace(),
+            [b'\\', b'\\', c, ..] => c.is_ascii_alphanumeric(),
+            [d, b':', b'\\' | b'/', ..] => d.is_ascii_alphabetic(),
+            _ => false,
+        }
+    })
+}
+
+fn trim_trailing_punct(s: &str) -> &str {
+    s.trim_end_matches([':', ',', ';', '.', ')', ']', '\'', '"'])
+}
+
+// cuts on a char boundary, so multi-byte text (Korean) never panics
+fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((end, _)) => format!("{}…", &s[..end]),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn korean_text_is_bounded_without_panicking() {
+        let mut log = LogBuffer::default();
+        let line = "[GEMINI] 경사로가 있어 휠체어 접근 가능\n".repeat(MAX_LOG_ENTRIES + 10);
+        log.push(&line);
+        assert_eq!(log.len(), MAX_LOG_ENTRIES);
+
+        // one huge line whose cut point falls inside a 3-byte character
+        log.push(&format!("[ERROR] a{}", "한".repeat(MAX_LINE_CHARS * 2)));
+        let last = log.get(log.len() - 1).unwrap();
+        assert_eq!(last.level, LogLevel::Error);
+        assert_eq!(last.text.chars().count(), MAX_LINE_CHARS + 1);
+    }
+
+    #[test]
+    fn saved_log_has_every_line_with_its_date() {
+        let mut log = LogBuffer::default();
+        log.push("[STEP] running YOLO inference...\n[ERROR] 경사로 판정 실패\n");
+        let entries: Vec<LogEntry> = log.iter().cloned().collect();
+        let path = std::env::temp_dir().join(format!("wheel_city_log_save_test_{}.txt", std::process::id()));
+        save(&path, &entries).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, e) in lines.iter().zip(&entries) {
+            assert_eq!(*line, format!("{} {}", e.time.format("%Y-%m-%d"), e.display()));
+        }
+        assert!(lines[1].ends_with("[ERROR] 경사로 판정 실패"));
+        assert!(save(&std::env::temp_dir().join("no such dir").join("log.txt"), &entries).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn paths_with_spaces_and_korean_are_found() {
+        let dir = std::env::temp_dir().join("wheel city 로그 test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("결과 1.json");
+        std::fs::write(&file, "{}").unwrap();
+
+        let line = format!("[DONE] saved: {} (3 entries)", file.display());
+        let paths = find_paths(&line);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(&line[paths[0].clone()], file.to_string_lossy());
+
+        // missing paths still link, up to the first space
+        let line = "[WARN] source missing, skip: /no/such dir/x.jpg";
+        let paths = find_paths(line);
+        assert_eq!(&line[paths[0].clone()], "/no/such");
+        assert!(find_paths("image 3/10 done").is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
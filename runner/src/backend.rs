@@ -0,0 +1,163 @@
+// 판정 백엔드: bbox 이미지에 접근성 판정을 내리는 모델(Gemini, GPT-4o, Ollama 의 LLaVA 등)마다
+// 명령줄, 필요한 환경 변수, 실행 전 점검을 구현. 새 백엔드는 자기 스크립트(script 재정의) + 이 트레이트 구현 + BACKENDS 항목 하나로 추가
+use anyhow::{Context, Result};
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+use crate::{pipeline::GeminiParams, preflight};
+
+pub trait JudgmentBackend: Sync {
+    // stable name kept in settings, sidecars and result files
+    fn id(&self) -> &'static str;
+    fn label(&self) -> &'static str;
+    // model names offered in the UI; the model field stays free text
+    fn models(&self) -> &'static [&'static str];
+    // variables the script reads (API keys, server address)
+    fn env_vars(&self) -> &'static [&'static str];
+
+    fn script(&self, project_root: &Path) -> PathBuf {
+        project_root.join("gemini").join("run.py")
+    }
+
+    // flags that select this backend in the script; part of the cache key
+    fn args(&self) -> Vec<String> {
+        vec![]
+    }
+
+    // the command without the per-run flags (--images_dir, --out_json, ...)
+    fn command(&self, python: &str, project_root: &Path) -> Command {
+        let mut cmd = Command::new(python);
+        cmd.arg(self.script(project_root)).args(self.args());
+        cmd
+    }
+
+    // what was found, or why a run would fail
+    fn preflight(&self, project_root: &Path) -> Result<String>;
+
+    // recorded in the result JSON next to the verdicts
+    fn identity(&self, params: &GeminiParams) -> serde_json::Value {
+        let model = params.model.trim();
+        serde_json::json!({
+            "id": self.id(),
+            "label": self.label(),
+            "model": if model.is_empty() { None } else { Some(model) },
+        })
+    }
+}
+
+pub struct Gemini;
+pub struct OpenAi;
+pub struct Ollama;
+
+pub static BACKENDS: &[&dyn JudgmentBackend] = &[&Gemini, &OpenAi, &Ollama];
+
+// unknown ids (e.g. from a newer settings file) fall back to Gemini
+pub fn find(id: &str) -> &'static dyn JudgmentBackend {
+    BACKENDS.iter().copied().find(|b| b.id() == id).unwrap_or(&Gemini)
+}
+
+fn script_exists(backend: &dyn JudgmentBackend, project_root: &Path) -> Result<()> {
+    let script = backend.script(project_root);
+    if !script.is_file() { anyhow::bail!("{} script not found: {}", backend.label(), script.display()); }
+    Ok(())
+}
+
+impl JudgmentBackend for Gemini {
+    fn id(&self) -> &'static str { "gemini" }
+    fn label(&self) -> &'static str { "Gemini" }
+    fn models(&self) -> &'static [&'static str] { &GeminiParams::MODELS }
+    fn env_vars(&self) -> &'static [&'static str] { &["GOOGLE_API_KEY"] }
+
+    fn preflight(&self, project_root: &Path) -> Result<String> {
+        script_exists(self, project_root)?;
+        preflight::env_key(project_root, "GOOGLE_API_KEY")
+    }
+}
+
+impl JudgmentBackend for OpenAi {
+    fn id(&self) -> &'static str { "openai" }
+    fn label(&self) -> &'static str { "OpenAI (GPT-4o)" }
+    fn models(&self) -> &'static [&'static str] { &["gpt-4o", "gpt-4o-mini"] }
+    fn env_vars(&self) -> &'static [&'static str] { &["OPENAI_API_KEY", "OPENAI_BASE_URL"] }
+
+    fn script(&self, project_root: &Path) -> PathBuf {
+        project_root.join("gemini").join("openai_run.py")
+    }
+
+    fn preflight(&self, project_root: &Path) -> Result<String> {
+        script_exists(self, project_root)?;
+        preflight::env_key(project_root, "OPENAI_API_KEY")
+    }
+}
+
+const OLLAMA_DEFAULT_HOST: &str = "127.0.0.1:11434";
+
+impl JudgmentBackend for Ollama {
+    fn id(&self) -> &'static str { "ollama" }
+    fn label(&self) -> &'static str { "Ollama (local LLaVA)" }
+    fn models(&self) -> &'static [&'static str] { &["llava", "llava:13b", "llama3.2-vision"] }
+    fn env_vars(&self) -> &'static [&'static str] { &["OLLAMA_HOST"] }
+
+    fn script(&self, project_root: &Path) -> PathBuf {
+        project_root.join("gemini").join("ollama_run.py")
+    }
+
+    // no key; the server has to be up
+    fn preflight(&self, project_root: &Path) -> Result<String> {
+        script_exists(self, project_root)?;
+        let host = std::env::var("OLLAMA_HOST").ok().filter(|h| !h.trim().is_empty())
+            .unwrap_or_else(|| OLLAMA_DEFAULT_HOST.to_string());
+        let addr = ollama_addr(&host);
+        let sock = addr.to_socket_addrs().ok().and_then(|mut a| a.next())
+            .with_context(|| format!("cannot resolve the Ollama host {}", addr))?;
+        TcpStream::connect_timeout(&sock, Duration::from_secs(2))
+            .with_context(|| format!("Ollama is not reachable at {} (run `ollama serve`)", addr))?;
+        Ok(format!("Ollama server at {}", addr))
+    }
+}
+
+// OLLAMA_HOST may be "http://host:port/", "host" or "host:port" (same rule as ollama_run.py)
+fn ollama_addr(host: &str) -> String {
+    let h = host.trim();
+    let h = h.strip_prefix("http://").or_else(|| h.strip_prefix("https://")).unwrap_or(h);
+    let h = h.trim_end_matches('/');
+    if h.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+        h.to_string()
+    } else {
+        format!("{}:11434", h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backends_are_found_by_id_and_run_their_own_script() {
+        assert_eq!(find("openai").id(), "openai");
+        assert_eq!(find("no-such-backend").id(), "gemini");
+        let ids: Vec<&str> = BACKENDS.iter().map(|b| b.id()).collect();
+        assert_eq!(ids, ["gemini", "openai", "ollama"]);
+
+        let args = |id: &str| -> Vec<String> {
+            find(id).command("python3", Path::new("/proj")).get_args().map(|a| a.to_string_lossy().to_string()).collect()
+        };
+        assert_eq!(args("gemini"), [Path::new("/proj/gemini/run.py").to_string_lossy().as_ref()]);
+        assert_eq!(args("ollama"), [Path::new("/proj/gemini/ollama_run.py").to_string_lossy().as_ref()]);
+        // the scripts ship with the repo
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        for b in BACKENDS {
+            assert!(b.script(root).is_file(), "{}", b.script(root).display());
+        }
+
+        assert_eq!(ollama_addr("http://localhost:11434/"), "localhost:11434");
+        assert_eq!(ollama_addr("gpu-box"), "gpu-box:11434");
+
+        let params = GeminiParams { model: "gemini-1.5-pro".to_string(), ..Default::default() };
+        assert_eq!(find("gemini").identity(&params)["model"], "gemini-1.5-pro");
+        assert_eq!(find("gemini").identity(&GeminiParams::default())["model"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,232 @@
+// 실행 번들(zip): 결과 JSON + 사이드카들 + bbox 폴더 전체를 프로젝트와 같은 경로 구조로 묶음
+//   results/result_<ts>.json, results/result_<ts>.*.json, .runner_work/bbox/<ts>/..., .runner_work/bbox/<ts>.classes.json
+// 가져오기는 같은 경로로 풀기만 하면 되고, 파일은 하나씩 스트리밍으로 씀 (메모리에 모으지 않음)
+use anyhow::{Context, Result};
+use eframe::egui;
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Component, Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::classes;
+
+// a progress line every this many files
+const PROGRESS_EVERY: usize = 100;
+
+pub enum BundleEvent {
+    Log(String),
+    // export: the bundle written; import: the restored result JSON
+    Finished(Result<PathBuf>),
+}
+
+// (file on disk, path inside the bundle)
+fn bundle_entries(json: &Path, bbox_dir: Option<&Path>) -> Result<Vec<(PathBuf, String)>> {
+    let name = json.file_name().with_context(|| "result JSON has no file name")?.to_string_lossy().to_string();
+    let stem = json.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let mut out = vec![(json.to_path_buf(), format!("results/{}", name))];
+    // result_<ts>.sidecar.json and any other per-result file next to it
+    let mut extras: Vec<PathBuf> = json.parent().map(fs::read_dir).transpose()?.into_iter().flatten().flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.file_name().is_some_and(|n| {
+            let n = n.to_string_lossy();
+            n.starts_with(&format!("{}.", stem)) && n != name
+        }))
+        .collect();
+    extras.sort();
+    for p in extras {
+        let n = p.file_name().unwrap_or_default().to_string_lossy().to_string();
+        out.push((p, format!("results/{}", n)));
+    }
+    // named after the result, where the app looks for it on load (see `guess_bbox_dir`)
+    let Some(bbox) = bbox_dir.filter(|d| d.is_dir()) else { return Ok(out); };
+    let ts = stem.strip_prefix("result_").unwrap_or(&stem);
+    let prefix = format!(".runner_work/bbox/{}", ts);
+    let mut stack = vec![bbox.to_path_buf()];
+    let mut files = vec![];
+    while let Some(dir) = stack.pop() {
+        for e in fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))?.flatten() {
+            let p = e.path();
+            if p.is_dir() { stack.push(p); } else { files.push(p); }
+        }
+    }
+    files.sort();
+    for p in files {
+        let rel = p.strip_prefix(bbox).unwrap_or(&p);
+        let rel: Vec<String> = rel.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+        out.push((p, format!("{}/{}", prefix, rel.join("/"))));
+    }
+    let class_sidecar = classes::sidecar_path(bbox);
+    if class_sidecar.is_file() {
+        out.push((class_sidecar, format!("{}.classes.json", prefix)));
+    }
+    Ok(out)
+}
+
+pub fn write_bundle(json: &Path, bbox_dir: Option<&Path>, dest: &Path, log: &dyn Fn(String)) -> Result<usize> {
+    let entries: Vec<(ZipSource, String)> = bundle_entries(json, bbox_dir)?.into_iter()
+        .map(|(path, name)| (ZipSource::File(path), name))
+        .collect();
+    write_zip(&entries, dest, log)
+}
+
+// what a zip entry holds
+pub enum ZipSource {
+    File(PathBuf),
+    // generated in memory (the CSV and HTML page of "Export starred")
+    Bytes(Vec<u8>),
+}
+
+pub fn write_zip(entries: &[(ZipSource, String)], dest: &Path, log: &dyn Fn(String)) -> Result<usize> {
+    // written next to the destination and renamed, so a failed export leaves no half zip behind
+    let tmp = dest.with_extension("zip.tmp");
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(&tmp).with_context(|| format!("failed to create {}", tmp.display()))?));
+    let res = (|| -> Result<()> {
+        for (i, (src, name)) in entries.iter().enumerate() {
+            // images are already compressed
+            let text = [".json", ".txt", ".csv", ".html"].iter().any(|ext| name.ends_with(ext));
+            let method = if text { CompressionMethod::Deflated } else { CompressionMethod::Stored };
+            zip.start_file(name.as_str(), SimpleFileOptions::default().compression_method(method))?;
+            match src {
+                ZipSource::File(path) => {
+                    let mut src = BufReader::new(File::open(path).with_context(|| format!("failed to open {}", path.display()))?);
+                    io::copy(&mut src, &mut zip).with_context(|| format!("failed to add {}", path.display()))?;
+                }
+                ZipSource::Bytes(data) => zip.write_all(data).with_context(|| format!("failed to add {}", name))?,
+            }
+            if (i + 1) % PROGRESS_EVERY == 0 {
+                log(format!("[INFO] bundle: {}/{} files\n", i + 1, entries.len()));
+            }
+        }
+        zip.finish()?;
+        Ok(())
+    })();
+    if let Err(e) = res {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    fs::rename(&tmp, dest).with_context(|| format!("failed to write {}", dest.display()))?;
+    Ok(entries.len())
+}
+
+// extracts a bundle into `project_root` and returns the result JSON; only `results/` and
+// `.runner_work/bbox/` entries are taken, and an existing result is never overwritten
+pub fn extract_bundle(bundle: &Path, project_root: &Path, log: &dyn Fn(String)) -> Result<PathBuf> {
+    let file = File::open(bundle).with_context(|| format!("failed to open {}", bundle.display()))?;
+    let mut zip = ZipArchive::new(BufReader::new(file)).with_context(|| format!("{} is not a zip file", bundle.display()))?;
+    let mut plan = vec![];
+    let mut result_json = None;
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)?;
+        if entry.is_dir() { continue; }
+        // enclosed_name rejects absolute paths and `..`
+        let Some(rel) = entry.enclosed_name() else {
+            log(format!("[WARN] bundle: skipped unsafe entry {}\n", entry.name()));
+            continue;
+        };
+        let parts: Vec<String> = rel.components().filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().to_string()),
+            _ => None,
+        }).collect();
+        let allowed = matches!(parts.as_slice(), [r, _] if r == "results")
+            || matches!(parts.as_slice(), [w, b, ..] if w == ".runner_work" && b == "bbox");
+        if !allowed {
+            log(format!("[WARN] bundle: skipped {}\n", entry.name()));
+            continue;
+        }
+        if let [_, name] = parts.as_slice() {
+            // result_<ts>.json, not one of its result_<ts>.<kind>.json sidecars
+            if parts[0] == "results" && name.starts_with("result_") && name.ends_with(".json") && name.matches('.').count() == 1 {
+                result_json = Some(project_root.join(&rel));
+            }
+        }
+        plan.push((i, project_root.join(rel)));
+    }
+    let json = result_json.with_context(|| "no results/result_*.json in the bundle")?;
+    if json.exists() { anyhow::bail!("{} already exists; remove it first to import this bundle again", json.display()); }
+    for (n, (i, dest)) in plan.iter().enumerate() {
+        let mut entry = zip.by_index(*i)?;
+        if let Some(dir) = dest.parent() { fs::create_dir_all(dir)?; }
+        let mut out = BufWriter::new(File::create(dest).with_context(|| format!("failed to create {}", dest.display()))?);
+        io::copy(&mut entry, &mut out).with_context(|| format!("failed to extract {}", dest.display()))?;
+        if (n + 1) % PROGRESS_EVERY == 0 {
+            log(format!("[INFO] bundle: {}/{} files\n", n + 1, plan.len()));
+        }
+    }
+    Ok(json)
+}
+
+fn spawn_with(ctx: egui::Context, work: impl FnOnce(&dyn Fn(String)) -> Result<PathBuf> + Send + 'static) -> Receiver<BundleEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let log = |s: String| {
+            let _ = tx.send(BundleEvent::Log(s));
+            ctx.request_repaint();
+        };
+        let res = work(&log);
+        let _ = tx.send(BundleEvent::Finished(res));
+        ctx.request_repaint();
+    });
+    rx
+}
+
+pub fn spawn_export(json: PathBuf, bbox_dir: Option<PathBuf>, dest: PathBuf, ctx: egui::Context) -> Receiver<BundleEvent> {
+    spawn_with(ctx, move |log| {
+        let n = write_bundle(&json, bbox_dir.as_deref(), &dest, log)?;
+        log(format!("[INFO] bundle: {} files\n", n));
+        Ok(dest)
+    })
+}
+
+pub fn spawn_zip(entries: Vec<(ZipSource, String)>, dest: PathBuf, ctx: egui::Context) -> Receiver<BundleEvent> {
+    spawn_with(ctx, move |log| {
+        let n = write_zip(&entries, &dest, log)?;
+        log(format!("[INFO] zip: {} files\n", n));
+        Ok(dest)
+    })
+}
+
+pub fn spawn_import(bundle: PathBuf, project_root: PathBuf, ctx: egui::Context) -> Receiver<BundleEvent> {
+    spawn_with(ctx, move |log| extract_bundle(&bundle, &project_root, log))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_round_trip_restores_the_layout() {
+        let tmp = std::env::temp_dir().join("wheel_city_bundle_test");
+        let _ = fs::remove_dir_all(&tmp);
+        let src = tmp.join("src");
+        let bbox = src.join(".runner_work").join("bbox").join("20240101_120000");
+        fs::create_dir_all(bbox.join("labels")).unwrap();
+        fs::create_dir_all(src.join("results")).unwrap();
+        let json = src.join("results").join("result_20240101_120000.json");
+        fs::write(&json, r#"{"results":[]}"#).unwrap();
+        fs::write(src.join("results").join("result_20240101_120000.sidecar.json"), "{}").unwrap();
+        fs::write(src.join("results").join("result_20240102_000000.json"), "{}").unwrap();
+        fs::write(bbox.join("a.jpg"), b"jpeg").unwrap();
+        fs::write(bbox.join("labels").join("a.txt"), "0 0.5 0.5 0.1 0.1").unwrap();
+        fs::write(classes::sidecar_path(&bbox), "{}").unwrap();
+
+        let dest = tmp.join("run.zip");
+        let n = write_bundle(&json, Some(&bbox), &dest, &|_| {}).unwrap();
+        assert_eq!(n, 5);
+
+        let dst = tmp.join("dst");
+        let restored = extract_bundle(&dest, &dst, &|_| {}).unwrap();
+        assert_eq!(restored, dst.join("results").join("result_20240101_120000.json"));
+        assert!(dst.join("results").join("result_20240101_120000.sidecar.json").is_file());
+        assert!(!dst.join("results").join("result_20240102_000000.json").exists());
+        let bbox2 = dst.join(".runner_work").join("bbox").join("20240101_120000");
+        assert_eq!(fs::read_to_string(bbox2.join("labels").join("a.txt")).unwrap(), "0 0.5 0.5 0.1 0.1");
+        assert!(classes::sidecar_path(&bbox2).is_file());
+        // never over an existing result
+        assert!(extract_bundle(&dest, &dst, &|_| {}).is_err());
+        let _ = fs::remove_dir_all(&tmp);
+    }
+}
@@ -0,0 +1,113 @@
+// Gemini 판정 캐시 (.runner_work/gemini_cache.db): bbox 이미지 내용 + 프롬프트 + 모델 설정이 같으면
+// Gemini 에 다시 묻지 않고 저장된 판정을 씀
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub fn cache_path(work_dir: &Path) -> PathBuf {
+    work_dir.join("gemini_cache.db")
+}
+
+// bytes on disk, WAL files included
+pub fn size_on_disk(path: &Path) -> u64 {
+    [path.to_path_buf(), wal_path(path, "-wal"), wal_path(path, "-shm")].iter()
+        .filter_map(|p| fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+pub fn clear(path: &Path) -> Result<()> {
+    for p in [path.to_path_buf(), wal_path(path, "-wal"), wal_path(path, "-shm")] {
+        if p.exists() { fs::remove_file(&p).with_context(|| format!("failed to delete {}", p.display()))?; }
+    }
+    Ok(())
+}
+
+fn wal_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(suffix);
+    PathBuf::from(s)
+}
+
+// everything besides the image that changes Gemini's answer
+pub struct CacheScope {
+    prompt_hash: String,
+    model: String,
+}
+
+impl CacheScope {
+    // `prompt` is the template text followed by the script's source (its own prompt); `model` the
+    // backend id, model flags and extra args
+    pub fn new(prompt: &str, model: &str) -> Self {
+        Self { prompt_hash: blake3::hash(prompt.as_bytes()).to_hex().to_string(), model: model.to_string() }
+    }
+
+    pub fn key(&self, image_bytes: &[u8]) -> String {
+        format!("{}|{}|{}", blake3::hash(image_bytes).to_hex(), self.prompt_hash, self.model)
+    }
+}
+
+pub struct JudgeCache {
+    conn: Connection,
+}
+
+impl JudgeCache {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() { fs::create_dir_all(dir).ok(); }
+        let conn = Connection::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS verdicts (
+                 key        TEXT PRIMARY KEY,
+                 result     TEXT NOT NULL,
+                 created_at TEXT NOT NULL DEFAULT (datetime('now'))
+             );",
+        ).with_context(|| "failed to create the cache table")?;
+        Ok(Self { conn })
+    }
+
+    // the stored `result` object of an entry
+    pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let text: Option<String> = self.conn
+            .query_row("SELECT result FROM verdicts WHERE key = ?1", params![key], |r| r.get(0))
+            .optional().ok()?;
+        serde_json::from_str(&text?).ok()
+    }
+
+    pub fn put(&self, key: &str, result: &serde_json::Value) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO verdicts (key, result) VALUES (?1, ?2)",
+            params![key, result.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts_are_found_only_for_the_same_image_prompt_and_model() {
+        let dir = std::env::temp_dir().join("wheel_city_cache_test");
+        let _ = fs::remove_dir_all(&dir);
+        let path = cache_path(&dir);
+        let cache = JudgeCache::open(&path).unwrap();
+        let scope = CacheScope::new("", "--model gemini-1.5-flash");
+        let verdict = serde_json::json!({ "accessible": true, "reason": "ramp" });
+        cache.put(&scope.key(b"image one"), &verdict).unwrap();
+
+        assert_eq!(cache.get(&scope.key(b"image one")), Some(verdict));
+        assert_eq!(cache.get(&scope.key(b"image two")), None);
+        assert_eq!(cache.get(&CacheScope::new("new prompt", "--model gemini-1.5-flash").key(b"image one")), None);
+        assert_eq!(cache.get(&CacheScope::new("", "--model gemini-1.5-pro").key(b"image one")), None);
+        assert!(size_on_disk(&path) > 0);
+
+        drop(cache);
+        clear(&path).unwrap();
+        assert_eq!(size_on_disk(&path), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,62 @@
+// 판정 결과 막대 그래프 (egui painter로 직접 그림)
+use eframe::egui::{self, Align2, Color32, FontId, Rounding, Sense, Stroke, Vec2};
+
+// accessible / not accessible / unknown counts under a label (a run, or "current")
+#[derive(Debug, Clone)]
+pub struct BarGroup {
+    pub label: String,
+    pub counts: [usize; 3],
+}
+
+const BAR_H: f32 = 18.0;
+const LABEL_W: f32 = 130.0;
+
+// one bar per verdict for a single group; one stacked bar per group otherwise
+pub fn verdict_bars(ui: &mut egui::Ui, groups: &[BarGroup], colors: [Color32; 3], names: [&str; 3]) {
+    match groups {
+        [] => {}
+        [single] => {
+            let max = single.counts.iter().copied().max().unwrap_or(0).max(1);
+            for i in 0..3 {
+                bar_row(ui, names[i], &[(single.counts[i], colors[i])], max);
+            }
+        }
+        _ => {
+            let max = groups.iter().map(|g| g.counts.iter().sum::<usize>()).max().unwrap_or(0).max(1);
+            for g in groups {
+                let segs: Vec<(usize, Color32)> = g.counts.iter().copied().zip(colors).collect();
+                bar_row(ui, &g.label, &segs, max);
+            }
+        }
+    }
+}
+
+fn bar_row(ui: &mut egui::Ui, label: &str, segments: &[(usize, Color32)], max: usize) {
+    let width = ui.available_width().max(LABEL_W + 60.0);
+    let (rect, resp) = ui.allocate_exact_size(Vec2::new(width, BAR_H), Sense::hover());
+    let painter = ui.painter_at(rect);
+    let text_color = ui.visuals().text_color();
+    let font = FontId::proportional(12.0);
+    painter.text(rect.left_center(), Align2::LEFT_CENTER, label, font.clone(), text_color);
+
+    let bar_w = rect.width() - LABEL_W - 8.0;
+    let mut x = rect.left() + LABEL_W;
+    for &(n, color) in segments {
+        if n == 0 { continue; }
+        let w = bar_w * n as f32 / max as f32;
+        let seg = egui::Rect::from_min_size(egui::pos2(x, rect.top() + 2.0), Vec2::new(w, BAR_H - 4.0));
+        painter.rect(seg, Rounding::same(2.0), color, Stroke::NONE);
+        // count inside the segment when it fits, else right after the bar
+        let txt = n.to_string();
+        if w >= 8.0 * txt.len() as f32 + 6.0 {
+            painter.text(seg.center(), Align2::CENTER_CENTER, txt, font.clone(), Color32::WHITE);
+        } else if segments.len() == 1 {
+            painter.text(seg.right_center() + Vec2::new(4.0, 0.0), Align2::LEFT_CENTER, txt, font.clone(), text_color);
+        }
+        x += w;
+    }
+    let total: usize = segments.iter().map(|s| s.0).sum();
+    if segments.len() > 1 {
+        resp.on_hover_text(format!("{}: {} images", label, total));
+    }
+}
@@ -0,0 +1,211 @@
+// 실행 중단(절전, 크래시 등) 후 이어서 하기 위한 체크포인트 (.runner_work/checkpoint.json)
+// 완료되었거나 취소된 실행은 체크포인트를 지움
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{exif_info::ImageMeta, write_atomic};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckpointStage {
+    // inputs are in `run_input`
+    Copied,
+    // YOLO finished; every input has a bbox image
+    Detected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub ts: String,
+    pub weights: PathBuf,
+    pub run_input: PathBuf,
+    pub bbox_dir: PathBuf,
+    pub out_json: PathBuf,
+    // Gemini's per-image JSONL; the verdicts it holds are not requested again
+    pub stream_path: PathBuf,
+    // copied input names
+    pub images: Vec<String>,
+    pub gps: BTreeMap<String, (f64, f64)>,
+    #[serde(default)]
+    pub meta: BTreeMap<String, ImageMeta>,
+    // "Run label" of the interrupted run, given back to the resumed one
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    pub stage: CheckpointStage,
+}
+
+impl Checkpoint {
+    pub fn path_in(work_dir: &Path) -> PathBuf {
+        work_dir.join("checkpoint.json")
+    }
+
+    pub fn load(work_dir: &Path) -> Option<Self> {
+        let data = fs::read_to_string(Self::path_in(work_dir)).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    pub fn save(&self, work_dir: &Path) -> Result<()> {
+        write_atomic(&Self::path_in(work_dir), serde_json::to_string_pretty(self)?.as_bytes())
+            .with_context(|| "failed to write checkpoint")
+    }
+
+    pub fn clear(work_dir: &Path) {
+        let _ = fs::remove_file(Self::path_in(work_dir));
+    }
+
+    // verdicts of the finished Gemini chunks, then those in the stream file, one per image (later lines win)
+    pub fn judged(&self) -> Vec<serde_json::Value> {
+        let chunks: Vec<serde_json::Value> = fs::read_to_string(chunks_path(&self.stream_path)).ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        let lines = fs::read_to_string(&self.stream_path).unwrap_or_default();
+        dedupe_by_image(chunks.into_iter().chain(lines.lines().filter_map(|l| serde_json::from_str(l.trim()).ok())).collect())
+    }
+
+    // file names currently in the bbox dir
+    pub fn bbox_names(&self) -> Vec<String> {
+        let Ok(rd) = fs::read_dir(&self.bbox_dir) else { return vec![]; };
+        let mut names: Vec<String> = rd.flatten()
+            .filter(|e| e.path().is_file())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+// the entries of every chunk judged so far, next to the run's stream; rewritten after each chunk
+// and removed once the merged result JSON is written
+pub fn chunks_path(stream_path: &Path) -> PathBuf {
+    stream_path.with_extension("chunks.json")
+}
+
+pub fn save_chunks(stream_path: &Path, entries: &[serde_json::Value]) -> Result<()> {
+    write_atomic(&chunks_path(stream_path), serde_json::to_string(entries)?.as_bytes())
+        .with_context(|| "failed to save the finished chunks")
+}
+
+#[derive(Debug)]
+pub struct ResumePlan {
+    pub run_yolo: bool,
+    // bbox image names still to send to Gemini
+    pub remaining: Vec<String>,
+    // verdicts carried over from before the interruption
+    pub done: Vec<serde_json::Value>,
+}
+
+// what is left to do, given the bbox images on disk and the verdicts already streamed
+pub fn plan_resume(cp: &Checkpoint, bbox_names: &[String], judged: Vec<serde_json::Value>) -> ResumePlan {
+    // YOLO may re-encode (x.webp → x.jpg), so inputs and bbox images are matched by stem
+    let bbox_stems: HashSet<&str> = bbox_names.iter().map(|n| stem(n)).collect();
+    let yolo_done = cp.stage == CheckpointStage::Detected
+        && cp.images.iter().all(|n| bbox_stems.contains(stem(n)));
+    if !yolo_done {
+        // bbox images are regenerated, so verdicts made from the old ones are redone as well
+        return ResumePlan { run_yolo: true, remaining: vec![], done: vec![] };
+    }
+    let judged_names: HashSet<String> = judged.iter().filter_map(image_of).map(|s| s.to_string()).collect();
+    let remaining = bbox_names.iter().filter(|n| !judged_names.contains(*n)).cloned().collect();
+    ResumePlan { run_yolo: false, remaining, done: judged }
+}
+
+// carried-over + newly judged entries in bbox-name order; a new verdict replaces an old one
+pub fn merge_entries(order: &[String], done: Vec<serde_json::Value>, new: Vec<serde_json::Value>) -> Vec<serde_json::Value> {
+    let mut all = dedupe_by_image(done.into_iter().chain(new).collect());
+    let rank = |v: &serde_json::Value| image_of(v).and_then(|n| order.iter().position(|o| o == n)).unwrap_or(usize::MAX);
+    all.sort_by_key(rank);
+    all
+}
+
+fn dedupe_by_image(entries: Vec<serde_json::Value>) -> Vec<serde_json::Value> {
+    let mut out: Vec<serde_json::Value> = vec![];
+    for e in entries {
+        let Some(name) = image_of(&e).map(|s| s.to_string()) else { continue; };
+        match out.iter().position(|o| image_of(o) == Some(name.as_str())) {
+            Some(i) => out[i] = e,
+            None => out.push(e),
+        }
+    }
+    out
+}
+
+fn image_of(v: &serde_json::Value) -> Option<&str> {
+    v.get("image")?.as_str()
+}
+
+fn stem(name: &str) -> &str {
+    Path::new(name).file_stem().and_then(|s| s.to_str()).unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn checkpoint(n: usize, stage: CheckpointStage) -> Checkpoint {
+        Checkpoint {
+            ts: "20240101_000000".to_string(),
+            weights: PathBuf::from("best.pt"),
+            run_input: PathBuf::from("in"),
+            bbox_dir: PathBuf::from("bbox"),
+            out_json: PathBuf::from("result.json"),
+            stream_path: PathBuf::from("stream.jsonl"),
+            images: (0..n).map(|i| format!("img{}.png", i)).collect(),
+            gps: BTreeMap::new(),
+            meta: BTreeMap::new(),
+            label: String::new(),
+            stage,
+        }
+    }
+
+    fn verdict(name: &str, accessible: bool) -> serde_json::Value {
+        json!({ "image": name, "result": { "accessible": accessible, "reason": "r" } })
+    }
+
+    #[test]
+    fn yolo_done_gemini_40_percent_done() {
+        let cp = checkpoint(10, CheckpointStage::Detected);
+        // YOLO wrote jpgs for the png inputs
+        let bbox: Vec<String> = (0..10).map(|i| format!("img{}.jpg", i)).collect();
+        let judged: Vec<_> = (0..4).map(|i| verdict(&format!("img{}.jpg", i), true)).collect();
+
+        let plan = plan_resume(&cp, &bbox, judged);
+        assert!(!plan.run_yolo);
+        assert_eq!(plan.done.len(), 4);
+        assert_eq!(plan.remaining, (4..10).map(|i| format!("img{}.jpg", i)).collect::<Vec<_>>());
+
+        // the resumed Gemini pass answers the rest, in its own order
+        let new: Vec<_> = (4..10).rev().map(|i| verdict(&format!("img{}.jpg", i), false)).collect();
+        let merged = merge_entries(&bbox, plan.done, new);
+        let names: Vec<&str> = merged.iter().filter_map(image_of).collect();
+        assert_eq!(names, bbox.iter().map(|s| s.as_str()).collect::<Vec<_>>());
+        assert_eq!(merged[3]["result"]["accessible"], json!(true));
+        assert_eq!(merged[4]["result"]["accessible"], json!(false));
+    }
+
+    #[test]
+    fn missing_bbox_images_rerun_yolo() {
+        let cp = checkpoint(3, CheckpointStage::Detected);
+        let plan = plan_resume(&cp, &["img0.jpg".to_string()], vec![verdict("img0.jpg", true)]);
+        assert!(plan.run_yolo);
+        assert!(plan.done.is_empty());
+
+        let cp = checkpoint(1, CheckpointStage::Copied);
+        assert!(plan_resume(&cp, &["img0.jpg".to_string()], vec![]).run_yolo);
+    }
+
+    #[test]
+    fn duplicate_stream_lines_keep_the_latest() {
+        let merged = merge_entries(
+            &["a.jpg".to_string()],
+            vec![verdict("a.jpg", true), verdict("a.jpg", false)],
+            vec![],
+        );
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0]["result"]["accessible"], json!(false));
+    }
+}
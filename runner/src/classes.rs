@@ -0,0 +1,35 @@
+// YOLO가 검출한 클래스(ramp, curb, stairs 등) 사이드카: bbox 폴더 옆 <dir>.classes.json
+// 파일 내용은 이미지 → 클래스 목록, 앱에서는 클래스 → 이미지 목록으로 뒤집어 보관
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// next to the bbox dir rather than inside it, so it is never mistaken for a bbox image
+pub fn sidecar_path(bbox_dir: &Path) -> PathBuf {
+    let name = bbox_dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    bbox_dir.with_file_name(format!("{}.classes.json", name))
+}
+
+// class → image names (as YOLO saw them); empty when the run predates the sidecar
+pub fn load(bbox_dir: &Path) -> HashMap<String, Vec<String>> {
+    let per_image: HashMap<String, Vec<String>> = fs::read_to_string(sidecar_path(bbox_dir))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let mut out: HashMap<String, Vec<String>> = HashMap::new();
+    for (image, classes) in per_image {
+        for c in classes {
+            out.entry(c).or_default().push(image.clone());
+        }
+    }
+    for images in out.values_mut() { images.sort(); }
+    out
+}
+
+// YOLO may re-encode (x.webp → x.jpg), so result names are matched by stem
+pub fn contains(images: &[String], result_image: &str) -> bool {
+    let stem = |n: &str| Path::new(n).file_stem().map(|s| s.to_os_string());
+    images.iter().any(|i| i == result_image || stem(i) == stem(result_image))
+}
@@ -0,0 +1,38 @@
+// 클립보드 이미지(카카오톡 스크린샷 등)를 .runner_work/clipboard 에 PNG로 저장해 입력으로 사용
+// 저장된 파일은 작업 폴더 보존 기간 설정에 따라 정리됨
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub fn staging_dir(work_dir: &Path) -> PathBuf {
+    work_dir.join("clipboard")
+}
+
+// Ok(None) when the clipboard holds no image (text, files, or nothing)
+pub fn stage_image(work_dir: &Path) -> Result<Option<PathBuf>> {
+    let mut cb = arboard::Clipboard::new().with_context(|| "clipboard is not available")?;
+    let img = match cb.get_image() {
+        Ok(img) => img,
+        Err(arboard::Error::ContentNotAvailable) => return Ok(None),
+        Err(e) => return Err(e).with_context(|| "failed to read the clipboard"),
+    };
+    let rgba = image::RgbaImage::from_raw(img.width as u32, img.height as u32, img.bytes.into_owned())
+        .with_context(|| "clipboard image has an unexpected size")?;
+
+    let dir = staging_dir(work_dir);
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let ts = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    // several pastes within the same second get _1, _2, ...
+    let mut path = dir.join(format!("clipboard_{}.png", ts));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("clipboard_{}_{}.png", ts, n));
+        n += 1;
+    }
+    rgba.save_with_format(&path, image::ImageFormat::Png)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(Some(path))
+}
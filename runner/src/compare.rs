@@ -0,0 +1,79 @@
+// A/B 비교 실행: 두 가중치로 얻은 판정 결과를 이미지별로 맞대어 일치율 계산
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+
+use crate::{parse_result_file, write_atomic, WheelJudge, WheelOne};
+
+#[derive(Debug, Clone)]
+pub struct CompareRow {
+    pub image: String,
+    pub a: Option<WheelJudge>,
+    pub b: Option<WheelJudge>,
+}
+
+impl CompareRow {
+    // both sides judged and returned the same verdict
+    pub fn agrees(&self) -> bool {
+        matches!((&self.a, &self.b), (Some(a), Some(b)) if a.accessible == b.accessible)
+    }
+}
+
+pub struct Summary {
+    pub total: usize,
+    pub agree: usize,
+}
+
+impl Summary {
+    pub fn rate(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.agree as f64 / self.total as f64 }
+    }
+}
+
+// union of both result sets, in A's order followed by B-only images
+pub fn build_rows(a: &[WheelOne], b: &[WheelOne]) -> Vec<CompareRow> {
+    let mut rows: Vec<CompareRow> = a.iter()
+        .map(|w| CompareRow { image: w.image.clone(), a: Some(w.result.clone()), b: None })
+        .collect();
+    for w in b {
+        match rows.iter_mut().find(|r| r.image == w.image && r.b.is_none()) {
+            Some(r) => r.b = Some(w.result.clone()),
+            None => rows.push(CompareRow { image: w.image.clone(), a: None, b: Some(w.result.clone()) }),
+        }
+    }
+    rows
+}
+
+pub fn summarize(rows: &[CompareRow]) -> Summary {
+    Summary { total: rows.len(), agree: rows.iter().filter(|r| r.agrees()).count() }
+}
+
+pub fn load_rows(json_a: &Path, json_b: &Path) -> Result<Vec<CompareRow>> {
+    Ok(build_rows(&read_results(json_a)?, &read_results(json_b)?))
+}
+
+// comparison_<ts>.json: both inputs, agreement rate and the list of disagreements
+pub fn write_summary(path: &Path, a: (&Path, &Path), b: (&Path, &Path)) -> Result<Summary> {
+    let rows = load_rows(a.1, b.1)?;
+    let summary = summarize(&rows);
+    let disagreements: Vec<serde_json::Value> = rows.iter().filter(|r| !r.agrees()).map(|r| serde_json::json!({
+        "image": r.image,
+        "a": r.a.as_ref().map(|j| j.accessible),
+        "b": r.b.as_ref().map(|j| j.accessible),
+    })).collect();
+    let payload = serde_json::json!({
+        "a": { "weights": a.0.to_string_lossy(), "result_json": a.1.to_string_lossy() },
+        "b": { "weights": b.0.to_string_lossy(), "result_json": b.1.to_string_lossy() },
+        "total": summary.total,
+        "agree": summary.agree,
+        "agreement_rate": summary.rate(),
+        "disagreements": disagreements,
+    });
+    write_atomic(path, serde_json::to_string_pretty(&payload)?.as_bytes())?;
+    Ok(summary)
+}
+
+fn read_results(path: &Path) -> Result<Vec<WheelOne>> {
+    let data = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let parsed = parse_result_file(&data).with_context(|| format!("failed to parse {}", path.display()))?;
+    Ok(parsed.results)
+}
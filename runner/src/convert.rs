@@ -0,0 +1,41 @@
+// 파이썬 쪽(OpenCV/ultralytics)이 못 읽는 HEIC/HEIF(아이폰 사진)는 복사 단계에서 PNG 로 변환
+// image 크레이트는 HEIC 를 디코딩하지 못하므로 설치된 변환 도구(heif-convert, sips, ImageMagick)를 차례로 시도
+use anyhow::{Context, Result};
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+};
+
+// copied as <stem>.png
+pub const CONVERT_EXTS: &[&str] = &["heic", "heif"];
+
+pub fn needs_conversion(name: &str) -> bool {
+    Path::new(name).extension().and_then(|e| e.to_str())
+        .is_some_and(|e| CONVERT_EXTS.iter().any(|x| x.eq_ignore_ascii_case(e)))
+}
+
+// the name a source is copied under: x.heic → x.png, everything else unchanged
+pub fn copy_name(name: &str) -> String {
+    if !needs_conversion(name) { return name.to_string(); }
+    Path::new(name).with_extension("png").to_string_lossy().to_string()
+}
+
+// writes `src` as a PNG at `dst`; returns the tool that did it
+pub fn to_png(src: &Path, dst: &Path) -> Result<&'static str> {
+    // a mislabeled JPEG/PNG needs no external tool
+    if let Ok(img) = image::open(src) {
+        img.save_with_format(dst, image::ImageFormat::Png).with_context(|| format!("failed to write {}", dst.display()))?;
+        return Ok("image");
+    }
+    let tools: [(&'static str, Vec<&std::ffi::OsStr>); 3] = [
+        ("heif-convert", vec![src.as_os_str(), dst.as_os_str()]),
+        ("sips", vec!["-s".as_ref(), "format".as_ref(), "png".as_ref(), src.as_os_str(), "--out".as_ref(), dst.as_os_str()]),
+        ("magick", vec![src.as_os_str(), dst.as_os_str()]),
+    ];
+    for (tool, args) in tools {
+        let ok = Command::new(tool).args(args).stdout(Stdio::null()).stderr(Stdio::null()).status()
+            .is_ok_and(|s| s.success());
+        if ok && dst.is_file() { return Ok(tool); }
+    }
+    anyhow::bail!("cannot convert {} to PNG: install libheif (heif-convert) or ImageMagick (magick)", src.display())
+}
@@ -0,0 +1,148 @@
+// Gemini 비용: 설정의 모델별 단가표로 실행 전 예상 비용을 보여주고, gemini/run.py 가
+// stdout 에 찍는 "USAGE tokens_in=.. tokens_out=.." 줄을 모아 실제 비용을 계산
+use serde::{Deserialize, Serialize};
+
+use crate::pipeline::GeminiParams;
+
+// rough per-image token counts for the estimate before a run (one image + the prompt in, a short JSON verdict out)
+const TYPICAL_TOKENS_IN: u64 = 800;
+const TYPICAL_TOKENS_OUT: u64 = 150;
+
+// USD; a model name matches itself and its versioned variants ("gemini-1.5-pro" covers "gemini-1.5-pro-002")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub model: String,
+    pub per_image: f64,
+    pub per_million_in: f64,
+    pub per_million_out: f64,
+}
+
+impl ModelPrice {
+    fn new(model: &str, per_million_in: f64, per_million_out: f64) -> Self {
+        Self { model: model.to_string(), per_image: 0.0, per_million_in, per_million_out }
+    }
+
+    pub fn cost(&self, images: usize, usage: Usage) -> f64 {
+        self.per_image * images as f64
+            + self.per_million_in * usage.tokens_in as f64 / 1e6
+            + self.per_million_out * usage.tokens_out as f64 / 1e6
+    }
+}
+
+// list prices at the time of writing; editable in the settings
+pub fn default_prices() -> Vec<ModelPrice> {
+    vec![
+        ModelPrice::new("gemini-1.5-flash", 0.075, 0.30),
+        ModelPrice::new("gemini-1.5-pro", 1.25, 5.00),
+        ModelPrice::new("gemini-2.0-flash", 0.10, 0.40),
+        ModelPrice::new("gemini-2.5-flash", 0.30, 2.50),
+    ]
+}
+
+// the longest matching entry; an empty model is the script's default, None for an unknown one
+pub fn price_for<'a>(prices: &'a [ModelPrice], model: &str) -> Option<&'a ModelPrice> {
+    let model = match model.trim() {
+        "" => GeminiParams::DEFAULT_MODEL,
+        m => m,
+    };
+    prices.iter()
+        .filter(|p| !p.model.trim().is_empty() && model.starts_with(p.model.trim()))
+        .max_by_key(|p| p.model.trim().len())
+}
+
+pub fn estimate(prices: &[ModelPrice], model: &str, images: usize) -> Option<f64> {
+    let usage = Usage { tokens_in: TYPICAL_TOKENS_IN * images as u64, tokens_out: TYPICAL_TOKENS_OUT * images as u64, images };
+    price_for(prices, model).map(|p| p.cost(images, usage))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    pub tokens_in: u64,
+    pub tokens_out: u64,
+    // USAGE lines seen (one per image by convention)
+    pub images: usize,
+}
+
+impl Usage {
+    pub fn add(&mut self, other: Usage) {
+        self.tokens_in += other.tokens_in;
+        self.tokens_out += other.tokens_out;
+        self.images += other.images;
+    }
+}
+
+// "USAGE tokens_in=1234 tokens_out=56", anywhere after the stage prefix
+pub fn parse_usage(line: &str) -> Option<Usage> {
+    let rest = &line[line.find("USAGE ")? + "USAGE ".len()..];
+    let mut usage = Usage { images: 1, ..Default::default() };
+    let mut seen = false;
+    for part in rest.split_whitespace() {
+        let Some((key, value)) = part.split_once('=') else { continue; };
+        match key {
+            "tokens_in" => usage.tokens_in = value.parse().ok()?,
+            "tokens_out" => usage.tokens_out = value.parse().ok()?,
+            _ => continue,
+        }
+        seen = true;
+    }
+    seen.then_some(usage)
+}
+
+// what a finished run used and cost; kept in the result sidecar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunCost {
+    pub model: String,
+    pub usage: Usage,
+    // None when the model is not in the price table
+    pub usd: Option<f64>,
+}
+
+impl RunCost {
+    pub fn new(model: &str, usage: Usage, prices: &[ModelPrice]) -> Self {
+        let usd = price_for(prices, model).map(|p| p.cost(usage.images, usage));
+        Self { model: model.trim().to_string(), usage, usd }
+    }
+
+    // a follow-up pass over the same result (e.g. retrying undetermined images) adds to its total
+    pub fn add(&mut self, other: RunCost) {
+        self.usage.add(other.usage);
+        self.usd = self.usd.zip(other.usd).map(|(a, b)| a + b);
+    }
+
+    pub fn describe(&self) -> String {
+        let model = if self.model.is_empty() { "script default model" } else { &self.model };
+        format!("{} tokens in, {} out over {} calls ({}) → {}",
+            self.usage.tokens_in, self.usage.tokens_out, self.usage.images, model, format_usd(self.usd))
+    }
+}
+
+pub fn format_usd(usd: Option<f64>) -> String {
+    match usd {
+        Some(v) if v < 0.01 && v > 0.0 => "< $0.01".to_string(),
+        Some(v) => format!("${:.2}", v),
+        None => "cost unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_lines_add_up_and_unknown_models_have_no_cost() {
+        let mut total = Usage::default();
+        for line in ["[GEMINI] USAGE tokens_in=1000000 tokens_out=200000", "[GEMINI] done 3/10", "USAGE tokens_in=0 tokens_out=x"] {
+            if let Some(u) = parse_usage(line) { total.add(u); }
+        }
+        assert_eq!(total, Usage { tokens_in: 1_000_000, tokens_out: 200_000, images: 1 });
+
+        let prices = default_prices();
+        let cost = RunCost::new("gemini-1.5-pro-002", total, &prices);
+        assert_eq!(cost.usd, Some(2.25));
+        assert_eq!(RunCost::new("my-local-model", total, &prices).usd, None);
+        assert_eq!(estimate(&prices, "", 100), estimate(&prices, GeminiParams::DEFAULT_MODEL, 100));
+        assert!(estimate(&prices, "", 100).is_some());
+        assert_eq!(format_usd(None), "cost unknown");
+        assert!(estimate(&prices, "gemini-1.5-flash", 100).is_some_and(|c| c > 0.0));
+    }
+}
@@ -0,0 +1,171 @@
+// 완료된 실행과 판정 결과를 누적 저장하는 SQLite DB (.runner_work/runner.db)
+// DB는 선택 사항: 열 수 없으면 앱은 기존과 동일하게 동작
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+use crate::WheelOne;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerdictFilter {
+    All,
+    Accessible,
+    NotAccessible,
+    Unknown,
+}
+
+impl VerdictFilter {
+    pub const ALL: [VerdictFilter; 4] = [VerdictFilter::All, VerdictFilter::Accessible, VerdictFilter::NotAccessible, VerdictFilter::Unknown];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            VerdictFilter::All => "All",
+            VerdictFilter::Accessible => "Accessible",
+            VerdictFilter::NotAccessible => "Not accessible",
+            VerdictFilter::Unknown => "Undetermined (null)",
+        }
+    }
+
+    pub fn accepts(self, accessible: Option<bool>) -> bool {
+        match self {
+            VerdictFilter::All => true,
+            VerdictFilter::Accessible => accessible == Some(true),
+            VerdictFilter::NotAccessible => accessible == Some(false),
+            VerdictFilter::Unknown => accessible.is_none(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DbQuery {
+    pub verdict: VerdictFilter,
+    // inclusive, "YYYY-MM-DD"; empty = unbounded
+    pub date_from: String,
+    pub date_to: String,
+    pub reason_contains: String,
+}
+
+impl Default for DbQuery {
+    fn default() -> Self {
+        Self { verdict: VerdictFilter::All, date_from: String::new(), date_to: String::new(), reason_contains: String::new() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DbRow {
+    pub image: String,
+    pub accessible: Option<bool>,
+    pub reason: String,
+    pub created_at: String,
+    pub json_path: String,
+}
+
+pub struct RunDb {
+    conn: Connection,
+}
+
+impl RunDb {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() { std::fs::create_dir_all(dir).ok(); }
+        let conn = Connection::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS runs (
+                 id         INTEGER PRIMARY KEY,
+                 json_path  TEXT NOT NULL UNIQUE,
+                 created_at TEXT NOT NULL,
+                 n_images   INTEGER NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS judgments (
+                 id         INTEGER PRIMARY KEY,
+                 run_id     INTEGER NOT NULL REFERENCES runs(id) ON DELETE CASCADE,
+                 image      TEXT NOT NULL,
+                 accessible INTEGER,
+                 reason     TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS judgments_run ON judgments(run_id);",
+        ).with_context(|| "failed to create schema")?;
+        Ok(Self { conn })
+    }
+
+    pub fn has_run(&self, json_path: &str) -> Result<bool> {
+        let id: Option<i64> = self.conn
+            .query_row("SELECT id FROM runs WHERE json_path = ?1", params![json_path], |r| r.get(0))
+            .optional()?;
+        Ok(id.is_some())
+    }
+
+    // returns false when the run was already recorded
+    pub fn insert_run(&mut self, json_path: &str, created_at: &str, results: &[WheelOne]) -> Result<bool> {
+        if self.has_run(json_path)? { return Ok(false); }
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO runs (json_path, created_at, n_images) VALUES (?1, ?2, ?3)",
+            params![json_path, created_at, results.len() as i64],
+        )?;
+        let run_id = tx.last_insert_rowid();
+        {
+            let mut stmt = tx.prepare("INSERT INTO judgments (run_id, image, accessible, reason) VALUES (?1, ?2, ?3, ?4)")?;
+            for w in results {
+                stmt.execute(params![run_id, w.image, w.result.accessible, w.result.reason])?;
+            }
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    // (json_path, created_at, [accessible, not accessible, unknown]) for the newest `limit` runs, oldest first
+    pub fn verdict_counts_by_run(&self, limit: usize) -> Result<Vec<(String, String, [usize; 3])>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.json_path, r.created_at,
+                    SUM(j.accessible = 1), SUM(j.accessible = 0), SUM(j.accessible IS NULL)
+             FROM runs r JOIN judgments j ON j.run_id = r.id
+             GROUP BY r.id ORDER BY r.created_at DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |r| {
+            let n = |i| r.get::<_, Option<i64>>(i).map(|v| v.unwrap_or(0) as usize);
+            Ok((r.get(0)?, r.get(1)?, [n(2)?, n(3)?, n(4)?]))
+        })?;
+        let mut out = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+        out.reverse();
+        Ok(out)
+    }
+
+    pub fn query(&self, q: &DbQuery) -> Result<Vec<DbRow>> {
+        let mut sql = String::from(
+            "SELECT j.image, j.accessible, j.reason, r.created_at, r.json_path
+             FROM judgments j JOIN runs r ON r.id = j.run_id WHERE 1=1",
+        );
+        let mut args: Vec<String> = vec![];
+        match q.verdict {
+            VerdictFilter::All => {}
+            VerdictFilter::Accessible => sql.push_str(" AND j.accessible = 1"),
+            VerdictFilter::NotAccessible => sql.push_str(" AND j.accessible = 0"),
+            VerdictFilter::Unknown => sql.push_str(" AND j.accessible IS NULL"),
+        }
+        if !q.date_from.trim().is_empty() {
+            args.push(q.date_from.trim().to_string());
+            sql.push_str(&format!(" AND substr(r.created_at, 1, 10) >= ?{}", args.len()));
+        }
+        if !q.date_to.trim().is_empty() {
+            args.push(q.date_to.trim().to_string());
+            sql.push_str(&format!(" AND substr(r.created_at, 1, 10) <= ?{}", args.len()));
+        }
+        if !q.reason_contains.trim().is_empty() {
+            args.push(format!("%{}%", q.reason_contains.trim()));
+            sql.push_str(&format!(" AND j.reason LIKE ?{}", args.len()));
+        }
+        sql.push_str(" ORDER BY r.created_at DESC, j.id");
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(args.iter()), |r| {
+            Ok(DbRow {
+                image: r.get(0)?,
+                accessible: r.get(1)?,
+                reason: r.get(2)?,
+                created_at: r.get(3)?,
+                json_path: r.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+}
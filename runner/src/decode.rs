@@ -0,0 +1,138 @@
+// 미리보기 이미지를 백그라운드 스레드에서 디코딩/축소 (UI가 멈추지 않도록)
+// 결과는 UI 스레드에서 poll 해서 텍스처로 올림
+// 결과를 불러오면 표 썸네일 전체를 작업 스레드 몇 개로 미리 디코딩 (스크롤 중 디코딩 끊김 방지)
+use eframe::egui;
+use std::{
+    collections::{HashSet, VecDeque},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{exif_info, thumbs};
+
+type Decoded = (String, Option<egui::ColorImage>);
+
+pub struct Decoder {
+    tx: Sender<Decoded>,
+    rx: Receiver<Decoded>,
+    // cache keys with a decode in flight
+    pending: HashSet<String>,
+    // keys whose file could not be decoded; not retried
+    failed: HashSet<String>,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self { tx, rx, pending: HashSet::new(), failed: HashSet::new() }
+    }
+}
+
+impl Decoder {
+    // decode `path` scaled to fit `max_side` (never enlarged); the result comes back from `poll` under `key`
+    pub fn request(&mut self, key: &str, path: PathBuf, max_side: u32, ctx: &egui::Context) {
+        if self.failed.contains(key) || !self.pending.insert(key.to_string()) { return; }
+        let (tx, key, ctx) = (self.tx.clone(), key.to_string(), ctx.clone());
+        thread::spawn(move || {
+            let img = std::fs::read(&path).ok().and_then(|d| exif_info::decode_upright(&d)).map(|img| {
+                let img = if img.width().max(img.height()) > max_side { img.thumbnail(max_side, max_side) } else { img };
+                let rgba = img.to_rgba8();
+                egui::ColorImage::from_rgba_unmultiplied([rgba.width() as usize, rgba.height() as usize], rgba.as_raw())
+            });
+            let _ = tx.send((key, img));
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn is_pending(&self, key: &str) -> bool {
+        self.pending.contains(key)
+    }
+
+    pub fn has_failed(&self, key: &str) -> bool {
+        self.failed.contains(key)
+    }
+
+    // finished decodes; None when the file could not be read
+    pub fn poll(&mut self) -> Vec<Decoded> {
+        let done: Vec<Decoded> = self.rx.try_iter().collect();
+        for (key, img) in &done {
+            self.pending.remove(key);
+            if img.is_none() { self.failed.insert(key.clone()); }
+        }
+        done
+    }
+}
+
+// threads decoding at once, at most
+const PREFETCH_WORKERS: usize = 4;
+
+// thumbnails of a loaded result decoded ahead of the table; dropped (and stopped) when the next
+// result loads
+pub struct Prefetch {
+    rx: Receiver<Decoded>,
+    cancel: Arc<AtomicBool>,
+    // keys not received yet
+    pending: HashSet<String>,
+    total: usize,
+    started: Instant,
+}
+
+impl Prefetch {
+    // `jobs` are (cache key, bbox image); each is fitted to `max_w` x `max_h` pixels and saved to
+    // the on-disk thumbnail cache like the ones the table loads itself
+    pub fn start(jobs: Vec<(String, PathBuf)>, max_w: u32, max_h: u32, cache_dir: Option<PathBuf>, ctx: &egui::Context) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let total = jobs.len();
+        let pending = jobs.iter().map(|(key, _)| key.clone()).collect();
+        let queue = Arc::new(Mutex::new(VecDeque::from(jobs)));
+        let workers = thread::available_parallelism().map_or(2, |n| n.get()).clamp(1, PREFETCH_WORKERS);
+        for _ in 0..workers.min(total) {
+            let (tx, queue, cancel, cache_dir, ctx) = (tx.clone(), queue.clone(), cancel.clone(), cache_dir.clone(), ctx.clone());
+            thread::spawn(move || {
+                while !cancel.load(Ordering::Relaxed) {
+                    let Some((key, path)) = queue.lock().unwrap().pop_front() else { break; };
+                    let img = thumbs::load(&path, max_w, max_h, cache_dir.as_deref()).map(|t| {
+                        egui::ColorImage::from_rgba_unmultiplied([t.image.width() as usize, t.image.height() as usize], t.image.as_raw())
+                    });
+                    if tx.send((key, img)).is_err() { break; }
+                    ctx.request_repaint();
+                }
+            });
+        }
+        Self { rx, cancel, pending, total, started: Instant::now() }
+    }
+
+    // finished thumbnails since the last poll; None when the file could not be decoded
+    pub fn poll(&mut self) -> Vec<Decoded> {
+        let done: Vec<Decoded> = self.rx.try_iter().collect();
+        for (key, _) in &done { self.pending.remove(key); }
+        done
+    }
+
+    // the table shows a placeholder for these instead of decoding them itself
+    pub fn is_pending(&self, key: &str) -> bool {
+        self.pending.contains(key)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    // (thumbnails, time since start)
+    pub fn summary(&self) -> (usize, Duration) {
+        (self.total, self.started.elapsed())
+    }
+}
+
+impl Drop for Prefetch {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,139 @@
+// URL로 이미지 추가: 백그라운드에서 .runner_work/downloads 로 내려받고 이미지인지 확인한 뒤 입력 목록에 추가
+// URL 하나가 실패해도 나머지는 계속 받음
+use anyhow::{Context, Result};
+use eframe::egui;
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use crate::percent_decode;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_REDIRECTS: usize = 10;
+
+pub enum DownloadEvent {
+    Log(String),
+    Saved(PathBuf),
+    Finished,
+}
+
+pub fn staging_dir(work_dir: &Path) -> PathBuf {
+    work_dir.join("downloads")
+}
+
+// one URL per line; blank lines and surrounding spaces are ignored
+pub fn parse_urls(input: &str) -> Vec<String> {
+    input.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect()
+}
+
+pub fn spawn(urls: Vec<String>, dir: PathBuf, max_bytes: u64, ctx: egui::Context) -> Receiver<DownloadEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let send = |ev: DownloadEvent| {
+            let _ = tx.send(ev);
+            ctx.request_repaint();
+        };
+        let client = reqwest::blocking::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
+            .build();
+        match client {
+            Ok(client) => {
+                for (i, url) in urls.iter().enumerate() {
+                    send(DownloadEvent::Log(format!("[INFO] downloading {}/{}: {}\n", i + 1, urls.len(), url)));
+                    match download_one(&client, url, &dir, max_bytes) {
+                        Ok(p) => {
+                            send(DownloadEvent::Log(format!("[INFO] saved {}\n", p.display())));
+                            send(DownloadEvent::Saved(p));
+                        }
+                        Err(e) => send(DownloadEvent::Log(format!("[WARN] download failed: {} ({:#})\n", url, e))),
+                    }
+                }
+            }
+            Err(e) => send(DownloadEvent::Log(format!("[ERROR] could not start downloads: {}\n", e))),
+        }
+        send(DownloadEvent::Finished);
+    });
+    rx
+}
+
+fn download_one(client: &reqwest::blocking::Client, url: &str, dir: &Path, max_bytes: u64) -> Result<PathBuf> {
+    let parsed = reqwest::Url::parse(url).with_context(|| "not a valid URL")?;
+    if !matches!(parsed.scheme(), "http" | "https") { anyhow::bail!("only http(s) URLs are supported"); }
+    let resp = client.get(parsed).send()?.error_for_status()?;
+    if resp.content_length().is_some_and(|n| n > max_bytes) {
+        anyhow::bail!("larger than the {} MB limit", max_bytes / (1024 * 1024));
+    }
+    let name = resp.headers().get(reqwest::header::CONTENT_DISPOSITION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(disposition_filename)
+        .or_else(|| resp.url().path_segments().and_then(|mut s| s.next_back()).map(percent_decode))
+        .map(|n| sanitize(&n))
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "download".to_string());
+
+    // the server may not send a length, so the body is capped while reading
+    let mut body = Vec::new();
+    resp.take(max_bytes + 1).read_to_end(&mut body)?;
+    if body.len() as u64 > max_bytes {
+        anyhow::bail!("larger than the {} MB limit", max_bytes / (1024 * 1024));
+    }
+    let format = image::guess_format(&body).with_context(|| "not an image")?;
+    image::load_from_memory_with_format(&body, format).with_context(|| "image could not be decoded")?;
+
+    // the extension follows the actual content (album links often end in .php or nothing)
+    let ext = format.extensions_str().first().copied().unwrap_or("img");
+    let stem = Path::new(&name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or(name.clone());
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let mut path = dir.join(format!("{}.{}", stem, ext));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{}_{}.{}", stem, n, ext));
+        n += 1;
+    }
+    fs::write(&path, &body).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+// `attachment; filename="a b.jpg"` or `filename*=UTF-8''a%20b.jpg`
+fn disposition_filename(v: &str) -> Option<String> {
+    let mut plain = None;
+    for part in v.split(';').map(str::trim) {
+        if let Some(enc) = part.strip_prefix("filename*=") {
+            let raw = enc.split_once("''").map_or(enc, |(_, n)| n);
+            return Some(percent_decode(raw.trim_matches('"')));
+        }
+        if let Some(n) = part.strip_prefix("filename=") {
+            plain = Some(n.trim_matches('"').to_string());
+        }
+    }
+    plain
+}
+
+// keeps only the last path component and drops characters Windows refuses in names
+fn sanitize(name: &str) -> String {
+    let last = name.rsplit(['/', '\\']).next().unwrap_or("");
+    last.chars()
+        .filter(|c| !c.is_control() && !matches!(c, ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+        .collect::<String>()
+        .trim_matches(['.', ' '])
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_from_content_disposition() {
+        assert_eq!(disposition_filename("attachment; filename=\"ramp 1.jpg\"").as_deref(), Some("ramp 1.jpg"));
+        assert_eq!(disposition_filename("attachment; filename=x.jpg; filename*=UTF-8''%EA%B2%BD%EC%82%AC.jpg").as_deref(), Some("경사.jpg"));
+        assert_eq!(disposition_filename("inline"), None);
+        assert_eq!(sanitize("../../etc/pa:ss?.jpg"), "pass.jpg");
+    }
+}
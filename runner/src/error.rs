@@ -0,0 +1,103 @@
+// 실행 실패의 종류: UI 는 종류별 안내를 보여주고, 명령줄 모드는 종류별 종료 코드를 씀
+// 파이프라인은 anyhow 로 문맥을 덧붙여 전달하고, 받는 쪽에서 `RunnerError::find` 로 꺼냄
+use std::{fmt, path::PathBuf};
+
+// last lines of a failed script's stderr kept on the error
+pub const STDERR_TAIL_LINES: usize = 20;
+
+#[derive(Debug)]
+pub enum RunnerError {
+    // yolov8/run.py or gemini/run.py is not under the project root
+    MissingScript(Vec<PathBuf>),
+    // `detail`: "not set", "not found", "is not a file" or "is empty"
+    MissingWeights { path: PathBuf, detail: &'static str },
+    NoPython { project_root: PathBuf },
+    // a script (or ffmpeg) that ran to completion but exited non-zero
+    SubprocessFailed { stage: String, code: Option<i32>, stderr_tail: Vec<String> },
+    // a result file the script wrote is not valid JSON
+    ParseError { path: PathBuf, detail: String },
+    Cancelled,
+    Timeout { stage: String, secs: u64 },
+}
+
+impl fmt::Display for RunnerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunnerError::MissingScript(paths) => {
+                let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+                write!(f, "Missing script: {}", paths.join(", "))
+            }
+            RunnerError::MissingWeights { detail: "not set", .. } => write!(f, "no weights file set"),
+            RunnerError::MissingWeights { path, detail } => write!(f, "Weights file {}: {}", detail, path.display()),
+            RunnerError::NoPython { project_root } => write!(f,
+                "No working Python found. Create venv at {}/.venv or set an explicit path.", project_root.display()),
+            RunnerError::SubprocessFailed { stage, code: Some(code), .. } => write!(f, "{} failed with exit code {}", stage, code),
+            RunnerError::SubprocessFailed { stage, code: None, .. } => write!(f, "{} was terminated by a signal", stage),
+            RunnerError::ParseError { path, detail } => write!(f, "failed to parse {}: {}", path.display(), detail),
+            RunnerError::Cancelled => write!(f, "Cancelled by user"),
+            RunnerError::Timeout { stage, secs } => write!(f, "{} timed out after {}s", stage, secs),
+        }
+    }
+}
+
+impl std::error::Error for RunnerError {}
+
+impl RunnerError {
+    // the typed error anywhere in an anyhow chain (context is usually added on the way up)
+    pub fn find(e: &anyhow::Error) -> Option<&RunnerError> {
+        e.chain().find_map(|c| c.downcast_ref::<RunnerError>())
+    }
+
+    // what to do about it, shown under the error in the log
+    pub fn guidance(&self) -> &'static str {
+        match self {
+            RunnerError::MissingScript(_) => "Check the project root: it must contain yolov8/run.py and gemini/run.py.",
+            RunnerError::MissingWeights { .. } => "Pick the YOLO weights file (.pt or .onnx) in the settings; download it again if it is empty.",
+            RunnerError::NoPython { .. } => "Create a virtual environment in the project root (python -m venv .venv) or set the Python path in the settings.",
+            RunnerError::SubprocessFailed { .. } => "The script's last error lines are above. Missing packages (pip install -r requirements.txt) and API keys are the usual causes.",
+            RunnerError::ParseError { .. } => "The script wrote a result file the runner can't read; check that the script matches this runner version.",
+            RunnerError::Cancelled => "The run was cancelled and its partial output removed.",
+            RunnerError::Timeout { .. } => "Raise the stage timeout in the settings, or run fewer images at once.",
+        }
+    }
+
+    // distinct process exit codes for command-line use; 1 stays "any other error"
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RunnerError::MissingScript(_) => 3,
+            RunnerError::MissingWeights { .. } => 4,
+            RunnerError::NoPython { .. } => 5,
+            RunnerError::SubprocessFailed { .. } => 6,
+            RunnerError::ParseError { .. } => 7,
+            RunnerError::Timeout { .. } => 124,
+            RunnerError::Cancelled => 130,
+        }
+    }
+}
+
+// exit code for any error a run ended with
+pub fn exit_code(e: &anyhow::Error) -> i32 {
+    RunnerError::find(e).map_or(1, RunnerError::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn typed_error_survives_added_context() {
+        let res: anyhow::Result<()> = Err(RunnerError::Timeout { stage: "YOLO".to_string(), secs: 60 }.into());
+        let e = res.context("variant A").context("run failed").unwrap_err();
+        assert!(matches!(RunnerError::find(&e), Some(RunnerError::Timeout { secs: 60, .. })));
+        assert_eq!(exit_code(&e), 124);
+        assert_eq!(format!("{:#}", e), "run failed: variant A: YOLO timed out after 60s");
+
+        let e = anyhow::anyhow!("something else");
+        assert!(RunnerError::find(&e).is_none());
+        assert_eq!(exit_code(&e), 1);
+
+        let e = RunnerError::MissingWeights { path: PathBuf::new(), detail: "not set" };
+        assert_eq!(e.to_string(), "no weights file set");
+    }
+}
@@ -0,0 +1,132 @@
+// 원본 사진의 EXIF에서 촬영 위치(GPS) 등 메타데이터 추출
+use exif::{Field, In, Reader, Tag, Value};
+use serde::{Deserialize, Serialize};
+use image::DynamicImage;
+use std::{
+    fs,
+    io::{BufReader, Cursor},
+    path::Path,
+};
+
+// what the input looked like before YOLO re-encoded it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageMeta {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub bytes: u64,
+    // EXIF DateTimeOriginal as "YYYY-MM-DD HH:MM:SS"
+    pub taken_at: Option<String>,
+}
+
+// reads only the image header and the EXIF block, not the pixels
+pub fn image_meta(path: &Path) -> ImageMeta {
+    let (width, height) = image::image_dimensions(path).map_or((None, None), |(w, h)| (Some(w), Some(h)));
+    ImageMeta {
+        width,
+        height,
+        bytes: fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        taken_at: read_taken_at(path),
+    }
+}
+
+pub fn read_taken_at(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    let exif = Reader::new().read_from_container(&mut BufReader::new(file)).ok()?;
+    let field = exif.get_field(Tag::DateTimeOriginal, In::PRIMARY)?;
+    let Value::Ascii(v) = &field.value else { return None; };
+    let dt = exif::DateTime::from_ascii(v.first()?).ok()?;
+    Some(format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second))
+}
+
+// (lat, lon) in decimal degrees; None when the file has no usable GPS tags
+pub fn read_gps(path: &Path) -> Option<(f64, f64)> {
+    let file = fs::File::open(path).ok()?;
+    let exif = Reader::new().read_from_container(&mut BufReader::new(file)).ok()?;
+    let lat = dms_to_deg(exif.get_field(Tag::GPSLatitude, In::PRIMARY)?)?;
+    let lon = dms_to_deg(exif.get_field(Tag::GPSLongitude, In::PRIMARY)?)?;
+    let lat_sign = if ref_is(exif.get_field(Tag::GPSLatitudeRef, In::PRIMARY), b'S') { -1.0 } else { 1.0 };
+    let lon_sign = if ref_is(exif.get_field(Tag::GPSLongitudeRef, In::PRIMARY), b'W') { -1.0 } else { 1.0 };
+    let (lat, lon) = (lat * lat_sign, lon * lon_sign);
+    // 0,0 is what some cameras write when they have no fix
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) || (lat == 0.0 && lon == 0.0) {
+        return None;
+    }
+    Some((lat, lon))
+}
+
+// decodes `data` upright: phones store portrait shots sideways with an EXIF Orientation tag,
+// which the decoder itself ignores
+pub fn decode_upright(data: &[u8]) -> Option<DynamicImage> {
+    let img = image::load_from_memory(data).ok()?;
+    Some(match orientation(data) {
+        Some(o) => apply_orientation(img, o),
+        None => img,
+    })
+}
+
+// EXIF Orientation (1..=8); None without EXIF
+pub fn orientation(data: &[u8]) -> Option<u32> {
+    let exif = Reader::new().read_from_container(&mut Cursor::new(data)).ok()?;
+    exif.get_field(Tag::Orientation, In::PRIMARY)?.value.get_uint(0)
+}
+
+pub fn apply_orientation(img: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => img.fliph(),
+        3 => img.rotate180(),
+        4 => img.flipv(),
+        5 => img.rotate90().fliph(),
+        6 => img.rotate90(),
+        7 => img.rotate270().fliph(),
+        8 => img.rotate270(),
+        _ => img,
+    }
+}
+
+fn dms_to_deg(field: &Field) -> Option<f64> {
+    match &field.value {
+        Value::Rational(v) if v.len() >= 3 => Some(v[0].to_f64() + v[1].to_f64() / 60.0 + v[2].to_f64() / 3600.0),
+        _ => None,
+    }
+}
+
+fn ref_is(field: Option<&Field>, c: u8) -> bool {
+    match field.map(|f| &f.value) {
+        Some(Value::Ascii(v)) => v.first().and_then(|s| s.first()).is_some_and(|b| b.eq_ignore_ascii_case(&c)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a JPEG with an APP1 block holding only Orientation = `orientation`
+    fn jpeg_with_orientation(w: u32, h: u32, orientation: u16) -> Vec<u8> {
+        let mut jpeg = vec![];
+        image::RgbImage::from_pixel(w, h, image::Rgb([200, 40, 40]))
+            .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg).unwrap();
+        let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08".to_vec();
+        exif.extend([0, 1]); // one entry
+        exif.extend([0x01, 0x12, 0, 3, 0, 0, 0, 1]); // Orientation, SHORT, count 1
+        exif.extend(orientation.to_be_bytes());
+        exif.extend([0, 0, 0, 0, 0, 0]); // value padding, no next IFD
+        let mut out = jpeg[..2].to_vec(); // SOI
+        out.extend([0xFF, 0xE1]);
+        out.extend(((exif.len() + 2) as u16).to_be_bytes());
+        out.extend(exif);
+        out.extend(&jpeg[2..]);
+        out
+    }
+
+    #[test]
+    fn orientation_6_rotates_a_landscape_jpeg_to_portrait() {
+        let data = jpeg_with_orientation(40, 20, 6);
+        assert_eq!(orientation(&data), Some(6));
+        let img = decode_upright(&data).unwrap();
+        assert_eq!((img.width(), img.height()), (20, 40));
+
+        let plain = jpeg_with_orientation(40, 20, 1);
+        assert_eq!(decode_upright(&plain).map(|i| (i.width(), i.height())), Some((40, 20)));
+    }
+}
@@ -0,0 +1,397 @@
+// 결과 테이블을 외부 포맷(CSV 등)으로 내보내기
+use anyhow::{Context, Result};
+use rust_xlsxwriter::{Color, Format, FormatAlign, Workbook};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{parse_result_file, sidecar::Sidecar, WheelOne};
+
+// "Run and export": the formats written to a preset folder once a run finishes
+#[derive(Debug, Clone)]
+pub struct AutoExport {
+    pub dir: PathBuf,
+    pub csv: bool,
+    pub json: bool,
+    pub xlsx: bool,
+}
+
+impl AutoExport {
+    // None when no folder or no format is set
+    pub fn from_settings(dir: &str, csv: bool, json: bool, xlsx: bool) -> Option<Self> {
+        if dir.trim().is_empty() || !(csv || json || xlsx) { return None; }
+        Some(Self { dir: crate::expand_path(dir.trim()), csv, json, xlsx })
+    }
+
+    // files are named after the result JSON; `gps` is the run's coordinates and `label` its run
+    // label (neither is in the JSON yet). Returns the written paths.
+    pub fn write(&self, result_json: &Path, gps: &HashMap<String, (f64, f64)>, label: &str) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let data = fs::read_to_string(result_json).with_context(|| format!("failed to read {}", result_json.display()))?;
+        let mut rows = parse_result_file(&data).with_context(|| format!("failed to parse {}", result_json.display()))?.results;
+        for r in &mut rows {
+            if let Some(&(lat, lon)) = gps.get(&r.image) { (r.lat, r.lon) = (Some(lat), Some(lon)); }
+        }
+        let stem = result_json.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "results".to_string());
+        let sidecar = Sidecar { label: label.to_string(), ..Default::default() };
+        let mut written = vec![];
+        if self.json {
+            let p = self.dir.join(format!("{}.json", stem));
+            fs::copy(result_json, &p).with_context(|| format!("failed to write {}", p.display()))?;
+            written.push(p);
+        }
+        if self.csv {
+            let p = self.dir.join(format!("{}.csv", stem));
+            write_csv(&p, &rows, &sidecar)?;
+            written.push(p);
+        }
+        if self.xlsx {
+            let p = self.dir.join(format!("{}.xlsx", stem));
+            write_xlsx(&p, &rows, &sidecar)?;
+            written.push(p);
+        }
+        Ok(written)
+    }
+}
+
+// sorted union of the criteria names across `rows`; empty for results without criteria
+pub fn criteria_keys<'a>(rows: impl IntoIterator<Item = &'a WheelOne>) -> Vec<String> {
+    let keys: BTreeSet<&String> = rows.into_iter().flat_map(|r| r.result.criteria.keys()).collect();
+    keys.into_iter().cloned().collect()
+}
+
+// a result without the criterion gets the same blank as a null one
+fn criterion(r: &WheelOne, key: &str) -> Option<bool> {
+    r.result.criteria.get(key).copied().flatten()
+}
+
+pub fn write_csv(path: &Path, rows: &[WheelOne], sidecar: &Sidecar) -> Result<()> {
+    fs::write(path, csv(rows, sidecar)).with_context(|| format!("failed to write {}", path.display()))
+}
+
+pub fn csv(rows: &[WheelOne], sidecar: &Sidecar) -> String {
+    let criteria = criteria_keys(rows);
+    let table: Vec<Vec<String>> = rows.iter().map(|r| {
+        let mut cells = vec![
+            r.image.clone(),
+            r.label.clone().unwrap_or_default(),
+            verdict_cell(r.result.accessible).to_string(),
+            r.result.reason.clone(),
+            r.lat.map(|v| format!("{:.6}", v)).unwrap_or_default(),
+            r.lon.map(|v| format!("{:.6}", v)).unwrap_or_default(),
+            sidecar.note(&r.image).unwrap_or("").to_string(),
+            sidecar.is_reviewed(&r.image).to_string(),
+            sidecar.is_starred(&r.image).to_string(),
+        ];
+        cells.extend(criteria.iter().map(|c| verdict_cell(criterion(r, c)).to_string()));
+        cells
+    }).collect();
+    let mut header = vec!["image", "label", "accessible", "reason", "lat", "lon", "notes", "reviewed", "starred"];
+    header.extend(criteria.iter().map(|c| c.as_str()));
+    csv_table(&header, &table)
+}
+
+// Excel refuses longer cell text
+const XLSX_MAX_CELL_CHARS: usize = 32_767;
+
+// a real workbook, so Korean reasons survive Excel's CSV encoding guess; returns the images whose
+// reason had to be cut to fit a cell
+pub fn write_xlsx(path: &Path, rows: &[WheelOne], sidecar: &Sidecar) -> Result<Vec<String>> {
+    let mut book = Workbook::new();
+    let header = Format::new().set_bold().set_background_color(Color::RGB(0xD9D9D9));
+    let wrap = Format::new().set_text_wrap().set_align(FormatAlign::Top);
+    let top = Format::new().set_align(FormatAlign::Top);
+    let yes = top.clone().set_background_color(Color::RGB(0xC6EFCE));
+    let no = top.clone().set_background_color(Color::RGB(0xFFC7CE));
+    let confidence = top.clone().set_num_format("0.00");
+    let coord = top.clone().set_num_format("0.000000");
+    let mut truncated = vec![];
+
+    let sheet = book.add_worksheet().set_name("Results")?;
+    let columns = [("image", 32.0), ("label", 24.0), ("accessible", 12.0), ("confidence", 12.0), ("reason", 80.0), ("lat", 12.0), ("lon", 12.0), ("reviewed", 10.0), ("starred", 10.0)];
+    for (col, (name, width)) in columns.into_iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, name, &header)?;
+        sheet.set_column_width(col as u16, width)?;
+    }
+    // criteria go after the fixed columns
+    let first_criterion = columns.len() as u16;
+    let criteria = criteria_keys(rows);
+    for (i, name) in criteria.iter().enumerate() {
+        let col = first_criterion + i as u16;
+        sheet.write_string_with_format(0, col, name, &header)?;
+        sheet.set_column_width(col, (name.chars().count() as f64 + 2.0).max(10.0))?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    for (i, r) in rows.iter().enumerate() {
+        let row = i as u32 + 1;
+        sheet.write_string_with_format(row, 0, &r.image, &top)?;
+        if let Some(label) = &r.label { sheet.write_string_with_format(row, 1, label, &top)?; }
+        match r.result.accessible {
+            Some(v) => { sheet.write_boolean_with_format(row, 2, v, if v { &yes } else { &no })?; }
+            None => { sheet.write_blank(row, 2, &top)?; }
+        }
+        if let Some(c) = r.result.confidence { sheet.write_number_with_format(row, 3, c, &confidence)?; }
+        let reason = match truncate_cell(&r.result.reason) {
+            Some(cut) => { truncated.push(r.image.clone()); cut }
+            None => r.result.reason.clone(),
+        };
+        sheet.write_string_with_format(row, 4, reason, &wrap)?;
+        if let Some(lat) = r.lat { sheet.write_number_with_format(row, 5, lat, &coord)?; }
+        if let Some(lon) = r.lon { sheet.write_number_with_format(row, 6, lon, &coord)?; }
+        sheet.write_boolean_with_format(row, 7, sidecar.is_reviewed(&r.image), &top)?;
+        sheet.write_boolean_with_format(row, 8, sidecar.is_starred(&r.image), &top)?;
+        for (i, c) in criteria.iter().enumerate() {
+            if let Some(v) = criterion(r, c) { sheet.write_boolean_with_format(row, first_criterion + i as u16, v, if v { &yes } else { &no })?; }
+        }
+    }
+
+    let summary = book.add_worksheet().set_name("Summary")?;
+    let count = |v: Option<bool>| rows.iter().filter(|r| r.result.accessible == v).count();
+    let with_gps = rows.iter().filter(|r| r.lat.is_some() && r.lon.is_some()).count();
+    summary.set_column_width(0, 24.0)?;
+    summary.write_string_with_format(0, 0, "verdict", &header)?;
+    summary.write_string_with_format(0, 1, "images", &header)?;
+    for (i, (label, n)) in [
+        ("accessible", count(Some(true))),
+        ("not accessible", count(Some(false))),
+        ("undetermined (null)", count(None)),
+        ("total", rows.len()),
+        ("with coordinates", with_gps),
+    ].into_iter().enumerate() {
+        summary.write_string(i as u32 + 1, 0, label)?;
+        summary.write_number(i as u32 + 1, 1, n as f64)?;
+    }
+    // the run's label and notes under the counts
+    for (i, (k, v)) in [("run label", sidecar.label.trim()), ("run notes", sidecar.run_notes.trim())]
+        .into_iter().filter(|(_, v)| !v.is_empty()).enumerate()
+    {
+        summary.write_string_with_format(7 + i as u32, 0, k, &header)?;
+        summary.write_string(7 + i as u32, 1, v)?;
+    }
+    book.save(path).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(truncated)
+}
+
+// `s` cut to the cell limit with a trailing ellipsis; None when it already fits
+fn truncate_cell(s: &str) -> Option<String> {
+    if s.chars().count() <= XLSX_MAX_CELL_CHARS { return None; }
+    let mut cut: String = s.chars().take(XLSX_MAX_CELL_CHARS - 1).collect();
+    cut.push('…');
+    Some(cut)
+}
+
+// FeatureCollection of Point features (lon, lat order) for QGIS / Leaflet; entries without
+// coordinates are skipped. Returns (collection, skipped).
+pub fn geojson(rows: &[WheelOne], sidecar: &Sidecar) -> (serde_json::Value, usize) {
+    let features: Vec<serde_json::Value> = rows.iter()
+        .filter_map(|r| {
+            let (lat, lon) = (r.lat?, r.lon?);
+            let mut props = serde_json::json!({
+                "image": r.image,
+                "accessible": r.result.accessible,
+                "reason": r.result.reason,
+            });
+            if let Some(c) = r.result.confidence { props["confidence"] = c.into(); }
+            if sidecar.is_reviewed(&r.image) { props["reviewed"] = true.into(); }
+            if sidecar.is_starred(&r.image) { props["starred"] = true.into(); }
+            if !r.result.criteria.is_empty() { props["criteria"] = serde_json::json!(r.result.criteria); }
+            Some(serde_json::json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [lon, lat] },
+                "properties": props,
+            }))
+        })
+        .collect();
+    let skipped = rows.len() - features.len();
+    let mut fc = serde_json::json!({ "type": "FeatureCollection", "features": features });
+    // top-level "name" is what QGIS shows as the layer name
+    if let Some(label) = sidecar.label() { fc["name"] = label.into(); }
+    (fc, skipped)
+}
+
+// Returns (written, skipped).
+pub fn write_geojson(path: &Path, rows: &[WheelOne], sidecar: &Sidecar) -> Result<(usize, usize)> {
+    let (fc, skipped) = geojson(rows, sidecar);
+    fs::write(path, serde_json::to_string_pretty(&fc)?).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok((rows.len() - skipped, skipped))
+}
+
+// GitHub-flavored markdown table for pasting into issues/docs
+pub fn markdown_table(rows: &[&WheelOne]) -> String {
+    let mut out = String::from("| Image | Accessible | Reason |\n|---|---|---|\n");
+    for r in rows {
+        let verdict = match r.result.accessible {
+            Some(true) => "✔ true",
+            Some(false) => "✖ false",
+            None => "? null",
+        };
+        out.push_str(&format!("| {} | {} | {} |\n", md_cell(&r.image), verdict, md_cell(&r.result.reason)));
+    }
+    out
+}
+
+pub fn write_csv_table(path: &Path, header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    fs::write(path, csv_table(header, rows)).with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn csv_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    // criterion names come from the model and can hold anything
+    let mut out = header.iter().map(|h| csv_field(h)).collect::<Vec<_>>().join(",");
+    out.push('\n');
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|c| csv_field(c)).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+pub fn verdict_cell(accessible: Option<bool>) -> &'static str {
+    match accessible {
+        Some(true) => "true",
+        Some(false) => "false",
+        None => "",
+    }
+}
+
+// tab-separated with a header row, for pasting into a spreadsheet; criteria get a column each
+pub fn tsv(rows: &[&WheelOne]) -> String {
+    let criteria = criteria_keys(rows.iter().copied());
+    let mut header: Vec<String> = ["image", "accessible", "confidence", "reason", "lat", "lon"].map(String::from).into();
+    header.extend(criteria.iter().map(|c| tsv_cell(c)));
+    let mut out = header.join("\t");
+    out.push('\n');
+    for r in rows {
+        let mut cells = vec![
+            tsv_cell(&r.image),
+            verdict_cell(r.result.accessible).to_string(),
+            r.result.confidence.map(|c| format!("{:.2}", c)).unwrap_or_default(),
+            tsv_cell(r.error.as_deref().unwrap_or(&r.result.reason)),
+            r.lat.map(|v| format!("{:.6}", v)).unwrap_or_default(),
+            r.lon.map(|v| format!("{:.6}", v)).unwrap_or_default(),
+        ];
+        cells.extend(criteria.iter().map(|c| verdict_cell(criterion(r, c)).to_string()));
+        out.push_str(&cells.join("\t"));
+        out.push('\n');
+    }
+    out
+}
+
+// a tab would start the next cell and a newline the next row
+fn tsv_cell(s: &str) -> String {
+    s.trim().replace("\r\n", " ").replace(['\t', '\n', '\r'], " ")
+}
+
+// pipes would end the cell and newlines the row
+fn md_cell(s: &str) -> String {
+    s.trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WheelJudge;
+
+    fn row(image: &str, accessible: Option<bool>, gps: Option<(f64, f64)>, confidence: Option<f64>) -> WheelOne {
+        WheelOne {
+            image: image.to_string(),
+            result: WheelJudge { accessible, reason: format!("{} reason", image), confidence, criteria: Default::default() },
+            lat: gps.map(|g| g.0),
+            lon: gps.map(|g| g.1),
+            cached: false,
+            error: None,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn geojson_matches_fixture() {
+        let rows = vec![
+            row("ramp.jpg", Some(true), Some((37.5665, 126.978)), Some(0.92)),
+            row("no_gps.jpg", Some(false), None, None),
+            row("stairs.jpg", None, Some((35.1796, 129.0756)), None),
+        ];
+        let (fc, skipped) = geojson(&rows, &Sidecar::default());
+        let expected: serde_json::Value = serde_json::from_str(include_str!("../tests/fixtures/results.geojson")).unwrap();
+        assert_eq!(fc, expected);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn criteria_and_review_state_become_csv_columns() {
+        let old: WheelOne = serde_json::from_str(r#"{"image": "old.jpg", "label": "Main St ramp #3", "result": {"accessible": true, "reason": "ramp"}}"#).unwrap();
+        assert!(old.result.criteria.is_empty());
+        assert!(criteria_keys(std::slice::from_ref(&old)).is_empty());
+        let new: WheelOne = serde_json::from_str(r#"{"image": "new.jpg", "result": {"accessible": false, "reason": "steps",
+            "criteria": {"ramp": false, "door_width": true, "step_free": null}}}"#).unwrap();
+        let other: WheelOne = serde_json::from_str(r#"{"image": "b.jpg", "result": {"accessible": null, "reason": "",
+            "criteria": {"handrail": true}}}"#).unwrap();
+        let rows = vec![old, new, other];
+        assert_eq!(criteria_keys(&rows), ["door_width", "handrail", "ramp", "step_free"]);
+
+        let path = std::env::temp_dir().join("wheel_city_export_criteria_test.csv");
+        let mut sidecar = Sidecar::default();
+        sidecar.reviewed.insert("new.jpg".to_string());
+        sidecar.starred.insert("old.jpg".to_string());
+        write_csv(&path, &rows, &sidecar).unwrap();
+        let csv = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "image,label,accessible,reason,lat,lon,notes,reviewed,starred,door_width,handrail,ramp,step_free");
+        assert!(lines[1].starts_with("old.jpg,Main St ramp #3,true,ramp,"));
+        assert!(lines[1].ends_with(",false,true,,,,"));
+        assert!(lines[2].starts_with("new.jpg,,false,"));
+        assert!(lines[2].ends_with(",true,false,true,,false,"));
+        assert!(lines[3].ends_with(",false,false,,true,,"));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn tsv_keeps_reasons_on_one_line_in_one_cell() {
+        let mut multi = row("multi.jpg", Some(false), Some((37.5, 127.0)), Some(0.8));
+        multi.result.reason = "step at door\tno ramp\r\nnarrow\npath".to_string();
+        let rows = [row("ok.jpg", Some(true), None, None), multi];
+        let text = tsv(&rows.iter().collect::<Vec<_>>());
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "image\taccessible\tconfidence\treason\tlat\tlon");
+        assert_eq!(lines[1], "ok.jpg\ttrue\t\tok.jpg reason\t\t");
+        assert_eq!(lines[2], "multi.jpg\tfalse\t0.80\tstep at door no ramp narrow path\t37.500000\t127.000000");
+
+        // criterion names are headers too
+        let odd: WheelOne = serde_json::from_str(r#"{"image": "c.jpg", "result": {"accessible": true, "reason": "",
+            "criteria": {"ramp, slope\"s\tend\nx": true}}}"#).unwrap();
+        let text = tsv(&[&odd]);
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.starts_with("image\taccessible\tconfidence\treason\tlat\tlon\tramp, slope\"s end x\n"));
+        assert_eq!(csv_table(&["image", "a,b"], &[]), "image,\"a,b\"\n");
+    }
+
+    #[test]
+    fn xlsx_truncates_reasons_over_the_cell_limit() {
+        let mut long = row("long.jpg", Some(false), None, Some(0.4));
+        long.result.reason = "경사로 없음 ".repeat(6000);
+        let rows = vec![row("ramp.jpg", Some(true), Some((37.5665, 126.978)), Some(0.92)), long, row("unsure.jpg", None, None, None)];
+        let path = std::env::temp_dir().join("wheel_city_export_test.xlsx");
+        let truncated = write_xlsx(&path, &rows, &Sidecar::default()).unwrap();
+        assert_eq!(truncated, vec!["long.jpg".to_string()]);
+        assert!(fs::metadata(&path).unwrap().len() > 0);
+        let cut = truncate_cell(&rows[1].result.reason).unwrap();
+        assert_eq!(cut.chars().count(), XLSX_MAX_CELL_CHARS);
+        assert!(cut.ends_with('…'));
+        assert!(truncate_cell("short").is_none());
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,261 @@
+// 사람이 라벨링한 정답 CSV (image,accessible) 와 불러온 결과를 맞대어 정확도/정밀도/재현율, 혼동 행렬 계산
+// 이름이 같으면 그대로, 아니면 확장자를 뺀 이름으로 매칭 (x.webp 결과 ↔ x.jpg 정답)
+use anyhow::{Context, Result};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{export, write_atomic};
+
+pub struct GroundTruth {
+    pub path: PathBuf,
+    // in file order
+    pub labels: Vec<(String, bool)>,
+    // rows whose accessible cell was empty; not scored
+    pub unlabeled: usize,
+    by_name: HashMap<String, bool>,
+    by_stem: HashMap<String, bool>,
+}
+
+impl GroundTruth {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let mut gt = Self::parse(&data).with_context(|| format!("failed to parse {}", path.display()))?;
+        gt.path = path.to_path_buf();
+        Ok(gt)
+    }
+
+    // header row with `image` and `accessible` columns (any order, other columns ignored)
+    pub fn parse(data: &str) -> Result<Self> {
+        let mut lines = data.lines().filter(|l| !l.trim().is_empty());
+        let header = split_csv_line(lines.next().with_context(|| "the file is empty")?.trim_start_matches('\u{feff}'));
+        let col = |name: &str| header.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+        let (image_col, acc_col) = match (col("image"), col("accessible")) {
+            (Some(i), Some(a)) => (i, a),
+            _ => anyhow::bail!("expected `image` and `accessible` columns, found: {}", header.join(", ")),
+        };
+        let mut labels = vec![];
+        let mut unlabeled = 0;
+        for (n, line) in lines.enumerate() {
+            let cells = split_csv_line(line);
+            let image = cells.get(image_col).map(|s| s.trim()).unwrap_or("");
+            if image.is_empty() { continue; }
+            match parse_bool(cells.get(acc_col).map(|s| s.as_str()).unwrap_or(""))
+                .with_context(|| format!("row {}: `accessible` must be true/false, 1/0 or yes/no", n + 2))?
+            {
+                Some(v) => labels.push((image.to_string(), v)),
+                None => unlabeled += 1,
+            }
+        }
+        let mut by_name = HashMap::new();
+        let mut by_stem = HashMap::new();
+        for (image, v) in &labels {
+            by_name.entry(image.clone()).or_insert(*v);
+            by_stem.entry(stem(image)).or_insert(*v);
+        }
+        Ok(Self { path: PathBuf::new(), labels, unlabeled, by_name, by_stem })
+    }
+
+    // the label for a result's image name, falling back to the stem
+    pub fn lookup(&self, image: &str) -> Option<bool> {
+        self.by_name.get(image).or_else(|| self.by_stem.get(&stem(image))).copied()
+    }
+}
+
+fn stem(name: &str) -> String {
+    Path::new(name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| name.to_string())
+}
+
+// empty → Ok(None)
+fn parse_bool(s: &str) -> Result<Option<bool>> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" => Ok(None),
+        "true" | "1" | "yes" | "y" | "o" => Ok(Some(true)),
+        "false" | "0" | "no" | "n" | "x" => Ok(Some(false)),
+        other => anyhow::bail!("unexpected value {:?}", other),
+    }
+}
+
+// one CSV record; quoted fields may contain commas and doubled quotes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cur = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => { cur.push('"'); chars.next(); }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cur)),
+            _ => cur.push(c),
+        }
+    }
+    cells.push(cur);
+    cells
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalRow {
+    pub image: String,
+    pub truth: bool,
+    // None: the model left it undetermined
+    pub predicted: Option<bool>,
+}
+
+impl EvalRow {
+    pub fn agrees(&self) -> bool {
+        self.predicted == Some(self.truth)
+    }
+}
+
+// "accessible" is the positive class
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Confusion {
+    pub tp: usize,
+    pub fp: usize,
+    pub fn_: usize,
+    pub tn: usize,
+}
+
+impl Confusion {
+    pub fn total(&self) -> usize {
+        self.tp + self.fp + self.fn_ + self.tn
+    }
+
+    pub fn accuracy(&self) -> Option<f64> {
+        ratio(self.tp + self.tn, self.total())
+    }
+
+    pub fn precision(&self) -> Option<f64> {
+        ratio(self.tp, self.tp + self.fp)
+    }
+
+    pub fn recall(&self) -> Option<f64> {
+        ratio(self.tp, self.tp + self.fn_)
+    }
+}
+
+fn ratio(n: usize, d: usize) -> Option<f64> {
+    (d > 0).then(|| n as f64 / d as f64)
+}
+
+pub struct Evaluation {
+    pub rows: Vec<EvalRow>,
+    pub matrix: Confusion,
+    // matched, but the model gave no verdict; not in the matrix
+    pub undetermined: usize,
+    // labeled images no result matched, and results without a label
+    pub missing_results: Vec<String>,
+    pub missing_labels: Vec<String>,
+}
+
+// `predictions` are (image, verdict) in table order; a name that appears more than once is
+// scored by its last (newest) entry
+pub fn evaluate(gt: &GroundTruth, predictions: &[(String, Option<bool>)]) -> Evaluation {
+    let mut last: HashMap<&str, Option<bool>> = HashMap::new();
+    let mut order = vec![];
+    for (image, v) in predictions {
+        if last.insert(image, *v).is_none() { order.push(image.as_str()); }
+    }
+    let mut rows = vec![];
+    let mut missing_labels = vec![];
+    let mut matched_stems = HashSet::new();
+    for image in order {
+        match gt.lookup(image) {
+            Some(truth) => {
+                matched_stems.insert(stem(image));
+                rows.push(EvalRow { image: image.to_string(), truth, predicted: last[image] });
+            }
+            None => missing_labels.push(image.to_string()),
+        }
+    }
+    let missing_results = gt.labels.iter()
+        .filter(|(image, _)| !last.contains_key(image.as_str()) && !matched_stems.contains(&stem(image)))
+        .map(|(image, _)| image.clone())
+        .collect();
+    let mut matrix = Confusion::default();
+    let mut undetermined = 0;
+    for r in &rows {
+        match (r.predicted, r.truth) {
+            (Some(true), true) => matrix.tp += 1,
+            (Some(true), false) => matrix.fp += 1,
+            (Some(false), true) => matrix.fn_ += 1,
+            (Some(false), false) => matrix.tn += 1,
+            (None, _) => undetermined += 1,
+        }
+    }
+    Evaluation { rows, matrix, undetermined, missing_results, missing_labels }
+}
+
+fn fmt_ratio(v: Option<f64>) -> String {
+    v.map(|v| format!("{:.4}", v)).unwrap_or_default()
+}
+
+// summary block, a blank line, then the per-image agreement table
+pub fn write_csv(path: &Path, eval: &Evaluation, gt: &GroundTruth) -> Result<()> {
+    let m = &eval.matrix;
+    let mut out = String::from("metric,value\n");
+    for (k, v) in [
+        ("ground_truth", gt.path.display().to_string()),
+        ("scored", m.total().to_string()),
+        ("accuracy", fmt_ratio(m.accuracy())),
+        ("precision", fmt_ratio(m.precision())),
+        ("recall", fmt_ratio(m.recall())),
+        ("true_positive", m.tp.to_string()),
+        ("false_positive", m.fp.to_string()),
+        ("false_negative", m.fn_.to_string()),
+        ("true_negative", m.tn.to_string()),
+        ("undetermined", eval.undetermined.to_string()),
+        ("labels_without_result", eval.missing_results.len().to_string()),
+        ("results_without_label", eval.missing_labels.len().to_string()),
+    ] {
+        out.push_str(&format!("{},{}\n", k, export::csv_field(&v)));
+    }
+    out.push_str("\nimage,truth,predicted,agrees\n");
+    for r in &eval.rows {
+        out.push_str(&format!("{},{},{},{}\n",
+            export::csv_field(&r.image), r.truth, export::verdict_cell(r.predicted), r.agrees()));
+    }
+    write_atomic(path, out.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_results_against_labels_matched_by_name_or_stem() {
+        let gt = GroundTruth::parse("\u{feff}id,accessible,image\n1,yes,ramp.jpg\n2,0,\"steps, front.jpg\"\n3,true,door.png\n4,,blur.jpg\n5,false,gone.jpg\n").unwrap();
+        assert_eq!(gt.labels.len(), 4);
+        assert_eq!(gt.unlabeled, 1);
+        assert_eq!(gt.lookup("door.jpg"), Some(true));
+
+        let preds = vec![
+            ("ramp.jpg".to_string(), Some(false)),
+            ("steps, front.jpg".to_string(), Some(false)),
+            ("door.jpg".to_string(), Some(true)),
+            ("ramp.jpg".to_string(), Some(true)),
+            ("extra.jpg".to_string(), Some(true)),
+            ("blur.jpg".to_string(), None),
+        ];
+        let eval = evaluate(&gt, &preds);
+        assert_eq!(eval.matrix, Confusion { tp: 2, fp: 0, fn_: 0, tn: 1 });
+        assert_eq!(eval.matrix.accuracy(), Some(1.0));
+        assert_eq!(eval.undetermined, 0);
+        assert_eq!(eval.missing_results, ["gone.jpg"]);
+        assert_eq!(eval.missing_labels, ["extra.jpg", "blur.jpg"]);
+        assert!(eval.rows.iter().all(|r| r.agrees()));
+
+        let eval = evaluate(&gt, &[("ramp.jpg".to_string(), Some(false)), ("door.png".to_string(), None)]);
+        assert_eq!(eval.matrix, Confusion { tp: 0, fp: 0, fn_: 1, tn: 0 });
+        assert_eq!(eval.matrix.precision(), None);
+        assert_eq!(eval.matrix.recall(), Some(0.0));
+        assert_eq!(eval.undetermined, 1);
+        assert!(eval.rows.iter().all(|r| !r.agrees()));
+
+        assert!(GroundTruth::parse("image,verdict\na.jpg,1\n").is_err());
+        assert!(GroundTruth::parse("image,accessible\na.jpg,maybe\n").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+// 끝난 실행 목록 (History 탭): 시각, 이미지 수, 판정별 개수, 결과 JSON 경로
+// 기본은 이번 세션만, 설정을 켜면 설정 폴더의 run_history.json 에 저장해 다음 실행에도 보여줌
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::{settings, write_atomic};
+
+// oldest entries are dropped past this
+pub const MAX_HISTORY: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    // local time the run finished, "YYYY-MM-DD HH:MM:SS"
+    pub at: String,
+    pub images: usize,
+    pub accessible: usize,
+    pub inaccessible: usize,
+    pub undetermined: usize,
+    pub json: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox_dir: Option<PathBuf>,
+    // the run's label from its sidecar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl RunRecord {
+    // "Gangnam station survey — 2025-03-11", or the result file name for unlabeled runs
+    pub fn title(&self) -> String {
+        match &self.label {
+            Some(l) => format!("{} — {}", l, self.at.get(..10).unwrap_or(&self.at)),
+            None => self.json.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    Some(settings::config_dir()?.join("run_history.json"))
+}
+
+// newest first; missing or unreadable file → empty
+pub fn load() -> Vec<RunRecord> {
+    history_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(list: &[RunRecord]) -> Result<()> {
+    let path = history_path().with_context(|| "no config directory on this platform")?;
+    if let Some(dir) = path.parent() { fs::create_dir_all(dir)?; }
+    write_atomic(&path, serde_json::to_string_pretty(list)?.as_bytes())
+}
+
+// forget what was saved when persisting is turned off
+pub fn delete_saved() {
+    if let Some(p) = history_path() { let _ = fs::remove_file(p); }
+}
+
+// a re-run into the same result file (e.g. a retry) replaces its older entry
+pub fn push(list: &mut Vec<RunRecord>, rec: RunRecord) {
+    list.retain(|r| r.json != rec.json);
+    list.insert(0, rec);
+    list.truncate(MAX_HISTORY);
+}
@@ -0,0 +1,433 @@
+// 화면 문구 번역(영어/한국어). 영어 문구 자체를 키로 쓰고, 번역이 없으면 영어 그대로 표시
+// 로그 파일/스크립트/JSON 은 번역하지 않음 (화면 표시만)
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Lang {
+    #[default]
+    En,
+    Ko,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Ko];
+
+    // shown in its own language so it can be found either way
+    pub fn label(self) -> &'static str {
+        match self {
+            Lang::En => "English",
+            Lang::Ko => "한국어",
+        }
+    }
+}
+
+// process-wide so `tr` can be called from any drawing helper; set from the settings each frame
+static LANG: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    if LANG.load(Ordering::Relaxed) == Lang::Ko as u8 { Lang::Ko } else { Lang::En }
+}
+
+// the English UI text `key` in the current language
+pub fn tr(key: &str) -> &str {
+    match lang() {
+        Lang::En => key,
+        Lang::Ko => ko(key).unwrap_or(key),
+    }
+}
+
+// a log line with its leading level tag ("[ERROR] ...") translated; the level itself is
+// still taken from the untranslated line
+pub fn log_tag(line: &str) -> Cow<'_, str> {
+    if lang() == Lang::En { return Cow::Borrowed(line); }
+    let Some(rest) = line.strip_prefix('[') else { return Cow::Borrowed(line); };
+    let Some((tag, rest)) = rest.split_once(']') else { return Cow::Borrowed(line); };
+    let ko = match tag {
+        "ERROR" => "오류",
+        "WARN" => "경고",
+        "INFO" => "정보",
+        "OK" => "정상",
+        "STEP" => "단계",
+        "DONE" => "완료",
+        "TIMEOUT" => "시간 초과",
+        _ => return Cow::Borrowed(line),
+    };
+    Cow::Owned(format!("[{}]{}", ko, rest))
+}
+
+// Hangul isn't in egui's bundled fonts; use a system font as a fallback when one is installed
+pub fn install_korean_font(ctx: &egui::Context) {
+    const CANDIDATES: &[&str] = &[
+        "C:\\Windows\\Fonts\\malgun.ttf",
+        "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+        "/Library/Fonts/AppleGothic.ttf",
+        "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
+        "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    ];
+    let Some(data) = CANDIDATES.iter().find_map(|p| std::fs::read(p).ok()) else { return; };
+    let mut fonts = egui::FontDefinitions::default();
+    fonts.font_data.insert("korean".into(), egui::FontData::from_owned(data));
+    for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+        fonts.families.entry(family).or_default().push("korean".into());
+    }
+    ctx.set_fonts(fonts);
+}
+
+fn ko(key: &str) -> Option<&'static str> {
+    Some(match key {
+        // top panel
+        "Close" => "닫기",
+        "Language" => "언어",
+        // project / inputs
+        "Project root (contains yolov8/ and gemini/)" => "프로젝트 루트 (yolov8/, gemini/ 포함)",
+        "Browse..." => "찾아보기...",
+        "Browse" => "찾아보기",
+        "✔ yolov8/run.py and gemini/run.py found" => "✔ yolov8/run.py 와 gemini/run.py 를 찾았습니다",
+        "Use it" => "사용",
+        "✖ yolov8/run.py or gemini/run.py not found here" => "✖ 여기에 yolov8/run.py 또는 gemini/run.py 가 없습니다",
+        "Python executable (path or command)" => "Python 실행 파일 (경로 또는 명령)",
+        "⚠ CPU only: YOLO will be slow" => "⚠ CPU 전용: YOLO 가 느립니다",
+        "GPU not checked yet" => "GPU 확인 전",
+        "Check GPU" => "GPU 확인",
+        "YOLO weights path (best.pt)" => "YOLO 가중치 경로 (best.pt)",
+        "Select file" => "파일 선택",
+        "A/B run with a second weights file" => "두 번째 가중치로 A/B 실행",
+        "Weights B" => "가중치 B",
+        "Stage timeouts" => "단계별 제한 시간",
+        "0 = no limit" => "0 = 제한 없음",
+        "Gemini (whole batch):" => "Gemini (배치 전체):",
+        "Prompt template" => "프롬프트 템플릿",
+        "Sent to gemini/run.py as --prompt_file; empty = the script's own prompt. Placeholders like {image_name} are passed through as written."
+            => "gemini/run.py 에 --prompt_file 로 전달; 비우면 스크립트 기본 프롬프트 사용. {image_name} 같은 자리표시자는 그대로 전달됩니다.",
+        "same as the saved default" => "저장된 기본값과 같음",
+        "lines vs the saved default" => "줄 (저장된 기본값 대비)",
+        "Save as default" => "기본값으로 저장",
+        "Revert to default" => "기본값으로 되돌리기",
+        "Judgment model" => "판정 모델",
+        "Backend" => "백엔드",
+        "e.g. Main St ramp #3" => "예: 중앙로 경사로 3번",
+        "Every line in the log, whatever the filters show" => "필터와 상관없이 로그의 모든 줄",
+        "Log lines copied:" => "복사한 로그 줄:",
+        "Click to show in the file manager" => "클릭하면 파일 관리자에서 표시",
+        "Copy path" => "경로 복사",
+        "CSV..." => "CSV...",
+        "Only the starred rows, e.g. the examples for a report" => "별표한 행만, 예: 보고서에 넣을 예시",
+        "quality" => "품질",
+        "Choose folder and export..." => "폴더 선택 후 내보내기...",
+        "Save the bbox images of the rows shown in the table" => "표에 보이는 행의 bbox 이미지를 저장",
+        "Result files" => "결과 파일",
+        "Input folders" => "입력 폴더",
+        "none" => "없음",
+        "Add the images in this folder" => "이 폴더의 이미지를 추가",
+        "Clear recent" => "최근 항목 지우기",
+        "Remove entry" => "항목 삭제",
+        "Remove from the loaded results:" => "불러온 결과에서 삭제:",
+        "Rewrites" => "다시 저장할 파일:",
+        "No result JSON is loaded; only the table is affected." => "불러온 결과 JSON 이 없어 표에서만 지워집니다.",
+        "Also delete the bbox image file" => "bbox 이미지 파일도 삭제",
+        "Run database" => "실행 데이터베이스",
+        "The run database could not be opened; see the log." => "실행 데이터베이스를 열 수 없습니다. 로그를 확인하세요.",
+        "Verdict:" => "판정:",
+        "From:" => "시작:",
+        "To:" => "끝:",
+        "Reason contains:" => "이유에 포함:",
+        "Search" => "검색",
+        "Import existing results" => "기존 결과 가져오기",
+        "A/B comparison" => "A/B 비교",
+        "Agreement" => "일치",
+        "missing" => "없음",
+        "Verdict A" => "판정 A",
+        "Verdict B" => "판정 B",
+        "≠ disagree" => "≠ 불일치",
+        "Select a row to compare both bbox images side by side." => "행을 선택하면 두 bbox 이미지를 나란히 비교합니다.",
+        "Variant" => "변형",
+        "No bbox image." => "bbox 이미지가 없습니다.",
+        "Drag on the image to measure a region." => "이미지를 드래그해 영역을 측정하세요.",
+        "Selection:" => "선택:",
+        "Export crop..." => "잘라서 내보내기...",
+        "Open result JSON..." => "결과 JSON 열기...",
+        "Recent" => "최근 파일",
+        "Merge results..." => "결과 합치기...",
+        "Export CSV..." => "CSV 내보내기...",
+        "Export XLSX..." => "XLSX 내보내기...",
+        "Excel workbook with a results sheet and a summary sheet" => "결과 시트와 요약 시트가 있는 Excel 통합 문서",
+        "Copy as Markdown" => "Markdown 으로 복사",
+        "Export GeoJSON..." => "GeoJSON 내보내기...",
+        "Export images" => "이미지 내보내기",
+        "Export run bundle..." => "실행 번들 내보내기...",
+        "Export run bundle" => "실행 번들 내보내기",
+        "One zip with the result JSON, its notes and other sidecars, and every bbox image of the run" => "결과 JSON, 메모 등 사이드카, 실행의 모든 bbox 이미지를 zip 하나로 묶음",
+        "Import bundle..." => "번들 가져오기...",
+        "Import run bundle" => "실행 번들 가져오기",
+        "Check integrity" => "무결성 검사",
+        "Copy all" => "전체 복사",
+        "Save log..." => "로그 저장...",
+        "Project root" => "프로젝트 루트",
+        "Python environment" => "Python 환경",
+        "YOLO weights" => "YOLO 가중치",
+        "Gemini API key" => "Gemini API 키",
+        "Welcome" => "환영합니다",
+        "Back" => "이전",
+        "Finish" => "완료",
+        "Next" => "다음",
+        "Skip" => "건너뛰기",
+        "Start with the default settings; everything can be set in the left panel later" => "기본 설정으로 시작합니다. 모든 항목은 나중에 왼쪽 패널에서 설정할 수 있습니다",
+        "The folder that contains yolov8/run.py and gemini/run.py." => "yolov8/run.py 와 gemini/run.py 가 있는 폴더입니다.",
+        "Found a project at" => "프로젝트 발견:",
+        "The Python that runs the scripts. A .venv in the project root is used first when it exists." => "스크립트를 실행할 Python 입니다. 프로젝트 루트에 .venv 가 있으면 그것을 먼저 씁니다.",
+        "Check" => "확인",
+        "Reinstall packages into .venv" => "패키지를 .venv 에 다시 설치",
+        "Create .venv and install packages" => ".venv 만들고 패키지 설치",
+        "python -m venv .venv, then pip install -r requirements.txt; this can take a few minutes" => "python -m venv .venv 후 pip install -r requirements.txt (몇 분 걸릴 수 있음)",
+        "The trained YOLO model (.pt, or .onnx for native inference)." => "학습한 YOLO 모델 (.pt, 내장 추론은 .onnx)",
+        "(none)" => "(없음)",
+        "found in the project" => "개를 프로젝트에서 찾음",
+        "Optional: the key gemini/run.py uses. It is saved to .env in the project root, not to the app settings." => "선택 사항: gemini/run.py 가 쓰는 키입니다. 앱 설정이 아니라 프로젝트 루트의 .env 에 저장됩니다.",
+        "⚠ .env is tracked by git in this project; a key saved to it can end up in a commit. Run `git rm --cached .env` first."
+            => "⚠ 이 프로젝트의 .env 는 git 이 추적 중이라 여기에 저장한 키가 커밋에 들어갈 수 있습니다. 먼저 `git rm --cached .env` 를 실행하세요.",
+        "✔ .venv is ready" => "✔ .venv 준비 완료",
+        "Label" => "라벨",
+        "Select a row to give it a label." => "행을 선택하면 라벨을 붙일 수 있습니다.",
+        "Shown in the table instead of" => "표에서 이 이름 대신 표시:",
+        "portable" => "포터블",
+        "Settings and history are kept in" => "설정과 실행 기록 저장 위치:",
+        "View JSON" => "JSON 보기",
+        "The loaded result file as written, with a schema check" => "불러온 결과 파일 원문과 스키마 검사",
+        "Result JSON" => "결과 JSON",
+        "Validate against schema" => "스키마 검사",
+        "lines" => "줄",
+        "✔ every entry matches the result schema" => "✔ 모든 항목이 결과 스키마와 맞음",
+        "warnings" => "경고",
+        "Reload" => "다시 읽기",
+        "App data" => "앱 데이터",
+        "Export app data..." => "앱 데이터 내보내기...",
+        "Import app data..." => "앱 데이터 가져오기...",
+        "Export app data" => "앱 데이터 내보내기",
+        "Import app data" => "앱 데이터 가져오기",
+        "Settings, recent files, saved run history and the label/notes sidecars in results/, in one zip; result JSONs and images are not included" => "설정, 최근 파일, 저장된 실행 기록, results/ 의 라벨/메모 사이드카를 zip 하나로 저장 (결과 JSON 과 이미지는 제외)",
+        "Written" => "작성",
+        "Files that already exist here:" => "이미 있는 파일:",
+        "Keep them (skip)" => "그대로 두기 (건너뜀)",
+        "Overwrite them" => "덮어쓰기",
+        "Import" => "가져오기",
+        "Starred only" => "별표만",
+        "Starred" => "별표",
+        "Star (S)" => "별표 (S)",
+        "Export starred ▾" => "별표 내보내기 ▾",
+        "HTML page + bbox images (zip)..." => "HTML 페이지 + bbox 이미지 (zip)...",
+        "Check the project root: it must contain yolov8/run.py and gemini/run.py." => "프로젝트 루트를 확인하세요. yolov8/run.py 와 gemini/run.py 가 있어야 합니다.",
+        "Pick the YOLO weights file (.pt or .onnx) in the settings; download it again if it is empty." => "설정에서 YOLO 가중치 파일(.pt 또는 .onnx)을 선택하세요. 파일이 비어 있으면 다시 내려받으세요.",
+        "Create a virtual environment in the project root (python -m venv .venv) or set the Python path in the settings." => "프로젝트 루트에 가상환경을 만들거나(python -m venv .venv) 설정에서 Python 경로를 지정하세요.",
+        "The script's last error lines are above. Missing packages (pip install -r requirements.txt) and API keys are the usual causes." => "스크립트의 마지막 오류 줄이 위에 있습니다. 보통 패키지 누락(pip install -r requirements.txt)이나 API 키 문제입니다.",
+        "The script wrote a result file the runner can't read; check that the script matches this runner version." => "스크립트가 읽을 수 없는 결과 파일을 썼습니다. 스크립트와 러너 버전이 맞는지 확인하세요.",
+        "The run was cancelled and its partial output removed." => "실행이 취소되었고 중간 결과는 삭제되었습니다.",
+        "Raise the stage timeout in the settings, or run fewer images at once." => "설정에서 단계 시간 제한을 늘리거나 한 번에 처리할 이미지 수를 줄이세요.",
+        "Run label" => "실행 이름",
+        "Run info" => "실행 정보",
+        "Run" => "실행",
+        "Fit width" => "너비에 맞춤",
+        "Fit to window" => "창에 맞춤",
+        "Middle-drag or Space+drag to pan" => "가운데 버튼 또는 Space+드래그로 이동",
+        "Copy table" => "표 복사",
+        "Copied" => "복사함:",
+        "rows" => "행",
+        "Keep the bbox images of the last" => "bbox 이미지를 남길 최근 실행 수",
+        "0 = keep all; older runs are removed after each successful run, never the one just finished" => "0 = 모두 유지; 실행이 성공할 때마다 오래된 실행을 지우며 방금 끝난 실행은 지우지 않음",
+        "Reveal" => "위치 열기",
+        "Criteria" => "기준",
+        "Open original" => "원본 열기",
+        "Reveal original" => "원본 위치 열기",
+        "moved or deleted since the run" => "실행 후 옮겨졌거나 삭제됨",
+        "Run and export" => "실행 후 내보내기",
+        "Folder:" => "폴더:",
+        "\"Run and export\" writes these next to each other, named after the result file, once the run succeeds" => "\"실행 후 내보내기\"는 실행이 성공하면 결과 파일 이름으로 이 형식들을 함께 저장합니다",
+        "Load ground truth..." => "정답 불러오기...",
+        "Ground truth" => "정답 비교",
+        "scored" => "채점",
+        "accuracy" => "정확도",
+        "precision" => "정밀도",
+        "recall" => "재현율",
+        "Truth: accessible" => "정답: 접근 가능",
+        "Truth: not accessible" => "정답: 접근 불가",
+        "Model: accessible" => "모델: 접근 가능",
+        "Model: not accessible" => "모델: 접근 불가",
+        "labeled images were left undetermined by the model" => "개 라벨 이미지를 모델이 판정하지 못함",
+        "Labels without a result" => "결과가 없는 라벨",
+        "Results without a label" => "라벨이 없는 결과",
+        "Only disagreements" => "불일치만",
+        "Export evaluation..." => "평가 내보내기...",
+        "Unload" => "닫기",
+        "Hide reviewed" => "검토한 항목 숨기기",
+        "reviewed" => "검토",
+        "Reviewed" => "검토함",
+        "Open" => "열기",
+        "Show in folder" => "폴더에서 보기",
+        "Copy reason" => "이유 복사",
+        "Re-run this image" => "이 이미지 다시 판정",
+        "Set verdict" => "판정 지정",
+        "Criterion:" => "기준:",
+        "Any" => "전체",
+        "Add images first: pick files, drop them on the window, or put them in input_images" => "먼저 이미지를 추가하세요: 파일을 고르거나 창에 끌어다 놓거나 input_images 폴더에 넣으세요",
+        "History" => "기록",
+        "Run history" => "실행 기록",
+        "Keep across sessions" => "다음 실행에도 유지",
+        "No finished runs yet. Each completed run is listed here; click one to reload its results." => "아직 끝난 실행이 없습니다. 끝난 실행이 여기 쌓이며, 누르면 그 결과를 다시 불러옵니다.",
+        "Finished" => "완료 시각",
+        "Images" => "이미지",
+        "Result file" => "결과 파일",
+        "Clear" => "지우기",
+        "When a script fails:" => "스크립트가 실패하면:",
+        "Abort on stage failure" => "실행 중단",
+        "Continue and report" => "계속하고 보고",
+        "errors" => "오류",
+        "Native inference" => "네이티브 추론",
+        "Draw boxes on the images" => "이미지에 박스 그리기",
+        "Reads" => "읽는 환경 변수",
+        "Model" => "모델",
+        "script default" => "스크립트 기본값",
+        "Temperature" => "온도",
+        "Max output tokens" => "최대 출력 토큰",
+        "Empty fields keep gemini/run.py's defaults" => "비워 둔 항목은 gemini/run.py 기본값을 사용",
+        "Prices (USD)" => "가격 (USD)",
+        "per image" => "이미지당",
+        "per 1M tokens in" => "입력 100만 토큰당",
+        "per 1M tokens out" => "출력 100만 토큰당",
+        "Add model" => "모델 추가",
+        "Reset prices" => "가격 초기화",
+        "cost unknown" => "비용 알 수 없음",
+        "images" => "이미지",
+        "No price for this model under \"Judgment model\" → \"Prices (USD)\"" => "\"판정 모델\" → \"가격 (USD)\" 에 이 모델의 가격이 없습니다",
+        "Gemini cache" => "Gemini 캐시",
+        "Bypass cache" => "캐시 사용 안 함",
+        "Cache size" => "캐시 크기",
+        "Clear cache" => "캐시 비우기",
+        "Gemini chunks" => "Gemini 묶음 처리",
+        "Images per Gemini call" => "Gemini 호출당 이미지 수",
+        "0 = all images in one call" => "0 = 한 번에 모두",
+        "When a chunk fails:" => "묶음이 실패하면:",
+        "Stop the run" => "실행 중단",
+        "Skip it and continue" => "건너뛰고 계속",
+        "Extra script arguments" => "추가 스크립트 인자",
+        "Work dir retention" => "작업 폴더 보관 기간",
+        "Delete run logs and bbox images older than" => "다음보다 오래된 실행 로그와 bbox 이미지 삭제:",
+        "0 = keep everything; applied when a run starts" => "0 = 모두 보관; 실행 시작 시 적용",
+        "Webhook" => "웹훅",
+        "Send last results now" => "마지막 결과 지금 보내기",
+        "Share on LAN" => "LAN 공유",
+        "Port:" => "포트:",
+        "Start share server" => "공유 서버 시작",
+        "Stop" => "중지",
+        "Map link" => "지도 링크",
+        "Reset to OpenStreetMap" => "OpenStreetMap 으로 되돌리기",
+        "Stream verdicts while Gemini runs" => "Gemini 실행 중 판정 바로 표시",
+        "Watch results folder" => "결과 폴더 감시",
+        "Color-blind-safe palette" => "색각 이상 친화 색상",
+        "Duplicate images:" => "중복 이미지:",
+        "Show all" => "모두 표시",
+        "Newest only" => "최신만",
+        "Select images..." => "이미지 선택...",
+        "Paste image" => "이미지 붙여넣기",
+        "You can also drag & drop images here." => "이미지를 여기로 끌어다 놓아도 됩니다.",
+        "Add from URL" => "URL 에서 추가",
+        "Download" => "다운로드",
+        "Video" => "동영상",
+        "One frame every" => "프레임 간격",
+        "Prefix clashing names with their folder" => "이름이 겹치면 폴더 이름을 앞에 붙이기",
+        "Run automatically after a drop" => "끌어다 놓으면 자동 실행",
+        "Pending images" => "대기 중인 이미지",
+        "Remove" => "제거",
+        // run controls
+        "Resume last run" => "마지막 실행 이어하기",
+        "Discard" => "버리기",
+        "▶ Run" => "▶ 실행",
+        "Validate" => "검사",
+        "cancelling..." => "취소 중...",
+        "Cancel" => "취소",
+        "pausing..." => "일시 정지 중...",
+        "Pause" => "일시 정지",
+        // log
+        "Log" => "로그",
+        "Copy visible log" => "보이는 로그 복사",
+        "Info" => "정보",
+        "Warn" => "경고",
+        "Error" => "오류",
+        "App" => "앱",
+        "Follow" => "따라가기",
+        "search" => "검색",
+        "no match" => "일치 없음",
+        "Copy command log" => "명령 기록 복사",
+        "Save run script..." => "실행 스크립트 저장...",
+        "Open log file" => "로그 파일 열기",
+        // central panel
+        "Results" => "결과",
+        "Query" => "조회",
+        "A/B compare" => "A/B 비교",
+        "Re-run YOLO to regenerate" => "YOLO 다시 실행해서 재생성",
+        "Results preview" => "결과 미리보기",
+        "results:" => "개 결과:",
+        "Show:" => "표시:",
+        "Columns ▾" => "열 ▾",
+        "Table" => "표",
+        "Quick check" => "빠른 확인",
+        "Batch" => "일괄",
+        "Checking" => "확인 중:",
+        "Drop an image here to check it" => "확인할 이미지를 여기로 끌어다 놓으세요",
+        "Switch to Batch for the results table" => "결과 표는 일괄 모드에서 볼 수 있습니다",
+        "confidence" => "신뢰도",
+        "Grid" => "격자",
+        "Retry undetermined" => "미판정 다시 시도",
+        "Require confidence ≥" => "최소 신뢰도 ≥",
+        "Detected:" => "검출:",
+        "all" => "전체",
+        "Chart" => "차트",
+        "BBox" => "BBox",
+        "Image" => "이미지",
+        "Accessible" => "접근 가능",
+        "Lat, Lon" => "위도, 경도",
+        "Size" => "크기",
+        "File" => "파일",
+        "Taken" => "촬영",
+        "Reason" => "근거",
+        // bottom panels
+        "Selected BBox image" => "선택한 BBox 이미지",
+        "Click a filename in the table to select." => "표에서 파일 이름을 눌러 선택하세요.",
+        "Select a row above to preview its bbox image here." => "위에서 행을 선택하면 bbox 이미지가 여기에 표시됩니다.",
+        "Full reason" => "전체 근거",
+        "No reason available." => "근거가 없습니다.",
+        "Select a row to view the full reason." => "행을 선택하면 전체 근거가 표시됩니다.",
+        "Copy coords" => "좌표 복사",
+        "Show on map" => "지도에서 보기",
+        "Copy JSON" => "JSON 복사",
+        "Original:" => "원본:",
+        "No original image info recorded for this result" => "이 결과에는 원본 이미지 정보가 없습니다",
+        "Notes" => "메모",
+        "Select a row to add notes." => "행을 선택하면 메모를 남길 수 있습니다.",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn korean_falls_back_to_english_and_keeps_log_levels() {
+        set_lang(Lang::Ko);
+        assert_eq!(tr("Cancel"), "취소");
+        assert_eq!(tr("not translated yet"), "not translated yet");
+        assert_eq!(log_tag("[ERROR] yolo failed"), "[오류] yolo failed");
+        assert_eq!(log_tag("[stderr] x"), "[stderr] x");
+        set_lang(Lang::En);
+        assert_eq!(tr("Cancel"), "Cancel");
+    }
+}
@@ -0,0 +1,130 @@
+// bbox 이미지 내보내기: 원본 그대로 복사하거나 JPEG/PNG로 다시 인코딩 (백그라운드 스레드)
+// 대상 폴더에 같은 이름이 있으면 덮어쓰지 않고 _1, _2 를 붙여 저장
+use anyhow::{Context, Result};
+use eframe::egui;
+use image::{codecs::jpeg::JpegEncoder, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    // byte-for-byte copy
+    Original,
+    Jpeg,
+    Png,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Original, ExportFormat::Jpeg, ExportFormat::Png];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Original => "Original (copy)",
+            ExportFormat::Jpeg => "JPEG",
+            ExportFormat::Png => "PNG",
+        }
+    }
+}
+
+pub enum ExportEvent {
+    // (done, total)
+    Progress(usize, usize),
+    // (written, failed)
+    Finished(Result<(usize, usize)>),
+    Log(String),
+}
+
+pub fn spawn(files: Vec<PathBuf>, out_dir: PathBuf, format: ExportFormat, quality: u8, ctx: egui::Context) -> Receiver<ExportEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let send = |ev: ExportEvent| {
+            let _ = tx.send(ev);
+            ctx.request_repaint();
+        };
+        let res = fs::create_dir_all(&out_dir)
+            .with_context(|| format!("failed to create {}", out_dir.display()))
+            .map(|_| {
+                let mut failed = 0;
+                for (i, f) in files.iter().enumerate() {
+                    send(ExportEvent::Progress(i, files.len()));
+                    match export_one(f, &out_dir, format, quality) {
+                        Ok(Some(renamed)) => send(ExportEvent::Log(format!("[INFO] a file with that name exists, saved as {}\n", renamed.display()))),
+                        Ok(None) => {}
+                        Err(e) => {
+                            send(ExportEvent::Log(format!("[WARN] export failed: {} ({:#})\n", f.display(), e)));
+                            failed += 1;
+                        }
+                    }
+                }
+                (files.len() - failed, failed)
+            });
+        send(ExportEvent::Finished(res));
+    });
+    rx
+}
+
+// Some(path) when the name was taken and the image went to `path` instead
+fn export_one(src: &Path, out_dir: &Path, format: ExportFormat, quality: u8) -> Result<Option<PathBuf>> {
+    let name = src.file_name().with_context(|| "no file name")?;
+    let wanted = match format {
+        ExportFormat::Original => out_dir.join(name),
+        ExportFormat::Jpeg => out_dir.join(Path::new(name).with_extension("jpg")),
+        ExportFormat::Png => out_dir.join(Path::new(name).with_extension("png")),
+    };
+    let dst = free_path(&wanted);
+    let renamed = (dst != wanted).then(|| dst.clone());
+    if format == ExportFormat::Original {
+        fs::copy(src, &dst)?;
+        return Ok(renamed);
+    }
+    let img = image::open(src).with_context(|| "failed to decode")?;
+    let mut out = BufWriter::new(File::create(&dst)?);
+    match format {
+        // JPEG has no alpha channel
+        ExportFormat::Jpeg => JpegEncoder::new_with_quality(&mut out, quality.clamp(1, 100)).encode_image(&img.to_rgb8())?,
+        _ => img.write_to(&mut out, ImageFormat::Png)?,
+    }
+    Ok(renamed)
+}
+
+// files already in the folder are never overwritten: a taken name gets _1, _2, ...
+fn free_path(wanted: &Path) -> PathBuf {
+    let stem = wanted.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = wanted.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    let mut path = wanted.to_path_buf();
+    let mut n = 1;
+    while path.exists() {
+        path = wanted.with_file_name(format!("{}_{}{}", stem, n, ext));
+        n += 1;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn existing_files_are_kept_and_the_export_gets_a_new_name() {
+        let dir = std::env::temp_dir().join(format!("wheel_city_imgexport_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let out = dir.join("out");
+        fs::create_dir_all(&out).unwrap();
+        let src = dir.join("door.png");
+        image::RgbImage::from_pixel(4, 4, image::Rgb([200, 10, 10])).save(&src).unwrap();
+        fs::write(out.join("door.png"), b"keep").unwrap();
+
+        assert_eq!(export_one(&src, &out, ExportFormat::Original, 90).unwrap(), Some(out.join("door_1.png")));
+        assert_eq!(export_one(&src, &out, ExportFormat::Png, 90).unwrap(), Some(out.join("door_2.png")));
+        assert_eq!(export_one(&src, &out, ExportFormat::Jpeg, 90).unwrap(), None);
+        assert_eq!(fs::read(out.join("door.png")).unwrap(), b"keep");
+        assert!(image::open(out.join("door_2.png")).is_ok() && out.join("door.jpg").is_file());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,187 @@
+// 결과 JSON 원문 창: 들여쓰기를 정리해 줄 단위로 색칠해 보여주고, 스키마 검사 결과에서 항목을 누르면 그 줄로 이동
+// 큰 파일도 보이는 줄만 그림 (show_rows)
+use anyhow::{Context, Result};
+use eframe::egui::{self, text::LayoutJob, Color32, FontId, TextFormat};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    i18n::tr,
+    schema::{self, Issue, Severity},
+};
+
+pub struct JsonViewer {
+    pub path: PathBuf,
+    data: String,
+    // pretty-printed; invalid JSON is shown as written so the error's line number matches
+    lines: Vec<String>,
+    // first line of each `results` entry
+    entry_lines: Vec<usize>,
+    // None until validated
+    issues: Option<Vec<Issue>>,
+    scroll_to: Option<usize>,
+}
+
+impl JsonViewer {
+    pub fn open(path: &Path) -> Result<Self> {
+        let data = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let text = serde_json::from_str::<serde_json::Value>(&data).ok()
+            .and_then(|v| serde_json::to_string_pretty(&v).ok())
+            .unwrap_or_else(|| data.clone());
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+        let entry_lines = entry_lines(&lines);
+        Ok(Self { path: path.to_path_buf(), data, lines, entry_lines, issues: None, scroll_to: None })
+    }
+
+    pub fn validate(&mut self) {
+        self.issues = Some(schema::validate(&self.data));
+    }
+
+    // false once the window is closed
+    pub fn show(&mut self, ctx: &egui::Context) -> bool {
+        let mut open = true;
+        let mut reload = false;
+        let name = self.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        egui::Window::new(format!("{} — {}", tr("Result JSON"), name))
+            .id(egui::Id::new("json_viewer"))
+            .open(&mut open)
+            .default_size([640.0, 520.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Validate against schema")).clicked() { self.validate(); }
+                    if ui.button(tr("Reload")).clicked() { reload = true; }
+                    ui.weak(format!("{} {}", self.lines.len(), tr("lines")));
+                });
+                if let Some(issues) = &self.issues {
+                    if let Some(line) = self.show_issues(ui, issues) { self.scroll_to = Some(line); }
+                    ui.separator();
+                }
+                let font = egui::TextStyle::Monospace.resolve(ui.style());
+                let row_h = ui.fonts(|f| f.row_height(&font));
+                let mut area = egui::ScrollArea::both().id_source("json_text").auto_shrink([false, false]);
+                if let Some(line) = self.scroll_to.take() {
+                    area = area.vertical_scroll_offset(line as f32 * (row_h + ui.spacing().item_spacing.y));
+                }
+                let colors = Palette::new(ui.visuals());
+                area.show_rows(ui, row_h, self.lines.len(), |ui, range| {
+                    for line in &self.lines[range] {
+                        ui.add(egui::Label::new(highlight(line, &font, &colors)).wrap(false));
+                    }
+                });
+            });
+        if reload {
+            match Self::open(&self.path) {
+                Ok(mut v) => {
+                    if self.issues.is_some() { v.validate(); }
+                    *self = v;
+                }
+                Err(e) => self.issues = Some(vec![Issue { severity: Severity::Error, entry: None, path: String::new(), message: format!("{:#}", e) }]),
+            }
+        }
+        open
+    }
+
+    // the line of the entry whose issue was clicked
+    fn show_issues(&self, ui: &mut egui::Ui, issues: &[Issue]) -> Option<usize> {
+        if issues.is_empty() {
+            ui.colored_label(Color32::from_rgb(0, 160, 0), tr("✔ every entry matches the result schema"));
+            return None;
+        }
+        let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+        ui.label(format!("{} {}, {} {}", errors, tr("errors"), issues.len() - errors, tr("warnings")));
+        let mut target = None;
+        egui::ScrollArea::vertical().id_source("json_issues").max_height(140.0).show(ui, |ui| {
+            for issue in issues {
+                let (sym, color) = match issue.severity {
+                    Severity::Error => ("✖", Color32::from_rgb(220, 50, 50)),
+                    Severity::Warning => ("⚠", Color32::from_rgb(230, 160, 0)),
+                };
+                let text = egui::RichText::new(format!("{} {}: {}", sym, issue.path, issue.message)).color(color);
+                let line = issue.entry.and_then(|i| self.entry_lines.get(i).copied());
+                let r = ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+                if let Some(line) = line {
+                    if r.on_hover_cursor(egui::CursorIcon::PointingHand).clicked() { target = Some(line); }
+                }
+            }
+        });
+        target
+    }
+}
+
+// first line of each element of the top-level `results` array in serde_json's pretty output
+fn entry_lines(lines: &[String]) -> Vec<usize> {
+    let Some(start) = lines.iter().position(|l| l == "  \"results\": [") else { return vec![]; };
+    lines.iter().enumerate().skip(start + 1)
+        .take_while(|(_, l)| !l.starts_with("  ]"))
+        .filter(|(_, l)| l.len() > 4 && l.starts_with("    ") && !l[4..].starts_with([' ', '}', ']']))
+        .map(|(n, _)| n)
+        .collect()
+}
+
+struct Palette {
+    plain: Color32,
+    key: Color32,
+    string: Color32,
+    number: Color32,
+    keyword: Color32,
+}
+
+impl Palette {
+    fn new(visuals: &egui::Visuals) -> Self {
+        if visuals.dark_mode {
+            Self {
+                plain: visuals.text_color(),
+                key: Color32::from_rgb(156, 220, 254),
+                string: Color32::from_rgb(206, 145, 120),
+                number: Color32::from_rgb(181, 206, 168),
+                keyword: Color32::from_rgb(86, 156, 214),
+            }
+        } else {
+            Self {
+                plain: visuals.text_color(),
+                key: Color32::from_rgb(0, 80, 160),
+                string: Color32::from_rgb(163, 21, 21),
+                number: Color32::from_rgb(9, 134, 88),
+                keyword: Color32::from_rgb(0, 0, 255),
+            }
+        }
+    }
+}
+
+// one line of JSON; strings never span lines, so no state is carried between lines
+fn highlight(line: &str, font: &FontId, colors: &Palette) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < line.len() {
+        let c = bytes[i];
+        let (end, color) = if c == b'"' {
+            let mut j = i + 1;
+            while j < bytes.len() {
+                match bytes[j] {
+                    b'\\' => j += 2,
+                    b'"' => { j += 1; break; }
+                    _ => j += 1,
+                }
+            }
+            let mut j = j.min(line.len());
+            while !line.is_char_boundary(j) { j += 1; }
+            let key = line[j..].trim_start().starts_with(':');
+            (j, if key { colors.key } else { colors.string })
+        } else if c == b'-' || c.is_ascii_digit() {
+            let n = line[i + 1..].find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))).map_or(line.len(), |n| i + 1 + n);
+            (n, colors.number)
+        } else if c.is_ascii_alphabetic() {
+            let n = line[i..].find(|c: char| !c.is_ascii_alphabetic()).map_or(line.len(), |n| i + n);
+            (n, colors.keyword)
+        } else {
+            let n = line[i..].find(|c: char| c == '"' || c == '-' || c.is_ascii_alphanumeric()).map_or(line.len(), |n| i + n);
+            (n.max(i + line[i..].chars().next().map_or(1, char::len_utf8)), colors.plain)
+        };
+        job.append(&line[i..end], 0.0, TextFormat::simple(font.clone(), color));
+        i = end;
+    }
+    job
+}
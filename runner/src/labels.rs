@@ -0,0 +1,177 @@
+// YOLO 라벨 파일(<bbox dir>/labels/<stem>.txt: class cx cy w h [conf], 0~1 정규화 좌표)을 읽어
+// 원본 이미지 위에 박스를 직접 그리는 데 사용. 라벨이 없는 예전 실행은 bbox 이미지를 그대로 보여줌
+// 클래스 이름: yolov8/classes.txt → yolov8/data.yaml → labels/names.json → 번호 순으로 찾음
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    pub class: String,
+    pub conf: Option<f32>,
+    // normalized center x/y, width, height
+    pub cx: f32,
+    pub cy: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl Detection {
+    // (x0, y0, x1, y1) in pixels of a `width` x `height` image, clamped to it
+    pub fn pixel_rect(&self, width: f32, height: f32) -> [f32; 4] {
+        let x0 = ((self.cx - self.w / 2.0) * width).clamp(0.0, width);
+        let y0 = ((self.cy - self.h / 2.0) * height).clamp(0.0, height);
+        let x1 = ((self.cx + self.w / 2.0) * width).clamp(0.0, width);
+        let y1 = ((self.cy + self.h / 2.0) * height).clamp(0.0, height);
+        [x0, y0, x1, y1]
+    }
+}
+
+pub fn labels_dir(bbox_dir: &Path) -> PathBuf {
+    bbox_dir.join("labels")
+}
+
+// class id → name; yolov8/classes.txt (one name per line) or the `names` of yolov8/data.yaml,
+// else what yolov8/run.py recorded for the run; ids without a name are shown as numbers
+pub fn class_names(project_root: Option<&Path>, bbox_dir: &Path) -> HashMap<usize, String> {
+    if let Some(yolo) = project_root.map(|r| r.join("yolov8")) {
+        if let Ok(text) = fs::read_to_string(yolo.join("classes.txt")) {
+            let names: HashMap<usize, String> = text.lines().map(str::trim).enumerate()
+                .filter(|(_, n)| !n.is_empty())
+                .map(|(i, n)| (i, n.to_string()))
+                .collect();
+            if !names.is_empty() { return names; }
+        }
+        if let Some(names) = fs::read_to_string(yolo.join("data.yaml")).ok().map(|t| yaml_names(&t)).filter(|n| !n.is_empty()) {
+            return names;
+        }
+    }
+    let names: HashMap<String, String> = read_json(&labels_dir(bbox_dir).join("names.json")).unwrap_or_default();
+    names.into_iter().filter_map(|(k, v)| Some((k.parse().ok()?, v))).collect()
+}
+
+// the `names` key of a YOLO data.yaml, in any of the forms ultralytics accepts:
+// `names: [a, b]`, a `- a` list, or a `0: a` map
+fn yaml_names(text: &str) -> HashMap<usize, String> {
+    let unquote = |s: &str| s.trim().trim_matches(['"', '\'']).to_string();
+    let mut out = HashMap::new();
+    let mut lines = text.lines().skip_while(|l| !l.starts_with("names:"));
+    let Some(first) = lines.next() else { return out; };
+    let inline = first["names:".len()..].trim();
+    if let Some(list) = inline.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        return list.split(',').map(unquote).filter(|n| !n.is_empty()).enumerate().collect();
+    }
+    let mut next_index = 0;
+    // PyYAML writes list items unindented (`- a` right under `names:`)
+    for line in lines.take_while(|l| l.starts_with([' ', '\t', '-']) || l.trim().is_empty()) {
+        let line = line.trim();
+        if let Some(item) = line.strip_prefix("- ") {
+            out.insert(next_index, unquote(item));
+            next_index += 1;
+        } else if let Some((k, v)) = line.split_once(':') {
+            if let Ok(id) = k.trim().parse() { out.insert(id, unquote(v)); }
+        }
+    }
+    out
+}
+
+pub struct Labels {
+    dir: PathBuf,
+    // class id → name (labels/names.json from yolov8/run.py)
+    names: HashMap<usize, String>,
+    // parsed label files by image name
+    cache: HashMap<String, Vec<Detection>>,
+}
+
+impl Labels {
+    // None for runs made before label files were saved
+    pub fn load(bbox_dir: &Path, names: HashMap<usize, String>) -> Option<Self> {
+        let dir = labels_dir(bbox_dir);
+        if !dir.is_dir() { return None; }
+        Some(Self {
+            names,
+            dir,
+            cache: HashMap::new(),
+        })
+    }
+
+    // YOLO writes no label file for an image without detections
+    pub fn detections(&mut self, image: &str) -> &[Detection] {
+        if !self.cache.contains_key(image) {
+            let stem = Path::new(image).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let text = fs::read_to_string(self.dir.join(format!("{}.txt", stem))).unwrap_or_default();
+            self.cache.insert(image.to_string(), parse(&text, &self.names));
+        }
+        &self.cache[image]
+    }
+
+    // class → label files that contain it, in the shape of `classes::load`; label file names
+    // share the image stem, so `classes::contains` matches them to results
+    pub fn images_by_class(&self) -> HashMap<String, Vec<String>> {
+        let mut out: HashMap<String, Vec<String>> = HashMap::new();
+        let files = fs::read_dir(&self.dir).into_iter().flatten().flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "txt"));
+        for p in files {
+            let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let mut classes: Vec<String> = parse(&fs::read_to_string(&p).unwrap_or_default(), &self.names)
+                .into_iter().map(|d| d.class).collect();
+            classes.sort();
+            classes.dedup();
+            for c in classes { out.entry(c).or_default().push(name.clone()); }
+        }
+        for images in out.values_mut() { images.sort(); }
+        out
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+// unknown class ids are shown as the number
+fn parse(text: &str, names: &HashMap<usize, String>) -> Vec<Detection> {
+    text.lines().filter_map(|line| {
+        let mut it = line.split_whitespace();
+        let id: usize = it.next()?.parse().ok()?;
+        let v: Vec<f32> = it.map(|x| x.parse().ok()).collect::<Option<_>>()?;
+        if v.len() < 4 { return None; }
+        Some(Detection {
+            class: names.get(&id).cloned().unwrap_or_else(|| id.to_string()),
+            conf: v.get(4).copied(),
+            cx: v[0], cy: v[1], w: v[2], h: v[3],
+        })
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_yaml_names_in_every_form() {
+        let map = "path: yolov8\nnames:\n  0: ramp\n  1: 'barrier'\ntrain: a.txt\n";
+        assert_eq!(yaml_names(map), HashMap::from([(0, "ramp".to_string()), (1, "barrier".to_string())]));
+        let list = "names:\n- ramp\n- barrier\nnc: 2\n";
+        assert_eq!(yaml_names(list), HashMap::from([(0, "ramp".to_string()), (1, "barrier".to_string())]));
+        let list = "names:\n  - ramp\n  - \"barrier\"\nnc: 2\n";
+        assert_eq!(yaml_names(list)[&1], "barrier");
+        let inline = "nc: 2\nnames: ['ramp', barrier]\n";
+        assert_eq!(yaml_names(inline), HashMap::from([(0, "ramp".to_string()), (1, "barrier".to_string())]));
+    }
+
+    #[test]
+    fn label_lines_become_pixel_rects() {
+        let names = HashMap::from([(0, "ramp".to_string())]);
+        let dets = parse("0 0.5 0.5 0.2 0.4 0.91\n3 0.1 0.1 0.4 0.4\nbad line\n", &names);
+        assert_eq!(dets.len(), 2);
+        assert_eq!((dets[0].class.as_str(), dets[0].conf), ("ramp", Some(0.91)));
+        let close = |a: [f32; 4], b: [f32; 4]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-3);
+        assert!(close(dets[0].pixel_rect(1000.0, 500.0), [400.0, 150.0, 600.0, 350.0]));
+        // unknown id, and a box hanging off the top-left corner
+        assert_eq!((dets[1].class.as_str(), dets[1].conf), ("3", None));
+        assert!(close(dets[1].pixel_rect(100.0, 100.0), [0.0, 0.0, 30.0, 30.0]));
+    }
+}
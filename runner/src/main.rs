@@ -6,22 +6,116 @@ use eframe::{egui, egui::Color32};
 use eframe::egui::Widget;
 use egui_extras::{TableBuilder, Column};
 use rfd::FileDialog;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     env, fs,
     path::{Path, PathBuf},
     process::Command,
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
 };
 
+mod appdata;
+mod applog;
+mod backend;
+mod bundle;
+mod cache;
+mod chart;
+mod checkpoint;
+mod classes;
+mod clipboard;
+mod compare;
+mod convert;
+mod cost;
+mod db;
+mod decode;
+mod download;
+mod error;
+mod exif_info;
+mod export;
+mod groundtruth;
+mod imgexport;
+mod jsonview;
+mod history;
+mod i18n;
+mod labels;
+mod merge;
+mod native;
+mod pipeline;
+mod preflight;
+mod prompt;
+mod recent;
+mod script;
+mod server;
+mod settings;
+mod schema;
+mod share;
+mod sidecar;
+mod sources;
+mod thumbs;
+mod timing;
+mod video;
+mod watch;
+mod webhook;
+mod wizard;
+mod workdir;
+
+use applog::{LogBuffer, LogFilter, LogLevel, RunLogFile};
+use i18n::tr;
+use checkpoint::Checkpoint;
+use compare::CompareRow;
+use db::{DbQuery, DbRow, RunDb, VerdictFilter};
+use pipeline::{GeminiParams, RunEvent};
+use script::{ScriptKind, ScriptStep};
+use settings::Settings;
+use error::RunnerError;
+use sidecar::Sidecar;
+use timing::RunTiming;
+
 #[derive(Debug, Deserialize, Clone)]
 struct WheelResultFile { results: Vec<WheelOne> }
-#[derive(Debug, Deserialize, Clone)]
-struct WheelOne { image: String, result: WheelJudge }
-#[derive(Debug, Deserialize, Clone)]
-struct WheelJudge { accessible: Option<bool>, reason: String }
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct WheelOne {
+    image: String,
+    // empty for an errored entry
+    #[serde(default)]
+    result: WheelJudge,
+    // from the original photo's GPS EXIF (kept in the sidecar); None when it had none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lat: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lon: Option<f64>,
+    // the verdict came from the Gemini cache instead of a new call
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    cached: bool,
+    // the script could not judge this image ({"image": ..., "error": ...})
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    // reviewer's display name ("Main St ramp #3") shown in place of `image`; files are still found by `image`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+}
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+struct WheelJudge {
+    accessible: Option<bool>,
+    reason: String,
+    // only when the prompt asks the model for one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    confidence: Option<f64>,
+    // per-criterion verdicts (ramp, door width, ...) when the prompt asks for them; the table shows one column per key
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    criteria: BTreeMap<String, Option<bool>>,
+}
 
 fn main() {
+    if let Some(args) = server::ServeArgs::from_env() {
+        if let Err(e) = server::serve(args) {
+            eprintln!("[ERROR] {e:#}");
+            std::process::exit(error::exit_code(&e));
+        }
+        return;
+    }
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size(egui::vec2(1024.0, 720.0))
@@ -29,123 +123,1101 @@ fn main() {
         ..Default::default()
     };
     if let Err(e) = eframe::run_native(
-        "Wheel City AI 2 – Runner",
+        APP_TITLE,
         native_options,
-        Box::new(|_cc| Box::new(AppState::default())),
+        Box::new(|cc| {
+            i18n::install_korean_font(&cc.egui_ctx);
+            Box::new(AppState::new())
+        }),
     ) {
         eprintln!("Failed to start app: {e:?}");
     }
 }
 
+// how rows sharing the same `image` name are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DupMode {
+    // every entry, duplicates tagged with #n
+    ShowAll,
+    // only the last (newest) entry per name, with a ×n badge
+    NewestOnly,
+}
+
 struct AppState {
     // inputs
     pending_files: Vec<PathBuf>,
     // logs & results
-    log: String,
+    log: LogBuffer,
+    log_filter: LogFilter,
+    log_follow: bool,
+    log_search: String,
+    // position in the current match list
+    log_match: usize,
+    // per-run log file while a run is active
+    log_file: Option<RunLogFile>,
+    last_log_path: Option<PathBuf>,
     last_json_path: Option<PathBuf>,
     results: Vec<WheelOne>,
-    // config
-    python_bin: String,
-    weights_path: String,
-    project_root: String,
+    sidecar: Sidecar,
+    // run label/notes typed but not yet in the sidecar file; written when the field loses focus
+    run_info_dirty: bool,
+    // "Run label" box next to the Run button; given to each run started from there
+    run_label: String,
+    // what the title bar was last set to (the loaded run's label or file name)
+    window_title: String,
+    // persisted config; written back once it differs from `saved_settings` and no field is being typed in
+    settings: Settings,
+    saved_settings: Settings,
+    // when `settings` first differed from `saved_settings`
+    settings_dirty_since: Option<std::time::Instant>,
     // caches
     tex_cache: HashMap<String, egui::TextureHandle>,
+    // thumbnails decoded so far this frame (see THUMB_DECODES_PER_FRAME)
+    thumb_decodes: usize,
+    thumb_stats: thumbs::Stats,
+    // background decoding of the loaded result's thumbnails; started on the frame after a load
+    thumb_prefetch: Option<decode::Prefetch>,
+    prefetch_due: bool,
+    // pending files whose thumbnail could not be decoded (videos, broken files); not retried
+    pending_thumb_failed: HashSet<PathBuf>,
+    // the queued tiles without a texture yet, decoded off the UI thread
+    pending_prefetch: Option<decode::Prefetch>,
+    // (occurrence #, total with this name) per entry of `results`; rebuilt when stale
+    duplicates: Vec<(usize, usize)>,
+    // larger previews decoded off the UI thread (`hover:` keys)
+    decoder: decode::Decoder,
+    // results row under the pointer and since when, for the hover preview
+    hover: Option<(usize, std::time::Instant)>,
+    // results table header widths of the last frame, and whether a column drag is in progress
+    column_widths_seen: Vec<(String, f32)>,
+    column_resized: bool,
     last_run_bbox_dir: Option<PathBuf>,
-    // UI selection
-    selected_image: Option<String>,
+    // UI selection (index into `results`, so duplicate image names stay distinct)
+    selected: Option<usize>,
+    dup_mode: DupMode,
+    result_verdict: VerdictFilter,
+    // criteria column the table is filtered on (empty: none) and the verdict it has to have
+    result_criterion: String,
+    result_criterion_verdict: VerdictFilter,
+    // leave rows the user already marked reviewed out of the table
+    hide_reviewed: bool,
+    // "★ Starred only" chip
+    starred_only: bool,
+    // image name → the photo it was copied from (results/sources_<ts>.json); empty for older results
+    sources: BTreeMap<String, PathBuf>,
+    // human labels loaded with "Load ground truth...", and whether the table shows only the rows that disagree
+    ground_truth: Option<groundtruth::GroundTruth>,
+    gt_disagreements_only: bool,
+    // entry awaiting delete confirmation
+    pending_removal: Option<usize>,
+    remove_bbox_too: bool,
+    // app data file picked for import, awaiting confirmation
+    pending_app_import: Option<(PathBuf, appdata::Manifest)>,
+    // (row, its image, text) of the label being typed; written to the result JSON when the field loses focus
+    label_edit: Option<(usize, String, String)>,
+    // first-launch setup, shown until finished or skipped
+    wizard: Option<wizard::Wizard>,
+    // "View JSON" window
+    json_viewer: Option<jsonview::JsonViewer>,
+    app_import_conflict: appdata::Conflict,
+    // background run, if any
+    run_rx: Option<std::sync::mpsc::Receiver<RunEvent>>,
+    // log lines from a manual "Send last results now"
+    webhook_rx: Option<std::sync::mpsc::Receiver<String>>,
+    // LAN read-only view of the loaded results
+    share: Option<share::ShareServer>,
+    // background bbox image export and its (done, total)
+    image_export_rx: Option<std::sync::mpsc::Receiver<imgexport::ExportEvent>>,
+    image_export_progress: (usize, usize),
+    // run bundle export/import in progress; true for an import
+    bundle_rx: Option<(std::sync::mpsc::Receiver<bundle::BundleEvent>, bool)>,
+    // YOLO class → images it was detected in, for the loaded run; empty without the sidecar
+    classes: HashMap<String, Vec<String>>,
+    // YOLO label files of the loaded run
+    labels: Option<labels::Labels>,
+    // region dragged on the big preview
+    measure: Option<Measure>,
+    preview_zoom: PreviewZoom,
+    // the big preview's scroll area last frame; Space over it pans instead of marking reviewed
+    preview_rect: Option<egui::Rect>,
+    // "Add from URL" text box and the download in progress
+    url_input: String,
+    download_rx: Option<std::sync::mpsc::Receiver<download::DownloadEvent>>,
+    // background PyTorch/CUDA probe from the "Check GPU" button; the result lands in the preflight cache
+    gpu_probe_rx: Option<std::sync::mpsc::Receiver<Result<preflight::Accel, String>>>,
+    // "Validate" checks on a worker thread; the GPU probe alone imports torch for seconds
+    validate_rx: Option<std::sync::mpsc::Receiver<Vec<preflight::CheckResult>>>,
+    // follows results/ while "Watch results folder" is on
+    results_watcher: Option<watch::ResultsWatcher>,
+    // "Save log..." writing on a worker thread: (file, entries written or the error)
+    log_save_rx: Option<std::sync::mpsc::Receiver<(PathBuf, std::io::Result<usize>)>>,
+    // classes toggled off in the "Detected:" row; hides their rows and their preview boxes
+    hidden_classes: HashSet<String>,
+    run_cancel: Option<Arc<AtomicBool>>,
+    run_pause: Option<Arc<AtomicBool>>,
+    run_timing: Option<RunTiming>,
+    // Gemini model of the run in progress and the token usage its script reported so far
+    run_usage: Option<(String, cost::Usage)>,
+    // steps of the last run, replayable as a script
+    command_log: Vec<ScriptStep>,
+    command_log_started: String,
+    // A/B comparison run
+    ab_enabled: bool,
+    weights_b_path: String,
+    comparison: Option<ComparisonView>,
+    // optional run database + query tab
+    db: Option<RunDb>,
+    db_unavailable: bool,
+    central_tab: CentralTab,
+    db_query: DbQuery,
+    db_rows: Vec<DbRow>,
+    // finished runs, newest first (History tab); loaded from the config dir when persisted
+    run_history: Vec<history::RunRecord>,
+    // chart: per-run counts from the DB, shown next to the current table
+    chart_recent_runs: bool,
+    // (json path, counts)
+    chart_history: Vec<(String, chart::BarGroup)>,
+    // short message in the bottom-right corner
+    toast: Option<(String, std::time::Instant)>,
+    // interrupted run found at startup, with how many images it had judged
+    resume_offer: Option<(Checkpoint, usize)>,
+    // bbox dir already reported missing, so the warning is logged once
+    bbox_missing_warned: Option<PathBuf>,
+    // validation of `project_root`, redone only when the text changes
+    root_check: Option<RootCheck>,
+    // auto-run after a drop starts once drops have stopped arriving until this time
+    auto_run_at: Option<std::time::Instant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathAction {
+    Open,
+    Reveal,
+}
+
+const APP_TITLE: &str = "Wheel City AI 2 – Runner";
+
+// runs from the database shown in the chart
+const CHART_RECENT_RUNS: usize = 10;
+
+// table thumbnails decoded per frame; the rest show a placeholder until the next frame,
+// so scrolling through a large result set doesn't stall on image decoding
+const THUMB_DECODES_PER_FRAME: usize = 4;
+// results above this are not prefetched (the decoded thumbnails stay in memory until the next load)
+const THUMB_PREFETCH_MAX: usize = 1000;
+// table thumbnail box in points
+const THUMB_SIZE: egui::Vec2 = egui::vec2(72.0, 54.0);
+// grid view tile (thumbnail box) in points
+const GRID_TILE: egui::Vec2 = egui::vec2(160.0, 120.0);
+// pending images tile in the left panel
+const PENDING_TILE: egui::Vec2 = egui::vec2(96.0, 72.0);
+// hover preview: pointer rest time over a thumbnail/filename, and the longest side in points
+const HOVER_DELAY: std::time::Duration = std::time::Duration::from_millis(300);
+const HOVER_SIZE: f32 = 400.0;
+
+const TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(4);
+// longest a settings change waits for the text field being typed in to lose focus
+const SETTINGS_SAVE_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+// quiet time after the last dropped file before auto-run starts, so a batch drop is one run
+const AUTO_RUN_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(800);
+
+// a rectangle dragged on the big preview, in pixels of `image`
+struct Measure {
+    image: PathBuf,
+    a: egui::Pos2,
+    b: egui::Pos2,
+}
+
+impl Measure {
+    fn rect(&self) -> egui::Rect {
+        egui::Rect::from_two_pos(self.a, self.b)
+    }
+}
+
+// how the big preview is scaled into its scroll area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreviewZoom {
+    // as wide as the area, never above 100%
+    Width,
+    // the whole image visible, width and height
+    Window,
+    // 100%
+    Actual,
+}
+
+impl PreviewZoom {
+    fn scale(self, size: egui::Vec2, avail: egui::Vec2) -> f32 {
+        match self {
+            PreviewZoom::Width => (avail.x / size.x).min(1.0),
+            PreviewZoom::Window => (avail.x / size.x).min(avail.y / size.y),
+            PreviewZoom::Actual => 1.0,
+        }
+    }
+}
+
+// results vs. bbox images of the loaded run
+struct IntegrityReport {
+    bbox_dir: Option<PathBuf>,
+    results: usize,
+    bbox_images: usize,
+    // result entries with no bbox image
+    missing: Vec<String>,
+    // bbox images no result refers to
+    orphans: Vec<String>,
+}
+
+struct RootCheck {
+    text: String,
+    valid: bool,
+    // what auto-detection would pick instead
+    detected: Option<PathBuf>,
+}
+
+impl RootCheck {
+    fn usable(&self) -> bool {
+        self.valid || self.detected.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CentralTab {
+    Results,
+    History,
+    Query,
+    Compare,
+}
+
+// result of the last A/B run, shown in the Compare tab
+struct ComparisonView {
+    rows: Vec<CompareRow>,
+    bbox_a: PathBuf,
+    bbox_b: PathBuf,
+    summary_path: PathBuf,
+    selected: Option<usize>,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         Self {
             pending_files: vec![],
-            log: String::new(),
+            log: LogBuffer::default(),
+            log_filter: LogFilter::default(),
+            log_follow: true,
+            log_search: String::new(),
+            log_match: 0,
+            log_file: None,
+            last_log_path: None,
             last_json_path: None,
             results: vec![],
-            python_bin: "python3".to_string(),
-            weights_path: "yolov8/train_result/ver14/weights/best.pt".to_string(),  // 학습한 모델중 가장 성능이 좋은 ver14 사용
-            project_root: ".".to_string(),
+            sidecar: Sidecar::default(),
+            run_info_dirty: false,
+            run_label: String::new(),
+            window_title: APP_TITLE.to_string(),
+            settings: Settings::default(),
+            saved_settings: Settings::default(),
+            settings_dirty_since: None,
             tex_cache: HashMap::new(),
+            thumb_decodes: 0,
+            thumb_stats: thumbs::Stats::default(),
+            thumb_prefetch: None,
+            prefetch_due: false,
+            pending_thumb_failed: HashSet::new(),
+            pending_prefetch: None,
+            duplicates: vec![],
+            decoder: decode::Decoder::default(),
+            hover: None,
+            column_widths_seen: vec![],
+            column_resized: false,
             last_run_bbox_dir: None,
-            selected_image: None,
+            selected: None,
+            dup_mode: DupMode::ShowAll,
+            result_verdict: VerdictFilter::All,
+            result_criterion: String::new(),
+            result_criterion_verdict: VerdictFilter::All,
+            hide_reviewed: false,
+            starred_only: false,
+            sources: BTreeMap::new(),
+            ground_truth: None,
+            gt_disagreements_only: false,
+            pending_removal: None,
+            pending_app_import: None,
+            label_edit: None,
+            wizard: None,
+            json_viewer: None,
+            app_import_conflict: appdata::Conflict::Skip,
+            remove_bbox_too: false,
+            run_rx: None,
+            webhook_rx: None,
+            share: None,
+            image_export_rx: None,
+            image_export_progress: (0, 0),
+            bundle_rx: None,
+            classes: HashMap::new(),
+            labels: None,
+            measure: None,
+            preview_zoom: PreviewZoom::Width,
+            preview_rect: None,
+            url_input: String::new(),
+            download_rx: None,
+            gpu_probe_rx: None,
+            validate_rx: None,
+            log_save_rx: None,
+            results_watcher: None,
+            hidden_classes: HashSet::new(),
+            run_cancel: None,
+            run_pause: None,
+            run_timing: None,
+            run_usage: None,
+            command_log: vec![],
+            command_log_started: String::new(),
+            ab_enabled: false,
+            weights_b_path: String::new(),
+            comparison: None,
+            db: None,
+            db_unavailable: false,
+            central_tab: CentralTab::Results,
+            db_query: DbQuery::default(),
+            db_rows: vec![],
+            run_history: vec![],
+            chart_recent_runs: false,
+            chart_history: vec![],
+            root_check: None,
+            bbox_missing_warned: None,
+            resume_offer: None,
+            toast: None,
+            auto_run_at: None,
         }
     }
 }
 
+impl AppState {
+    fn new() -> Self {
+        let settings = Settings::load();
+        let resume_offer = find_project_root(&settings.project_root).and_then(|root| resume_offer_in(&root));
+        let run_history = if settings.persist_history { history::load() } else { vec![] };
+        let wizard = (!settings::exists()).then(|| wizard::Wizard::new(&settings));
+        Self { saved_settings: settings.clone(), settings, resume_offer, run_history, wizard, ..Default::default() }
+    }
+}
+
 impl eframe::App for AppState {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.flush_run_info();
+        self.persist_settings();
+        if let Some(mut s) = self.share.take() { s.stop(); }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.set_debug_on_hover(false);
+        // the last frame loaded no thumbnail, so a burst (first screen, a scroll) is over
+        if self.thumb_decodes == 0 {
+            if let Some(summary) = self.thumb_stats.take_summary() {
+                self.append_log(&format!("[INFO] {}\n", summary));
+            }
+        }
+        self.thumb_decodes = 0;
+        i18n::set_lang(self.settings.lang);
+        self.poll_run();
+        self.sync_window_title(ctx);
+        self.poll_webhook();
+        self.poll_image_export();
+        self.poll_bundle();
+        self.poll_downloads();
+        self.poll_gpu_probe();
+        self.poll_validate();
+        self.poll_log_save();
+        self.sync_results_watcher(ctx);
+        self.poll_decoded(ctx);
+        self.poll_prefetch(ctx);
+        self.persist_settings_when_idle(ctx);
+        self.check_bbox_dir();
 
         // drag & drop
-        for dropped in &ctx.input(|i| i.raw.dropped_files.clone()) {
-            if let Some(path) = &dropped.path { self.pending_files.push(path.clone()); }
+        let dropped: Vec<PathBuf> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|f| f.path.clone()).collect());
+        if self.settings.quick_mode && !dropped.is_empty() {
+            self.quick_check(ctx, dropped);
+        } else if !dropped.is_empty() {
+            self.add_pending(dropped);
+            if self.settings.auto_run_on_drop {
+                self.auto_run_at = Some(std::time::Instant::now() + AUTO_RUN_DEBOUNCE);
+            }
+        }
+        self.auto_run(ctx);
+
+        // egui-winit only reports Ctrl+V when the clipboard also holds text, so an image-only
+        // clipboard needs the "Paste image" button
+        let pasted = ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Paste(_))));
+        if pasted && !ctx.wants_keyboard_input() {
+            self.paste_image(true);
         }
 
         egui::TopBottomPanel::top("top").show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.heading("Wheel City AI 2 – Runner");
-                if ui.button("Close").clicked() {
+                ui.heading(APP_TITLE);
+                if let Some(dir) = settings::portable_dir() {
+                    ui.weak(tr("portable")).on_hover_text(format!("{} {}", tr("Settings and history are kept in"), dir.display()));
+                }
+                if ui.button(tr("Close")).clicked() {
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                 }
+                ui.menu_button(tr("App data"), |ui| {
+                    if ui.button(tr("Export app data...")).on_hover_text(tr("Settings, recent files, saved run history and the label/notes sidecars in results/, in one zip; result JSONs and images are not included")).clicked() {
+                        ui.close_menu();
+                        self.export_app_data();
+                    }
+                    if ui.button(tr("Import app data...")).clicked() {
+                        ui.close_menu();
+                        self.pick_app_import();
+                    }
+                });
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    egui::ComboBox::from_id_source("lang")
+                        .selected_text(self.settings.lang.label())
+                        .show_ui(ui, |ui| {
+                            for lang in i18n::Lang::ALL {
+                                ui.selectable_value(&mut self.settings.lang, lang, lang.label());
+                            }
+                        });
+                    ui.label(tr("Language"));
+                });
             });
         });
 
         egui::SidePanel::left("left").resizable(true).show(ctx, |ui| {
             ui.group(|ui| {
-                ui.label("Python executable (path or command)");
-                ui.text_edit_singleline(&mut self.python_bin);
+                ui.label(tr("Project root (contains yolov8/ and gemini/)"));
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.settings.project_root);
+                    if ui.button(tr("Browse...")).clicked() {
+                        if let Some(p) = FileDialog::new().pick_folder() {
+                            self.settings.project_root = p.to_string_lossy().to_string();
+                        }
+                    }
+                });
+                let (valid, detected) = { let c = self.root_check(); (c.valid, c.detected.clone()) };
+                if valid {
+                    ui.colored_label(Color32::from_rgb(0,160,0), tr("✔ yolov8/run.py and gemini/run.py found"));
+                } else if let Some(found) = detected {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.colored_label(Color32::from_rgb(230,160,0), format!("✖ not a project root; Run will use {}", found.display()));
+                        if ui.small_button(tr("Use it")).clicked() {
+                            self.settings.project_root = found.to_string_lossy().to_string();
+                        }
+                    });
+                } else {
+                    ui.colored_label(Color32::from_rgb(220,50,50), tr("✖ yolov8/run.py or gemini/run.py not found here"));
+                }
             });
 
             ui.add_space(8.0);
             ui.group(|ui| {
-                ui.label("YOLO weights path (best.pt)");
-                ui.text_edit_singleline(&mut self.weights_path);
-                if ui.button("Select file").clicked() {
-                    if let Some(p) = FileDialog::new().add_filter("pt", &["pt"]).pick_file() {
-                        self.weights_path = p.to_string_lossy().to_string();
+                ui.label(tr("Python executable (path or command)"));
+                ui.text_edit_singleline(&mut self.settings.python_bin);
+                ui.horizontal_wrapped(|ui| {
+                    match preflight::last_gpu() {
+                        Some((py, preflight::Accel::Cuda(name))) => {
+                            ui.colored_label(Color32::from_rgb(0,160,0), format!("✔ GPU: {}", name)).on_hover_text(py);
+                        }
+                        Some((py, preflight::Accel::Cpu)) => {
+                            ui.colored_label(Color32::from_rgb(230,160,0), tr("⚠ CPU only: YOLO will be slow"))
+                                .on_hover_text(format!("PyTorch in {} sees no CUDA GPU", py));
+                        }
+                        None => { ui.weak(tr("GPU not checked yet")); }
                     }
-                }
+                    if self.gpu_probe_rx.is_some() {
+                        ui.spinner();
+                    } else if ui.small_button(tr("Check GPU")).on_hover_text("Ask PyTorch in this interpreter whether CUDA is available").clicked() {
+                        self.start_gpu_probe(ctx);
+                    }
+                });
             });
 
             ui.add_space(8.0);
             ui.group(|ui| {
-                if ui.button("Select images...").clicked() {
-                    if let Some(files) = FileDialog::new()
-                        .add_filter("images", &["jpg","jpeg","png","webp","bmp"])
-                        .pick_files()
+                ui.label(tr("YOLO weights path (best.pt)"));
+                ui.text_edit_singleline(&mut self.settings.weights_path);
+                if ui.button(tr("Select file")).clicked() {
+                    if let Some(p) = FileDialog::new().add_filter("weights", &["pt", "onnx"]).pick_file() {
+                        self.settings.weights_path = p.to_string_lossy().to_string();
+                    }
+                }
+                if native::is_onnx(&self.settings.weights_path) {
+                    let hint = if native::available() {
+                        "Run the ONNX model inside the runner (no Python/ultralytics needed for detection)"
+                    } else {
+                        "This build was made without native inference (cargo build --features native-inference)"
+                    };
+                    ui.add_enabled(native::available(), egui::Checkbox::new(&mut self.settings.native_inference, tr("Native inference")))
+                        .on_hover_text(hint);
+                    if self.settings.native_inference && native::available() {
+                        ui.checkbox(&mut self.settings.native_draw_boxes, tr("Draw boxes on the images"))
+                            .on_hover_text("Off: the images go to Gemini unannotated; the boxes are still saved as label files");
+                    }
+                }
+                ui.checkbox(&mut self.ab_enabled, tr("A/B run with a second weights file"));
+                if self.ab_enabled {
+                    ui.label(tr("Weights B"));
+                    ui.text_edit_singleline(&mut self.weights_b_path);
+                    if ui.button(tr("Select file")).clicked() {
+                        if let Some(p) = FileDialog::new().add_filter("weights", &["pt", "onnx"]).pick_file() {
+                            self.weights_b_path = p.to_string_lossy().to_string();
+                        }
+                    }
+                }
+            });
+
+            ui.collapsing(tr("Stage timeouts"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("YOLO:");
+                    ui.add(egui::DragValue::new(&mut self.settings.stage_timeouts.yolo).clamp_range(0..=86400).suffix(" s"));
+                    ui.label(tr("Gemini (whole batch):"));
+                    ui.add(egui::DragValue::new(&mut self.settings.stage_timeouts.gemini).clamp_range(0..=86400).suffix(" s"));
+                });
+                ui.label(egui::RichText::new(tr("0 = no limit")).weak());
+                ui.horizontal(|ui| {
+                    ui.label(tr("When a script fails:"));
+                    ui.radio_value(&mut self.settings.stage_failure, pipeline::StageFailure::Abort, tr("Abort on stage failure"));
+                    ui.radio_value(&mut self.settings.stage_failure, pipeline::StageFailure::Continue, tr("Continue and report"))
+                        .on_hover_text("Keep what the script wrote before it failed; images without a verdict are marked as errors and can be retried");
+                });
+            });
+            ui.collapsing(tr("Prompt template"), |ui| {
+                ui.label(egui::RichText::new(tr("Sent to gemini/run.py as --prompt_file; empty = the script's own prompt. Placeholders like {image_name} are passed through as written.")).weak());
+                ui.add(egui::TextEdit::multiline(&mut self.settings.prompt_template)
+                    .code_editor()
+                    .desired_rows(8)
+                    .desired_width(f32::INFINITY));
+                ui.horizontal(|ui| {
+                    let (added, removed) = prompt::line_diff(&self.settings.prompt_template_default, &self.settings.prompt_template);
+                    if added + removed == 0 {
+                        ui.label(egui::RichText::new(tr("same as the saved default")).weak());
+                    } else {
+                        ui.colored_label(Color32::from_rgb(0,160,0), format!("+{}", added));
+                        ui.colored_label(Color32::from_rgb(220,50,50), format!("−{}", removed));
+                        ui.label(tr("lines vs the saved default"));
+                    }
+                    let modified = added + removed > 0;
+                    if ui.add_enabled(modified, egui::Button::new(tr("Save as default"))).clicked() {
+                        self.settings.prompt_template_default = self.settings.prompt_template.clone();
+                    }
+                    if ui.add_enabled(modified, egui::Button::new(tr("Revert to default"))).clicked() {
+                        self.settings.prompt_template = self.settings.prompt_template_default.clone();
+                    }
+                });
+            });
+            ui.collapsing(tr("Judgment model"), |ui| {
+                let g = &mut self.settings.gemini;
+                let current = backend::find(&self.settings.backend);
+                egui::Grid::new("gemini_params").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("Backend"));
+                    egui::ComboBox::from_id_source("judgment_backend")
+                        .selected_text(current.label())
+                        .show_ui(ui, |ui| {
+                            for b in backend::BACKENDS {
+                                if ui.selectable_label(b.id() == current.id(), b.label()).clicked() && b.id() != current.id() {
+                                    self.settings.backend = b.id().to_string();
+                                    // another backend's model name would only fail
+                                    g.model.clear();
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text(format!("{}: {}", tr("Reads"), current.env_vars().join(", ")));
+                    ui.end_row();
+                    ui.label(tr("Model"));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut g.model).hint_text(tr("script default")).desired_width(180.0));
+                        ui.menu_button("▾", |ui| {
+                            for &m in current.models() {
+                                if ui.button(m).clicked() {
+                                    g.model = m.to_string();
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    });
+                    ui.end_row();
+                    ui.label(tr("Temperature"));
+                    ui.add(egui::TextEdit::singleline(&mut g.temperature).hint_text("0 – 2").desired_width(80.0));
+                    ui.end_row();
+                    ui.label(tr("Max output tokens"));
+                    ui.add(egui::TextEdit::singleline(&mut g.max_tokens).hint_text("1 – 65536").desired_width(80.0));
+                    ui.end_row();
+                });
+                ui.label(egui::RichText::new(tr("Empty fields keep gemini/run.py's defaults")).weak());
+                if let Err(e) = g.check() {
+                    ui.colored_label(Color32::from_rgb(220,50,50), format!("{:#}", e));
+                }
+                ui.collapsing(tr("Prices (USD)"), |ui| {
+                    let mut remove = None;
+                    egui::Grid::new("model_prices").num_columns(5).show(ui, |ui| {
+                        ui.label(tr("Model"));
+                        ui.label(tr("per image"));
+                        ui.label(tr("per 1M tokens in"));
+                        ui.label(tr("per 1M tokens out"));
+                        ui.end_row();
+                        for (i, p) in self.settings.model_prices.iter_mut().enumerate() {
+                            ui.add(egui::TextEdit::singleline(&mut p.model).desired_width(140.0));
+                            for v in [&mut p.per_image, &mut p.per_million_in, &mut p.per_million_out] {
+                                ui.add(egui::DragValue::new(v).clamp_range(0.0..=1000.0).speed(0.01).max_decimals(4));
+                            }
+                            if ui.small_button("🗑").clicked() { remove = Some(i); }
+                            ui.end_row();
+                        }
+                    });
+                    if let Some(i) = remove { self.settings.model_prices.remove(i); }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("Add model")).clicked() {
+                            self.settings.model_prices.push(cost::ModelPrice { model: String::new(), per_image: 0.0, per_million_in: 0.0, per_million_out: 0.0 });
+                        }
+                        if ui.button(tr("Reset prices")).clicked() {
+                            self.settings.model_prices = cost::default_prices();
+                        }
+                    });
+                });
+            });
+            ui.collapsing(tr("Gemini cache"), |ui| {
+                ui.checkbox(&mut self.settings.bypass_cache, tr("Bypass cache"))
+                    .on_hover_text("Send every image to Gemini even when the same image, prompt and model were judged before");
+                let path = preflight::project_root(&self.settings.project_root).ok()
+                    .map(|root| cache::cache_path(&root.join(".runner_work")));
+                let size = path.as_deref().map(cache::size_on_disk).unwrap_or(0);
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}: {:.1} MB", tr("Cache size"), size as f64 / 1_048_576.0));
+                    let running = self.run_rx.is_some();
+                    if ui.add_enabled(!running && size > 0, egui::Button::new(tr("Clear cache"))).clicked() {
+                        if let Some(p) = &path {
+                            match cache::clear(p) {
+                                Ok(()) => self.append_log("[INFO] Gemini cache cleared\n"),
+                                Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+                            }
+                        }
+                    }
+                });
+            });
+            ui.collapsing(tr("Gemini chunks"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Images per Gemini call"));
+                    ui.add(egui::DragValue::new(&mut self.settings.gemini_chunk_size).clamp_range(0..=100000));
+                });
+                ui.label(egui::RichText::new(tr("0 = all images in one call")).weak());
+                ui.horizontal(|ui| {
+                    ui.label(tr("When a chunk fails:"));
+                    ui.radio_value(&mut self.settings.chunk_failure, pipeline::ChunkFailure::Abort, tr("Stop the run"));
+                    ui.radio_value(&mut self.settings.chunk_failure, pipeline::ChunkFailure::Skip, tr("Skip it and continue"));
+                });
+            });
+            ui.collapsing(tr("Extra script arguments"), |ui| {
+                for (label, args) in [
+                    ("YOLO (yolov8/run.py)", &mut self.settings.extra_yolo_args),
+                    ("Gemini (gemini/run.py)", &mut self.settings.extra_gemini_args),
+                ] {
+                    ui.label(label);
+                    ui.add(egui::TextEdit::singleline(args).hint_text("e.g. --imgsz 1280 --device cpu"));
+                    if let Err(e) = script::split_args(args) {
+                        ui.colored_label(Color32::from_rgb(220,50,50), e);
+                    }
+                }
+            });
+            ui.collapsing(tr("Work dir retention"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Delete run logs and bbox images older than"));
+                    ui.add(egui::DragValue::new(&mut self.settings.retention_days).clamp_range(0..=3650).suffix(" days"));
+                });
+                ui.label(egui::RichText::new(tr("0 = keep everything; applied when a run starts")).weak());
+                ui.horizontal(|ui| {
+                    ui.label(tr("Keep the bbox images of the last"));
+                    ui.add(egui::DragValue::new(&mut self.settings.keep_last_n_runs).clamp_range(0..=1000).suffix(" runs"));
+                });
+                ui.label(egui::RichText::new(tr("0 = keep all; older runs are removed after each successful run, never the one just finished")).weak());
+            });
+            ui.collapsing(tr("Run and export"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Folder:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.settings.run_export_dir).hint_text("e.g. ~/wheel_exports"));
+                    if ui.button(tr("Browse")).clicked() {
+                        if let Some(p) = FileDialog::new().pick_folder() {
+                            self.settings.run_export_dir = p.to_string_lossy().to_string();
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.settings.run_export_csv, "CSV");
+                    ui.checkbox(&mut self.settings.run_export_json, "JSON");
+                    ui.checkbox(&mut self.settings.run_export_xlsx, "XLSX");
+                });
+                ui.label(egui::RichText::new(tr("\"Run and export\" writes these next to each other, named after the result file, once the run succeeds")).weak());
+            });
+            ui.collapsing(tr("Webhook"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("URL:");
+                    ui.add(egui::TextEdit::singleline(&mut self.settings.webhook_url).hint_text("https://… (empty = off)").desired_width(f32::INFINITY));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Bearer token:");
+                    ui.add(egui::TextEdit::singleline(&mut self.settings.webhook_token).password(true).desired_width(f32::INFINITY));
+                });
+                ui.label(egui::RichText::new("The result JSON is POSTed after each successful run; the token is stored in the settings file").weak());
+                let can_send = self.webhook_rx.is_none() && self.last_json_path.is_some() && !self.settings.webhook_url.trim().is_empty();
+                if ui.add_enabled(can_send, egui::Button::new(tr("Send last results now"))).clicked() {
+                    self.send_webhook(ctx);
+                }
+            });
+            ui.collapsing(tr("Share on LAN"), |ui| {
+                let active = self.share.is_some();
+                ui.horizontal(|ui| {
+                    ui.label(tr("Port:"));
+                    ui.add_enabled(!active, egui::DragValue::new(&mut self.settings.share_port).clamp_range(1024..=65535));
+                    if active {
+                        if ui.button(tr("Stop")).clicked() { self.stop_share(); }
+                    } else if ui.button(tr("Start share server"))
+                        .on_hover_text("Serve the loaded results and bbox images read-only to browsers on this network")
+                        .clicked()
                     {
-                        self.pending_files.extend(files);
+                        self.start_share();
+                    }
+                });
+                if let Some(s) = &self.share {
+                    ui.hyperlink(&s.url);
+                }
+            });
+            ui.collapsing(tr("Map link"), |ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.settings.map_url_template).desired_width(f32::INFINITY));
+                ui.label(egui::RichText::new("URL opened by \"Show on map\"; {lat} and {lon} are replaced").weak());
+                if ui.small_button(tr("Reset to OpenStreetMap")).clicked() {
+                    self.settings.map_url_template = settings::DEFAULT_MAP_URL.to_string();
+                }
+            });
+
+            ui.add_space(8.0);
+            ui.checkbox(&mut self.settings.stream_results, tr("Stream verdicts while Gemini runs"))
+                .on_hover_text("Add rows to the table as each image finishes.\nWhen off, the rows appear when the Gemini stage ends; progress is shown either way.");
+            ui.checkbox(&mut self.settings.watch_results, tr("Watch results folder"))
+                .on_hover_text("Load the newest results/result_*.json once it is fully written,\ne.g. from the headless mode or a run started outside the app");
+            ui.checkbox(&mut self.settings.colorblind_palette, tr("Color-blind-safe palette"))
+                .on_hover_text("Use blue/orange instead of green/red for verdicts");
+            ui.horizontal(|ui| {
+                ui.label(tr("Duplicate images:"));
+                egui::ComboBox::from_id_source("dup_mode")
+                    .selected_text(match self.dup_mode { DupMode::ShowAll => tr("Show all"), DupMode::NewestOnly => tr("Newest only") })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.dup_mode, DupMode::ShowAll, tr("Show all"));
+                        ui.selectable_value(&mut self.dup_mode, DupMode::NewestOnly, tr("Newest only"));
+                    });
+            });
+
+            ui.add_space(8.0);
+            ui.group(|ui| {
+                if ui.button(tr("Select images...")).clicked() {
+                    let media: Vec<&str> = IMAGE_EXTS.iter().chain(video::VIDEO_EXTS).copied().collect();
+                    let mut dialog = FileDialog::new()
+                        .add_filter("images and videos", &media)
+                        .add_filter("images", IMAGE_EXTS)
+                        .add_filter("videos", video::VIDEO_EXTS);
+                    if let Some(dir) = self.settings.recent_input_dirs.first() { dialog = dialog.set_directory(&dir.path); }
+                    if let Some(files) = dialog.pick_files() {
+                        self.add_pending(files);
                     }
                 }
                 ui.add_space(4.0);
-                ui.label("You can also drag & drop images here.");
-                ui.separator();
-                ui.label(egui::RichText::new("Pending images").strong());
-                let mut remove_idx: Option<usize> = None;
-                for (i, p) in self.pending_files.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Paste image")).on_hover_text("Add the image on the clipboard (e.g. a screenshot) as a PNG").clicked() {
+                        self.paste_image(false);
+                    }
+                    ui.label(tr("You can also drag & drop images here."));
+                });
+                egui::CollapsingHeader::new(tr("Add from URL")).id_source("add_from_url").show(ui, |ui| {
+                    ui.add(egui::TextEdit::multiline(&mut self.url_input)
+                        .hint_text("https://… (one per line)")
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY));
                     ui.horizontal(|ui| {
-                        ui.label(format!("• {}", p.display()));
-                        if ui.small_button("Remove").clicked() { remove_idx = Some(i); }
+                        let busy = self.download_rx.is_some();
+                        if ui.add_enabled(!busy && !self.url_input.trim().is_empty(), egui::Button::new(tr("Download"))).clicked() {
+                            self.start_downloads(ctx);
+                        }
+                        if busy { ui.spinner(); }
+                        ui.label("Max");
+                        ui.add(egui::DragValue::new(&mut self.settings.download_max_mb).clamp_range(1..=1024).suffix(" MB"));
                     });
-                }
-                if let Some(i) = remove_idx { self.pending_files.remove(i); }
+                });
+                egui::CollapsingHeader::new(tr("Video")).id_source("video_frames").show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("ffmpeg");
+                        ui.text_edit_singleline(&mut self.settings.ffmpeg_bin)
+                            .on_hover_text("Command name or full path; checked by Validate when a video is pending");
+                        if ui.button(tr("Browse")).clicked() {
+                            if let Some(p) = FileDialog::new().pick_file() {
+                                self.settings.ffmpeg_bin = p.to_string_lossy().to_string();
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("One frame every"));
+                        ui.add(egui::DragValue::new(&mut self.settings.frame_interval_secs).clamp_range(1..=600).suffix(" s"));
+                    });
+                });
+                ui.checkbox(&mut self.settings.prefix_folder_names, tr("Prefix clashing names with their folder"))
+                    .on_hover_text("site1/a.jpg and site2/a.jpg become site1_a.jpg and site2_a.jpg instead of a.jpg and a_1.jpg");
+                ui.checkbox(&mut self.settings.auto_run_on_drop, tr("Run automatically after a drop"))
+                    .on_hover_text("Start a run once files stop arriving; a drop during a run is only queued");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(tr("Pending images")).strong());
+                    if !self.pending_files.is_empty() {
+                        ui.weak(format!("({})", self.pending_files.len()));
+                        if ui.small_button(tr("Clear")).clicked() { self.pending_files.clear(); }
+                    }
+                });
+                self.show_pending_grid(ui, ctx);
                 ui.add_space(8.0);
 
-                if ui.button(egui::RichText::new("▶ Run").color(Color32::WHITE)).clicked() {
-                    if let Err(e) = self.run_pipeline() {
-                        self.append_log(&format!("[ERROR] {}\n", e));
+                let running = self.run_rx.is_some();
+                if let Some((cp, judged)) = &self.resume_offer {
+                    let (resume, discard) = ui.group(|ui| {
+                        ui.label(format!("Unfinished run {}: {}/{} images judged", cp.ts, judged, cp.images.len()));
+                        ui.horizontal(|ui| {
+                            (ui.add_enabled(!running, egui::Button::new(tr("Resume last run"))).clicked(),
+                             ui.add_enabled(!running, egui::Button::new(tr("Discard"))).clicked())
+                        }).inner
+                    }).inner;
+                    if resume {
+                        if let Err(e) = self.resume_run(ctx) {
+                            self.append_log(&format!("[ERROR] {:#}\n", e));
+                        }
+                    } else if discard {
+                        if let Ok(root) = self.resolve_project_root() { Checkpoint::clear(&root.join(".runner_work")); }
+                        self.resume_offer = None;
                     }
-                    ctx.request_repaint();
+                }
+                let root_ok = self.root_check().usable();
+                ui.horizontal(|ui| {
+                    ui.label(tr("Run label"));
+                    ui.add(egui::TextEdit::singleline(&mut self.run_label)
+                        .hint_text("e.g. Gangnam station survey")
+                        .desired_width(240.0))
+                        .on_hover_text("Optional. Kept with the run's results and shown in History, exports and the title bar; files stay named by time");
+                });
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!running && root_ok, egui::Button::new(egui::RichText::new(tr("▶ Run")).color(Color32::WHITE))).clicked() {
+                        if let Err(e) = self.run_pipeline(ctx) {
+                            self.append_log(&format!("[ERROR] {}\n", e));
+                        }
+                        ctx.request_repaint();
+                    }
+                    let export = self.auto_export();
+                    let hover = match &export {
+                        Some(e) => format!("Run, then write {} to {}", [("CSV", e.csv), ("JSON", e.json), ("XLSX", e.xlsx)]
+                            .iter().filter(|f| f.1).map(|f| f.0).collect::<Vec<_>>().join(" + "), e.dir.display()),
+                        None => "Set a folder and at least one format under \"Run and export\" first".to_string(),
+                    };
+                    if ui.add_enabled(!running && root_ok && export.is_some(), egui::Button::new(tr("Run and export")))
+                        .on_hover_text(&hover)
+                        .on_disabled_hover_text(&hover)
+                        .clicked()
+                    {
+                        if let Err(e) = self.start_run(ctx, export) {
+                            self.append_log(&format!("[ERROR] {}\n", e));
+                        }
+                        ctx.request_repaint();
+                    }
+                    if ui.add_enabled(!running && self.validate_rx.is_none(), egui::Button::new(tr("Validate")))
+                        .on_hover_text("Run the pre-flight checks without copying files or starting YOLO/Gemini")
+                        .clicked()
+                    {
+                        self.validate(ctx);
+                    }
+                    if self.validate_rx.is_some() { ui.spinner(); }
+                    if !running && !self.pending_files.is_empty() {
+                        let model = self.settings.gemini.model.trim();
+                        let est = cost::estimate(&self.settings.model_prices, model, self.pending_files.len());
+                        let label = match est {
+                            Some(_) => format!("≈ {}", cost::format_usd(est)),
+                            None => tr("cost unknown").to_string(),
+                        };
+                        // an empty field runs the script's default model, so that is what gets priced
+                        let priced = if model.is_empty() { GeminiParams::DEFAULT_MODEL } else { model };
+                        let hover = match est {
+                            Some(_) => format!("{} × {} ({})", self.pending_files.len(), tr("images"), priced),
+                            None => format!("{}: {}", tr("No price for this model under \"Judgment model\" → \"Prices (USD)\""), priced),
+                        };
+                        ui.label(egui::RichText::new(label).weak()).on_hover_text(hover);
+                    }
+                    if running {
+                        ui.spinner();
+                        let cancelling = self.run_cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed));
+                        let pausing = self.run_pause.as_ref().is_some_and(|c| c.load(Ordering::Relaxed));
+                        if cancelling {
+                            ui.label(tr("cancelling..."));
+                        } else if ui.button(tr("Cancel")).clicked() {
+                            if let Some(c) = &self.run_cancel { c.store(true, Ordering::Relaxed); }
+                        }
+                        if pausing && !cancelling {
+                            ui.label(tr("pausing...")).on_hover_text("Stopping after the current step; the finished part is kept");
+                        } else if !cancelling {
+                            // A/B runs keep no checkpoint, so there is nothing to resume
+                            let pausable = self.run_pause.is_some();
+                            if ui.add_enabled(pausable, egui::Button::new(tr("Pause")))
+                                .on_hover_text("Stop after the image in progress and keep what is done; continue later with \"Resume last run\"")
+                                .on_disabled_hover_text("A/B runs cannot be paused")
+                                .clicked()
+                            {
+                                if let Some(c) = &self.run_pause { c.store(true, Ordering::Relaxed); }
+                            }
+                        }
+                    }
+                });
+                if let Some(t) = &self.run_timing {
+                    ui.label(t.status_line());
+                    // keep the elapsed clock ticking
+                    ctx.request_repaint_after(std::time::Duration::from_secs(1));
                 }
             });
 
             ui.add_space(12.0);
             ui.separator();
-            ui.label(egui::RichText::new("Log").strong());
-            egui::ScrollArea::vertical()
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(tr("Log")).strong());
+                ui.label(egui::RichText::new(format!("{} lines", self.log.len())).weak());
+                if ui.add_enabled(!self.log.is_empty(), egui::Button::new(tr("Copy visible log")).small()).clicked() {
+                    let text = self.log.iter()
+                        .filter(|e| self.log_filter.accepts(e))
+                        .map(|e| e.display())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ui.output_mut(|o| o.copied_text = text);
+                }
+                if ui.add_enabled(!self.log.is_empty(), egui::Button::new(tr("Copy all")).small())
+                    .on_hover_text(tr("Every line in the log, whatever the filters show"))
+                    .clicked()
+                {
+                    let text = self.log.iter().map(|e| e.display()).collect::<Vec<_>>().join("\n");
+                    ui.output_mut(|o| o.copied_text = text);
+                    self.show_toast(format!("{} {}", tr("Log lines copied:"), self.log.len()));
+                }
+                if ui.add_enabled(!self.log.is_empty() && self.log_save_rx.is_none(), egui::Button::new(tr("Save log...")).small()).clicked() {
+                    self.save_log(ctx);
+                }
+                if self.log_save_rx.is_some() { ui.spinner(); }
+            });
+            ui.horizontal_wrapped(|ui| {
+                let f = &mut self.log_filter;
+                ui.checkbox(&mut f.info, tr("Info"));
+                ui.checkbox(&mut f.warn, tr("Warn"));
+                ui.checkbox(&mut f.error, tr("Error"));
+                ui.separator();
+                ui.checkbox(&mut f.app, tr("App"));
+                ui.checkbox(&mut f.yolo, "YOLO");
+                ui.checkbox(&mut f.gemini, "Gemini");
+            });
+            let visible: Vec<usize> = self.log.iter().enumerate()
+                .filter(|(_, e)| self.log_filter.accepts(e))
+                .map(|(i, _)| i)
+                .collect();
+            let needle = self.log_search.trim().to_lowercase();
+            // rows (positions in `visible`) whose entry matches the search
+            let matches: Vec<usize> = if needle.is_empty() { vec![] } else {
+                visible.iter().enumerate()
+                    .filter(|(_, &i)| self.log.get(i).is_some_and(|e| e.matches(&needle)))
+                    .map(|(row, _)| row)
+                    .collect()
+            };
+            let mut jump_to: Option<usize> = None;
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.log_follow, tr("Follow"))
+                    .on_hover_text("Keep the newest lines in view");
+                ui.separator();
+                let search = ui.add(egui::TextEdit::singleline(&mut self.log_search).hint_text(tr("search")).desired_width(120.0));
+                if search.changed() { self.log_match = 0; }
+                let enter = search.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let has = !matches.is_empty();
+                if ui.add_enabled(has, egui::Button::new("◀").small()).clicked() {
+                    self.log_match = (self.log_match + matches.len() - 1) % matches.len();
+                    jump_to = Some(matches[self.log_match]);
+                }
+                if ui.add_enabled(has, egui::Button::new("▶").small()).clicked() || (enter && has) {
+                    self.log_match = (self.log_match + 1) % matches.len();
+                    jump_to = Some(matches[self.log_match]);
+                }
+                if has {
+                    self.log_match = self.log_match.min(matches.len() - 1);
+                    ui.label(format!("{}/{}", self.log_match + 1, matches.len()));
+                } else if !needle.is_empty() {
+                    ui.label(egui::RichText::new(tr("no match")).weak());
+                }
+            });
+            if jump_to.is_some() {
+                // a jump means the user wants to look back; don't yank the view to the bottom
+                self.log_follow = false;
+            }
+            let current = matches.get(self.log_match).copied();
+            let row_h = ui.text_style_height(&egui::TextStyle::Monospace);
+            let mut scroll = egui::ScrollArea::vertical()
                 .id_source("log_scroll")
                 .max_height(220.0)
-                .show(ui, |ui| { ui.monospace(&self.log); });
+                .auto_shrink([false, true])
+                .stick_to_bottom(self.log_follow);
+            if let Some(row) = jump_to {
+                let spacing = ui.spacing().item_spacing.y;
+                scroll = scroll.vertical_scroll_offset((row as f32 * (row_h + spacing) - 80.0).max(0.0));
+            }
+            let mut path_action: Option<(PathAction, PathBuf)> = None;
+            scroll.show_rows(ui, row_h, visible.len(), |ui, range| {
+                for row in range {
+                    let Some(e) = self.log.get(visible[row]) else { continue; };
+                    let highlight = if current == Some(row) {
+                        Some(Color32::from_rgba_unmultiplied(255, 200, 0, 110))
+                    } else if matches.binary_search(&row).is_ok() {
+                        Some(Color32::from_rgba_unmultiplied(255, 200, 0, 45))
+                    } else { None };
+                    let styled = |s: &str| {
+                        let mut text = egui::RichText::new(s).monospace();
+                        match e.level {
+                            LogLevel::Error => text = text.color(Color32::from_rgb(220,50,50)),
+                            LogLevel::Warn => text = text.color(Color32::from_rgb(230,160,0)),
+                            LogLevel::Info => {}
+                        }
+                        if let Some(bg) = highlight { text = text.background_color(bg); }
+                        text
+                    };
+                    if e.paths.is_empty() {
+                        ui.add(egui::Label::new(styled(&format!("{}{}", e.prefix(), i18n::log_tag(&e.text)))).wrap(false));
+                        continue;
+                    }
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        ui.add(egui::Label::new(styled(&e.prefix())).wrap(false));
+                        for (seg, is_path) in e.segments() {
+                            if !is_path {
+                                ui.add(egui::Label::new(styled(&i18n::log_tag(seg))).wrap(false));
+                                continue;
+                            }
+                            let mut link = egui::RichText::new(seg).monospace();
+                            if let Some(bg) = highlight { link = link.background_color(bg); }
+                            let resp = ui.add(egui::Link::new(link)).on_hover_text(tr("Click to show in the file manager"));
+                            if resp.clicked() { path_action = Some((PathAction::Reveal, PathBuf::from(seg))); }
+                            resp.context_menu(|ui| {
+                                if ui.button(tr("Open")).clicked() {
+                                    path_action = Some((PathAction::Open, PathBuf::from(seg)));
+                                    ui.close_menu();
+                                }
+                                if ui.button(tr("Show in folder")).clicked() {
+                                    path_action = Some((PathAction::Reveal, PathBuf::from(seg)));
+                                    ui.close_menu();
+                                }
+                                if ui.button(tr("Copy path")).clicked() {
+                                    ui.output_mut(|o| o.copied_text = seg.to_string());
+                                    ui.close_menu();
+                                }
+                            });
+                        }
+                    });
+                }
+            });
+            if let Some((action, path)) = path_action {
+                self.open_log_path(action, &path);
+            }
 
             ui.add_space(8.0);
             if let Some(p) = &self.last_json_path {
@@ -154,58 +1226,334 @@ impl eframe::App for AppState {
             if let Some(d) = &self.last_run_bbox_dir {
                 ui.label(format!("Last run bbox dir: {}", d.display()));
             }
+            ui.horizontal(|ui| {
+                if ui.button(tr("Open result JSON...")).clicked() {
+                    if let Some(p) = FileDialog::new().add_filter("json", &["json"]).pick_file() {
+                        if let Err(e) = self.load_results_file(&p, None) {
+                            self.append_log(&format!("[ERROR] {}\n", e));
+                            // the field errors are easier to act on than serde's first one
+                            self.open_json_viewer(&p, true);
+                        }
+                    }
+                }
+                if ui.add_enabled(self.last_json_path.is_some(), egui::Button::new(tr("View JSON")))
+                    .on_hover_text(tr("The loaded result file as written, with a schema check"))
+                    .clicked()
+                {
+                    if let Some(p) = self.last_json_path.clone() { self.open_json_viewer(&p, false); }
+                }
+                ui.menu_button(tr("Recent"), |ui| self.recent_menu(ui));
+                if ui.button(tr("Merge results...")).clicked() {
+                    self.merge_results();
+                }
+                if ui.add_enabled(!self.results.is_empty(), egui::Button::new(tr("Load ground truth...")))
+                    .on_hover_text("CSV with image and accessible columns, to score the loaded results against human labels")
+                    .clicked()
+                {
+                    self.load_ground_truth();
+                }
+                if ui.add_enabled(!self.results.is_empty(), egui::Button::new(tr("Export CSV..."))).clicked() {
+                    self.export_csv();
+                }
+                if ui.add_enabled(!self.results.is_empty(), egui::Button::new(tr("Export XLSX...")))
+                    .on_hover_text(tr("Excel workbook with a results sheet and a summary sheet"))
+                    .clicked()
+                {
+                    self.export_xlsx();
+                }
+                if ui.add_enabled(!self.results.is_empty(), egui::Button::new(tr("Copy as Markdown")))
+                    .on_hover_text("Copy the rows shown in the table as a markdown table")
+                    .clicked()
+                {
+                    let rows: Vec<&WheelOne> = self.visible_rows().into_iter().map(|i| &self.results[i]).collect();
+                    let n = rows.len();
+                    let text = export::markdown_table(&rows);
+                    ui.output_mut(|o| o.copied_text = text);
+                    self.append_log(&format!("[INFO] copied {} rows as a markdown table\n", n));
+                }
+                if ui.add_enabled(!self.results.is_empty(), egui::Button::new(tr("Copy table")))
+                    .on_hover_text("Copy the rows shown in the table (with the current filters) as tab-separated values, e.g. for Google Sheets")
+                    .clicked()
+                {
+                    let rows: Vec<&WheelOne> = self.visible_rows().into_iter().map(|i| &self.results[i]).collect();
+                    let n = rows.len();
+                    let text = export::tsv(&rows);
+                    ui.output_mut(|o| o.copied_text = text);
+                    self.show_toast(format!("{} {} {}", tr("Copied"), n, tr("rows")));
+                }
+                if ui.add_enabled(!self.results.is_empty(), egui::Button::new(tr("Export GeoJSON..."))).clicked() {
+                    self.export_geojson();
+                }
+                let any_starred = self.results.iter().any(|w| self.sidecar.is_starred(&w.image));
+                ui.add_enabled_ui(any_starred && self.bundle_rx.is_none(), |ui| {
+                    ui.menu_button(tr("Export starred ▾"), |ui| {
+                        if ui.button(tr("CSV...")).clicked() {
+                            ui.close_menu();
+                            self.export_starred_csv();
+                        }
+                        if ui.button(tr("HTML page + bbox images (zip)...")).clicked() {
+                            ui.close_menu();
+                            self.export_starred_zip(ctx);
+                        }
+                    }).response.on_hover_text(tr("Only the starred rows, e.g. the examples for a report"));
+                });
+                ui.add_enabled_ui(!self.results.is_empty() && self.image_export_rx.is_none(), |ui| {
+                    ui.menu_button(tr("Export images"), |ui| {
+                        egui::ComboBox::from_id_source("image_export_format")
+                            .selected_text(self.settings.image_export_format.label())
+                            .show_ui(ui, |ui| {
+                                for f in imgexport::ExportFormat::ALL {
+                                    ui.selectable_value(&mut self.settings.image_export_format, f, f.label());
+                                }
+                            });
+                        if self.settings.image_export_format == imgexport::ExportFormat::Jpeg {
+                            ui.add(egui::Slider::new(&mut self.settings.image_export_quality, 1..=100).text(tr("quality")));
+                        }
+                        if ui.button(tr("Choose folder and export...")).clicked() {
+                            ui.close_menu();
+                            self.export_images(ctx);
+                        }
+                    }).response.on_hover_text(tr("Save the bbox images of the rows shown in the table"));
+                });
+                let bundle_idle = self.bundle_rx.is_none();
+                if ui.add_enabled(bundle_idle && self.last_json_path.is_some(), egui::Button::new(tr("Export run bundle...")))
+                    .on_hover_text(tr("One zip with the result JSON, its notes and other sidecars, and every bbox image of the run"))
+                    .clicked()
+                {
+                    self.export_bundle(ctx);
+                }
+                if ui.add_enabled(bundle_idle && self.run_rx.is_none(), egui::Button::new(tr("Import bundle..."))).clicked() {
+                    self.import_bundle(ctx);
+                }
+                if !bundle_idle { ui.spinner(); }
+                if ui.add_enabled(!self.results.is_empty(), egui::Button::new(tr("Check integrity")))
+                    .on_hover_text("Check that every result has a bbox image and every bbox image has a result")
+                    .clicked()
+                {
+                    let report = self.check_result_integrity();
+                    self.log_integrity(&report);
+                }
+            });
+            if self.image_export_rx.is_some() {
+                let (done, total) = self.image_export_progress;
+                ui.add(egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                    .text(format!("exporting images {}/{}", done, total)));
+            }
+            ui.horizontal(|ui| {
+                let has_steps = !self.command_log.is_empty();
+                if ui.add_enabled(has_steps, egui::Button::new(tr("Copy command log")))
+                    .on_hover_text("Copy a script that repeats the last run outside the app")
+                    .clicked()
+                {
+                    let text = self.run_script(ScriptKind::native());
+                    ui.output_mut(|o| o.copied_text = text);
+                }
+                if ui.add_enabled(has_steps, egui::Button::new(tr("Save run script..."))).clicked() {
+                    self.save_run_script();
+                }
+                if let Some(p) = &self.last_log_path {
+                    if ui.button(tr("Open log file")).on_hover_text(p.display().to_string()).clicked() {
+                        if let Err(e) = open_external(&p.to_string_lossy()) {
+                            self.append_log(&format!("[ERROR] {}\n", e));
+                        }
+                    }
+                }
+            });
         });
 
+        self.show_removal_dialog(ctx);
+        self.show_app_import_dialog(ctx);
+        self.show_wizard(ctx);
+        if self.json_viewer.as_mut().is_some_and(|v| !v.show(ctx)) { self.json_viewer = None; }
+        self.draw_toast(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
-            // ===== Results table =====
-            ui.heading("Results preview");
-            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.central_tab, CentralTab::Results, tr("Results"));
+                ui.selectable_value(&mut self.central_tab, CentralTab::History, tr("History"));
+                ui.selectable_value(&mut self.central_tab, CentralTab::Query, tr("Query"));
+                if self.comparison.is_some() {
+                    ui.selectable_value(&mut self.central_tab, CentralTab::Compare, tr("A/B compare"));
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.selectable_value(&mut self.settings.quick_mode, true, tr("Quick check"))
+                        .on_hover_text("Drop one image: it runs right away and only its verdict, reason and bbox are shown");
+                    ui.selectable_value(&mut self.settings.quick_mode, false, tr("Batch"));
+                });
+            });
+            ui.separator();
+            if self.settings.quick_mode {
+                self.show_quick_view(ui, ctx);
+                return;
+            }
+            match self.central_tab {
+                CentralTab::History => { self.show_history_tab(ui); return; }
+                CentralTab::Query => { self.show_query_tab(ui); return; }
+                CentralTab::Compare if self.comparison.is_some() => { self.show_compare_tab(ui, ctx); return; }
+                _ => {}
+            }
 
-            let rows = self.results.clone(); // avoid borrow conflicts
-
-            egui::ScrollArea::vertical()
-                .id_source("results_scroll")
-                .auto_shrink([false; 2])
-                .show(ui, |ui| {
-                    TableBuilder::new(ui)
-                        .striped(true)
-                        .column(Column::auto().at_least(78.0))    // BBox thumb
-                        .column(Column::auto().at_least(200.0))   // Image name
-                        .column(Column::auto().at_least(110.0))   // Accessible
-                        .column(Column::remainder())               // Reason (ellipsized)
-                        .header(22.0, |mut header| {
-                            header.col(|ui| { ui.strong("BBox"); });
-                            header.col(|ui| { ui.strong("Image"); });
-                            header.col(|ui| { ui.strong("Accessible"); });
-                            header.col(|ui| { ui.strong("Reason"); });
-                        })
-                        .body(|mut body| {
-                            for r in rows {
-                                let is_selected = self.selected_image.as_deref() == Some(r.image.as_str());
-                                body.row(28.0, |mut row| {
-                                    // thumb
-                                    row.col(|ui| { self.show_bbox_thumb(ui, &r.image, ctx); });
-                                    // filename (click to select)
-                                    row.col(|ui| {
-                                        let resp = ui.selectable_label(is_selected, &r.image);
-                                        if resp.clicked() {
-                                            self.selected_image = Some(r.image.clone());
-                                        }
-                                    });
-                                    // accessible
-                                    row.col(|ui| match r.result.accessible {
-                                        Some(true)  => { ui.colored_label(Color32::from_rgb(0,160,0), "true"); }
-                                        Some(false) => { ui.colored_label(Color32::from_rgb(200,0,0), "false"); }
-                                        None        => { ui.label("null"); }
-                                    });
-                                    // reason (single line, ellipsized to avoid overlap)
-                                    row.col(|ui| {
-                                        ui.add(egui::Label::new(egui::RichText::new(&r.result.reason)).truncate(true).wrap(false));
-                                    });
-                                });
+            // ===== Results table =====
+            if let Some(dir) = self.bbox_missing_warned.clone().filter(|d| Some(d) == self.last_run_bbox_dir.as_ref() && !d.is_dir()) {
+                ui.horizontal_wrapped(|ui| {
+                    ui.colored_label(Color32::from_rgb(230,160,0), format!("⚠ bbox images are gone ({})", dir.display()));
+                    let running = self.run_rx.is_some();
+                    if ui.add_enabled(!running, egui::Button::new(tr("Re-run YOLO to regenerate"))).clicked() {
+                        if let Err(e) = self.regenerate_bbox(ctx) {
+                            self.append_log(&format!("[ERROR] {:#}\n", e));
+                        }
+                    }
+                });
+            }
+            ui.heading(tr("Results preview"));
+            if let Some(json) = self.last_json_path.clone() {
+                let header = match self.sidecar.label() {
+                    Some(l) => format!("{}: {}", tr("Run info"), l),
+                    None => tr("Run info").to_string(),
+                };
+                egui::CollapsingHeader::new(header).id_source("run_info")
+                    .show(ui, |ui| self.show_run_info(ui, &json));
+            }
+            if !self.results.is_empty() {
+                let (yes, no, _) = verdict_counts(&self.results, self.min_confidence());
+                // errored entries are counted apart from the nulls (an error may come with a cached verdict)
+                let errors = self.results.iter().filter(|w| w.error.is_some()).count();
+                let unknown = self.results.iter()
+                    .filter(|w| w.error.is_none() && shown_verdict(&w.result, self.min_confidence()).is_none())
+                    .count();
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} {}", self.results.len(), tr("results:")));
+                    for (v, n) in [(Some(true), yes), (Some(false), no), (None, unknown)] {
+                        let (sym, text, color) = verdict_style(v, self.settings.colorblind_palette);
+                        let r = ui.colored_label(color, format!("{} {} {}", sym, text, n));
+                        if v.is_none() { r.on_hover_text(NULL_VERDICT_HINT); }
+                    }
+                    if errors > 0 {
+                        ui.colored_label(ERROR_COLOR, format!("⚠ {} {}", tr("errors"), errors))
+                            .on_hover_text("The script failed on these images; see the Reason column");
+                    }
+                    let reviewed = self.results.iter().filter(|w| self.sidecar.is_reviewed(&w.image)).count();
+                    ui.separator();
+                    ui.label(format!("{} {}/{}", tr("reviewed"), reviewed, self.results.len()))
+                        .on_hover_text("Press Space to mark the selected row reviewed and jump to the next unreviewed one");
+                    let starred = self.results.iter().filter(|w| self.sidecar.is_starred(&w.image)).count();
+                    ui.label(format!("★ {}", starred)).on_hover_text("Starred rows; press S to star or unstar the selected row");
+                    ui.separator();
+                    ui.label(tr("Show:"));
+                    verdict_filter_combo(ui, "result_verdict", &mut self.result_verdict);
+                    let criteria = export::criteria_keys(&self.results);
+                    // a criterion the loaded results don't have would hide everything but undetermined rows
+                    if !criteria.contains(&self.result_criterion) { self.result_criterion.clear(); }
+                    if !criteria.is_empty() {
+                        ui.label(tr("Criterion:"));
+                        egui::ComboBox::from_id_source("result_criterion")
+                            .selected_text(if self.result_criterion.is_empty() { tr("Any").to_string() } else { self.result_criterion.clone() })
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.result_criterion, String::new(), tr("Any"));
+                                for c in criteria {
+                                    ui.selectable_value(&mut self.result_criterion, c.clone(), c);
+                                }
+                            });
+                        if !self.result_criterion.is_empty() {
+                            verdict_filter_combo(ui, "result_criterion_verdict", &mut self.result_criterion_verdict);
+                        }
+                    }
+                    ui.checkbox(&mut self.hide_reviewed, tr("Hide reviewed"));
+                    ui.toggle_value(&mut self.starred_only, format!("★ {}", tr("Starred only")));
+                    ui.add_enabled_ui(!self.settings.results_grid, |ui| {
+                        ui.menu_button(tr("Columns ▾"), |ui| columns_menu(ui, &mut self.settings.hidden_columns));
+                    });
+                    ui.separator();
+                    ui.selectable_value(&mut self.settings.results_grid, false, tr("Table"));
+                    ui.selectable_value(&mut self.settings.results_grid, true, tr("Grid"));
+                    let retry = self.undetermined_images().len();
+                    if retry > 0 {
+                        ui.separator();
+                        let can = self.run_rx.is_none() && self.last_json_path.is_some();
+                        if ui.add_enabled(can, egui::Button::new(format!("{} ({})", tr("Retry undetermined"), retry)))
+                            .on_hover_text("Ask Gemini again for the undetermined and errored images (and bbox images without a verdict) and merge the answers into this result file")
+                            .clicked()
+                        {
+                            if let Err(e) = self.retry_undetermined(ctx) {
+                                self.append_log(&format!("[ERROR] {:#}\n", e));
                             }
-                        });
+                        }
+                    }
+                });
+                let scored = self.results.iter().filter(|w| w.result.confidence.is_some()).count();
+                if scored > 0 {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.settings.confidence_rule, tr("Require confidence ≥"))
+                            .on_hover_text("Show verdicts scored below this as undetermined (null).\nDisplay only: the result JSON keeps the model's verdict.");
+                        ui.add_enabled(self.settings.confidence_rule, egui::Slider::new(&mut self.settings.min_confidence, 0.0..=1.0).fixed_decimals(2));
+                        if let Some(min) = self.min_confidence() {
+                            let changed = self.results.iter().filter(|w| shown_verdict(&w.result, Some(min)) != w.result.accessible).count();
+                            ui.weak(format!("{} of {} scored verdicts set to null", changed, scored));
+                        }
+                    });
+                }
+            }
+            if !self.classes.is_empty() {
+                // counted over the rows the verdict filter lets through, e.g. inaccessible entries with stairs
+                let rows = self.filtered_rows();
+                let mut legend: Vec<(String, usize)> = self.classes.iter()
+                    .map(|(c, imgs)| (c.clone(), rows.iter().filter(|&&i| classes::contains(imgs, &self.results[i].image)).count()))
+                    .collect();
+                legend.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr("Detected:"));
+                    if ui.selectable_label(self.hidden_classes.is_empty(), tr("all")).clicked() {
+                        self.hidden_classes.clear();
+                    }
+                    for (c, n) in legend {
+                        let on = !self.hidden_classes.contains(&c);
+                        let text = egui::RichText::new(format!("{} {}", c, n)).color(class_color(&c));
+                        if ui.selectable_label(on, text)
+                            .on_hover_text(format!("{} shown entries where YOLO detected {}; click to {} them", n, c, if on { "hide" } else { "show" }))
+                            .clicked()
+                        {
+                            if on { self.hidden_classes.insert(c); } else { self.hidden_classes.remove(&c); }
+                        }
+                    }
+                });
+            }
+            if !self.results.is_empty() || !self.chart_history.is_empty() {
+                egui::CollapsingHeader::new(tr("Chart")).id_source("verdict_chart").show(ui, |ui| {
+                    if ui.checkbox(&mut self.chart_recent_runs, "Include recent runs from the database").changed() {
+                        self.refresh_chart_history();
+                    }
+                    let groups = self.chart_groups();
+                    let styles = [Some(true), Some(false), None].map(|v| verdict_style(v, self.settings.colorblind_palette));
+                    chart::verdict_bars(ui, &groups, styles.map(|s| s.2), ["accessible", "not accessible", "undetermined (null)"]);
                 });
+            }
+            if self.ground_truth.is_some() && !self.results.is_empty() {
+                egui::CollapsingHeader::new(tr("Ground truth")).id_source("ground_truth").default_open(true)
+                    .show(ui, |ui| self.show_ground_truth_panel(ui));
+            }
+            ui.add_space(6.0);
+
+            // Space while no text field has focus: mark reviewed, go to the next unreviewed row
+            let over_preview = self.preview_rect.zip(ctx.pointer_hover_pos()).is_some_and(|(r, p)| r.contains(p));
+            if self.selected.is_some() && !ctx.wants_keyboard_input() && !over_preview
+                && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Space))
+            {
+                self.review_and_advance();
+            }
+            if self.selected.is_some() && !ctx.wants_keyboard_input()
+                && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::S))
+            {
+                self.toggle_star_selected();
+            }
+
+            // (index, occurrence #, total with this name); entries are cloned only for the rows on screen
+            let rows = self.table_rows();
+
+            if self.settings.results_grid {
+                self.show_results_grid(ui, ctx, &rows);
+            } else {
+                self.show_results_table(ui, ctx, &rows);
+            }
 
             ui.separator();
             ui.add_space(6.0);
@@ -213,56 +1561,131 @@ impl eframe::App for AppState {
             // ===== Bottom area: Big bbox preview + full reason =====
             ui.horizontal(|ui| {
                 ui.vertical(|ui| {
-                    ui.heading("Selected BBox image");
-                    ui.label("Click a filename in the table to select.");
+                    ui.heading(tr("Selected BBox image"));
+                    ui.label(tr("Click a filename in the table to select."));
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.preview_zoom, PreviewZoom::Width, tr("Fit width"));
+                        ui.selectable_value(&mut self.preview_zoom, PreviewZoom::Window, tr("Fit to window"));
+                        ui.selectable_value(&mut self.preview_zoom, PreviewZoom::Actual, "100%");
+                        ui.weak(tr("Middle-drag or Space+drag to pan"));
+                    });
                     ui.add_space(6.0);
-                    egui::ScrollArea::both()
+                    let out = egui::ScrollArea::both()
                         .id_source("big_preview_scroll")
                         .show(ui, |ui| {
                             if let Some(p) = self.find_bbox_image_for_selected() {
-                                let key = format!("big:{}", p.display());
-                                if !self.tex_cache.contains_key(&key) {
-                                    if let Some(tex) = load_texture_from_path(ctx, &p) {
-                                        self.tex_cache.insert(key.clone(), tex);
-                                    }
-                                }
-                                if let Some(tex) = self.tex_cache.get(&key) {
-                                    let size = tex.size_vec2();
-                                    let max_w = ui.available_width().min(1400.0);
-                                    let scale = (max_w / size.x).min(1.0);
-                                    let sized = egui::load::SizedTexture::from_handle(tex);
-                                    egui::Image::new(sized)
-                                        .max_width(size.x * scale)
-                                        .max_height(size.y * scale)
-                                        .ui(ui);
-                                } else {
-                                    ui.label("Failed to load selected image.");
-                                }
+                                let avail = match self.preview_zoom {
+                                    PreviewZoom::Width => egui::vec2(1400.0, f32::INFINITY),
+                                    _ => egui::Vec2::INFINITY,
+                                };
+                                self.show_preview(ui, ctx, &p, avail, self.preview_zoom);
                             } else {
-                                ui.label("Select a row above to preview its bbox image here.");
+                                ui.label(tr("Select a row above to preview its bbox image here."));
                             }
                         });
+                    self.preview_rect = Some(out.inner_rect);
                 });
 
                 ui.separator();
 
                 ui.vertical(|ui| {
-                    ui.heading("Full reason");
+                    ui.heading(tr("Full reason"));
                     ui.add_space(6.0);
                     egui::ScrollArea::vertical()
                         .id_source("reason_full_scroll")
+                        .max_height(160.0)
                         .show(ui, |ui| {
-                            if let Some(sel) = self.selected_image.as_ref() {
-                                if let Some(item) = self.results.iter().find(|w| &w.image == sel) {
+                            if let Some(sel) = self.selected {
+                                if let Some(item) = self.results.get(sel) {
                                     // wrapped, full text
                                     ui.label(egui::RichText::new(&item.result.reason));
+                                    if let Some(c) = item.result.confidence {
+                                        ui.weak(format!("confidence {:.2}", c));
+                                    }
                                 } else {
-                                    ui.label("No reason available.");
+                                    ui.label(tr("No reason available."));
                                 }
                             } else {
-                                ui.label("Select a row to view the full reason.");
+                                ui.label(tr("Select a row to view the full reason."));
+                            }
+                        });
+
+                    if let Some(w) = self.selected_entry() {
+                        let coords = w.lat.zip(w.lon);
+                        let image = w.image.clone();
+                        // same serde types the result JSON is read with, so the copy matches its schema
+                        let entry_json = serde_json::to_string_pretty(w);
+                        ui.add_space(6.0);
+                        ui.horizontal(|ui| {
+                            match coords {
+                                Some((lat, lon)) => {
+                                    ui.label(format!("📍 {:.6}, {:.6}", lat, lon));
+                                    if ui.small_button(tr("Copy coords")).clicked() {
+                                        ui.output_mut(|o| o.copied_text = format!("{:.6}, {:.6}", lat, lon));
+                                    }
+                                }
+                                None => { ui.weak("📍 no GPS"); }
+                            }
+                            if ui.add_enabled(coords.is_some(), egui::Button::new(tr("Show on map")).small())
+                                .on_disabled_hover_text("The original photo had no GPS EXIF")
+                                .clicked()
+                            {
+                                if let Some((lat, lon)) = coords {
+                                    let url = map_url(&self.settings.map_url_template, lat, lon);
+                                    if let Err(e) = open_external(&url) {
+                                        self.append_log(&format!("[ERROR] could not open browser for {}: {:#}\n", url, e));
+                                    }
+                                }
+                            }
+                            if ui.small_button(tr("Copy JSON"))
+                                .on_hover_text("Copy this entry as it appears in the result JSON, e.g. for a prompt bug report")
+                                .clicked()
+                            {
+                                match &entry_json {
+                                    Ok(text) => {
+                                        ui.output_mut(|o| o.copied_text = text.clone());
+                                        self.show_toast(format!("Copied JSON for {}", image));
+                                    }
+                                    Err(e) => self.append_log(&format!("[ERROR] could not serialize {}: {}\n", image, e)),
+                                }
                             }
                         });
+                        // the original input, e.g. to spot a low-resolution thumbnail behind an odd verdict
+                        match self.sidecar.meta(&image) {
+                            Some(m) => {
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("Original:"));
+                                    dimensions_label(ui, Some(m));
+                                    if m.bytes > 0 { ui.label(format!("· {}", preflight::fmt_bytes(m.bytes))); }
+                                    ui.label(format!("· taken {}", m.taken_at.as_deref().unwrap_or("unknown")));
+                                });
+                            }
+                            None => { ui.weak(tr("No original image info recorded for this result")); }
+                        }
+                    }
+
+                    ui.add_space(8.0);
+                    ui.heading(tr("Label"));
+                    self.show_label_edit(ui);
+
+                    ui.add_space(8.0);
+                    ui.heading(tr("Notes"));
+                    match (self.selected_entry().map(|w| w.image.clone()), self.last_json_path.clone()) {
+                        (Some(sel), Some(json)) => {
+                            let note = self.sidecar.notes.entry(sel).or_default();
+                            let resp = ui.add(
+                                egui::TextEdit::multiline(note)
+                                    .hint_text("e.g. check again in winter, ramp was icy")
+                                    .desired_rows(4),
+                            );
+                            if resp.changed() {
+                                if let Err(e) = self.sidecar.save(&json) {
+                                    self.append_log(&format!("[WARN] notes not saved: {}\n", e));
+                                }
+                            }
+                        }
+                        _ => { ui.label(tr("Select a row to add notes.")); }
+                    }
                 });
             });
         });
@@ -270,239 +1693,3208 @@ impl eframe::App for AppState {
 }
 
 impl AppState {
+    fn show_wizard(&mut self, ctx: &egui::Context) {
+        let Some(w) = self.wizard.as_mut() else { return; };
+        let Some(outcome) = w.show(ctx, &mut self.settings) else { return; };
+        self.wizard = None;
+        // written even when nothing changed, so the wizard doesn't come back next launch
+        self.saved_settings = self.settings.clone();
+        if let Err(e) = self.settings.save() {
+            self.append_log(&format!("[WARN] failed to save settings: {:#}\n", e));
+        }
+        match outcome {
+            wizard::Outcome::Finished => self.append_log("[DONE] setup finished; the settings are in the left panel\n"),
+            wizard::Outcome::Skipped => self.append_log("[INFO] setup skipped; set the project root, Python and weights in the left panel\n"),
+        }
+    }
+
+    fn open_json_viewer(&mut self, json: &Path, validate: bool) {
+        match jsonview::JsonViewer::open(json) {
+            Ok(mut v) => {
+                if validate { v.validate(); }
+                self.json_viewer = Some(v);
+            }
+            Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+        }
+    }
+
+    // settings, history and sidecars, for moving to another computer
+    fn export_app_data(&mut self) {
+        let Some(config) = settings::config_dir() else {
+            self.append_log("[ERROR] no config directory on this platform\n");
+            return;
+        };
+        let results = match self.resolve_project_root() {
+            Ok(root) => Some(root.join("results")),
+            Err(_) => {
+                self.append_log("[WARN] no project root set; only settings and history are exported\n");
+                None
+            }
+        };
+        let Some(dest) = FileDialog::new()
+            .set_title(tr("Export app data"))
+            .add_filter("zip", &["zip"])
+            .set_file_name(format!("wheel_city_app_data_{}.zip", Local::now().format("%Y%m%d")))
+            .save_file() else { return; };
+        // the zip takes the files on disk
+        self.persist_settings();
+        match appdata::export(&dest, &config, results.as_deref(), Local::now().format("%Y-%m-%d %H:%M:%S").to_string()) {
+            Ok(m) => self.append_log(&format!("[DONE] saved {} ({} files)\n", dest.display(), m.files.len())),
+            Err(e) => self.append_log(&format!("[ERROR] app data export failed: {:#}\n", e)),
+        }
+    }
+
+    fn pick_app_import(&mut self) {
+        let Some(path) = FileDialog::new().set_title(tr("Import app data")).add_filter("zip", &["zip"]).pick_file() else { return; };
+        match appdata::read_manifest(&path) {
+            Ok(m) => self.pending_app_import = Some((path, m)),
+            Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+        }
+    }
+
+    fn show_app_import_dialog(&mut self, ctx: &egui::Context) {
+        let Some((path, manifest)) = &self.pending_app_import else { return; };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr("Import app data"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(path.display().to_string());
+                ui.label(format!("{} {} (v{})", tr("Written"), manifest.created, manifest.app_version));
+                egui::ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    for f in &manifest.files { ui.monospace(f); }
+                });
+                ui.separator();
+                ui.label(tr("Files that already exist here:"));
+                ui.radio_value(&mut self.app_import_conflict, appdata::Conflict::Skip, tr("Keep them (skip)"));
+                ui.radio_value(&mut self.app_import_conflict, appdata::Conflict::Overwrite, tr("Overwrite them"));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Import")).clicked() { confirmed = true; }
+                    if ui.button(tr("Cancel")).clicked() { cancelled = true; }
+                });
+            });
+        if confirmed {
+            if let Some((path, _)) = self.pending_app_import.take() { self.import_app_data(&path); }
+        } else if cancelled {
+            self.pending_app_import = None;
+        }
+    }
+
+    fn import_app_data(&mut self, path: &Path) {
+        let Some(config) = settings::config_dir() else {
+            self.append_log("[ERROR] no config directory on this platform\n");
+            return;
+        };
+        let root = match self.resolve_project_root() {
+            Ok(r) => r,
+            Err(e) => { self.append_log(&format!("[ERROR] {}\n", e)); return; }
+        };
+        let restored = match appdata::import(path, &config, &root.join("results"), self.app_import_conflict) {
+            Ok(r) => r,
+            Err(e) => { self.append_log(&format!("[ERROR] app data import failed: {:#}\n", e)); return; }
+        };
+        if restored.wrote("settings.json") {
+            let mut s = Settings::load();
+            // the other computer's project root, unless it doesn't exist here
+            if !expand_path(&s.project_root).is_dir() { s.project_root = self.settings.project_root.clone(); }
+            self.saved_settings = Settings::load();
+            self.settings = s;
+        }
+        if restored.wrote("run_history.json") && self.settings.persist_history {
+            self.run_history = history::load();
+        }
+        if let Some(json) = self.last_json_path.clone() {
+            if restored.written.contains(&Sidecar::path_for(&json)) {
+                if let Err(e) = self.load_results_file(&json, self.last_run_bbox_dir.clone()) {
+                    self.append_log(&format!("[WARN] failed to reload {}: {:#}\n", json.display(), e));
+                }
+            }
+        }
+        self.append_log(&format!("[DONE] imported app data: {} files restored, {} existing kept\n",
+            restored.written.len(), restored.skipped.len()));
+    }
+
+    // not on every keystroke: a change is saved when the field loses focus, or after a short delay
+    fn persist_settings_when_idle(&mut self, ctx: &egui::Context) {
+        if self.settings == self.saved_settings {
+            self.settings_dirty_since = None;
+            return;
+        }
+        let since = *self.settings_dirty_since.get_or_insert_with(std::time::Instant::now);
+        let typing = ctx.memory(|m| m.focused().is_some());
+        if typing && since.elapsed() < SETTINGS_SAVE_DELAY {
+            ctx.request_repaint_after(SETTINGS_SAVE_DELAY - since.elapsed());
+            return;
+        }
+        self.persist_settings();
+    }
+
+    fn persist_settings(&mut self) {
+        self.settings_dirty_since = None;
+        if self.settings == self.saved_settings { return; }
+        // remember it either way so a read-only config dir doesn't warn every frame
+        self.saved_settings = self.settings.clone();
+        if let Err(e) = self.settings.save() {
+            self.append_log(&format!("[WARN] failed to save settings: {:#}\n", e));
+        }
+    }
+
     fn append_log(&mut self, s: &str) {
-        self.log.push_str(s);
-        if self.log.len() > 120_000 { self.log = self.log[self.log.len() - 60_000..].to_string(); }
+        let added = self.log.push(s);
+        let Some(file) = self.log_file.as_mut() else { return; };
+        if let Err(e) = file.write(self.log.last(added)) {
+            // one warning, then memory only (read-only disk, full disk, ...)
+            let msg = format!("[WARN] writing {} failed ({}); keeping the log in memory only\n", file.path().display(), e);
+            self.log_file = None;
+            self.log.push(&msg);
+        }
+    }
+
+    fn run_pipeline(&mut self, ctx: &egui::Context) -> Result<()> {
+        self.start_run(ctx, None)
+    }
+
+    fn auto_export(&self) -> Option<export::AutoExport> {
+        let s = &self.settings;
+        export::AutoExport::from_settings(&s.run_export_dir, s.run_export_csv, s.run_export_json, s.run_export_xlsx)
     }
 
-    fn run_pipeline(&mut self) -> Result<()> {
+    // `export`: written by the worker once the run succeeds ("Run and export")
+    fn start_run(&mut self, ctx: &egui::Context, export: Option<export::AutoExport>) -> Result<()> {
+        if self.run_rx.is_some() { anyhow::bail!("A run is already in progress"); }
+        let typed = expand_path(&self.settings.project_root);
         let project_root = self.resolve_project_root()?;
-        if project_root.to_string_lossy() != self.project_root {
+        if project_root != typed {
             self.append_log(&format!("[INFO] project root auto-detected: {}\n", project_root.display()));
         }
+        // nothing to run: keep the current table and say so instead of starting the scripts
+        if let Err(e) = preflight::sources(&project_root, &self.pending_files) {
+            self.show_toast(tr("Add images first: pick files, drop them on the window, or put them in input_images").to_string());
+            return Err(e);
+        }
 
-        let python = self.resolve_python(&project_root)?;
-        self.append_log(&format!("[INFO] using Python: {}\n", python));
+        // results stream into a fresh table
+        self.results.clear();
+        self.flush_run_info();
+        self.sidecar = Sidecar::default();
+        self.sources.clear();
+        self.last_json_path = None;
+        self.last_run_bbox_dir = None;
+        self.selected = None;
+        self.tex_cache.clear();
+        self.thumb_prefetch = None;
+
+        let weights_b = if self.ab_enabled {
+            if self.weights_b_path.trim().is_empty() { anyhow::bail!("A/B run needs a second weights file"); }
+            Some(self.weights_b_path.clone())
+        } else { None };
+        let label = self.run_label.trim().to_string();
+        let cfg = pipeline::RunConfig { weights_b, export, label, ..self.run_config(project_root)? };
+        // the pipeline discards the old checkpoint when it starts
+        self.resume_offer = None;
+        self.begin_run(&cfg);
+        self.run_rx = Some(pipeline::spawn(cfg, ctx.clone()));
+        Ok(())
+    }
 
-        let yolo_script   = project_root.join("yolov8").join("run.py");
-        let gemini_script = project_root.join("gemini").join("run.py");
-        if !yolo_script.exists()  { anyhow::bail!("Missing script: {}", yolo_script.display()); }
-        if !gemini_script.exists(){ anyhow::bail!("Missing script: {}", gemini_script.display()); }
-        let weights_abs = project_root.join(&self.weights_path);
-        if !weights_abs.exists()  { anyhow::bail!("Weights file not found: {}", weights_abs.display()); }
+    // the checks a run would make, as a checklist in the log; nothing is copied or started
+    fn validate(&mut self, ctx: &egui::Context) {
+        let mut weights = vec![self.settings.weights_path.clone()];
+        if self.ab_enabled { weights.push(self.weights_b_path.clone()); }
+        let (root, python, ffmpeg) = (self.settings.project_root.clone(), self.settings.python_bin.clone(), self.settings.ffmpeg_bin.clone());
+        let pending = self.pending_files.clone();
+        let backend = backend::find(&self.settings.backend);
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let weights: Vec<&str> = weights.iter().map(String::as_str).collect();
+            let _ = tx.send(preflight::run_all(&root, &python, &ffmpeg, &weights, &pending, backend));
+            ctx.request_repaint();
+        });
+        self.validate_rx = Some(rx);
+    }
 
-        // user-visible
-        let user_input_dir = project_root.join("input_images");
-        fs::create_dir_all(&user_input_dir).ok();
+    fn poll_validate(&mut self) {
+        let res = match self.validate_rx.as_ref().map(|rx| rx.try_recv()) {
+            None | Some(Err(std::sync::mpsc::TryRecvError::Empty)) => return,
+            Some(res) => res,
+        };
+        self.validate_rx = None;
+        let Ok(checks) = res else {
+            self.append_log("[ERROR] validate: the pre-flight checks stopped unexpectedly\n");
+            return;
+        };
+        let mut lines = String::from("[INFO] validate: pre-flight checks (dry run)\n");
+        for c in &checks {
+            match &c.outcome {
+                Ok(detail) if c.warning => lines.push_str(&format!("[WARN] {}: {}\n", c.name, detail)),
+                Ok(detail) => lines.push_str(&format!("[OK] {}: {}\n", c.name, detail)),
+                Err(e) => lines.push_str(&format!("[ERROR] FAIL {}: {:#}\n", c.name, e)),
+            }
+        }
+        let failed = checks.iter().filter(|c| c.outcome.is_err()).count();
+        if failed == 0 {
+            lines.push_str(&format!("[DONE] validate: all {} checks passed\n", checks.len()));
+        } else {
+            lines.push_str(&format!("[ERROR] validate: {} of {} checks FAILED\n", failed, checks.len()));
+        }
+        self.append_log(&lines);
+    }
 
-        // run-scoped
-        let work_dir   = project_root.join(".runner_work");
-        let run_input  = work_dir.join("input");
-        let ts         = Local::now().format("%Y%m%d_%H%M%S").to_string();
-        let run_bbox   = work_dir.join("bbox").join(&ts);
-        let results_dir= project_root.join("results");
-        fs::create_dir_all(&run_input).ok();
-        fs::create_dir_all(&run_bbox).ok();
-        fs::create_dir_all(&results_dir).ok();
+    // starts the debounced run after a drop; while a run is going the drop is only queued
+    fn auto_run(&mut self, ctx: &egui::Context) {
+        let Some(at) = self.auto_run_at else { return; };
+        let now = std::time::Instant::now();
+        if now < at {
+            ctx.request_repaint_after(at - now);
+            return;
+        }
+        self.auto_run_at = None;
+        if self.run_rx.is_some() {
+            self.append_log("[INFO] a run is in progress; dropped images were queued for the next run\n");
+            return;
+        }
+        self.append_log(&format!("[INFO] auto-run: starting with {} queued images\n", self.pending_files.len()));
+        if let Err(e) = self.run_pipeline(ctx) {
+            self.append_log(&format!("[ERROR] {}\n", e));
+        }
+    }
 
-        // clear run_input only
-        for e in fs::read_dir(&run_input)? {
-            let p = e?.path();
-            if p.is_file() { let _ = fs::remove_file(p); }
+    // quick mode: the first dropped image replaces the queue and runs at once
+    fn quick_check(&mut self, ctx: &egui::Context, dropped: Vec<PathBuf>) {
+        if self.run_rx.is_some() {
+            self.append_log("[WARN] quick check: a run is in progress; drop the image again when it is done\n");
+            return;
+        }
+        let Some(first) = dropped.into_iter().next() else { return; };
+        self.pending_files.clear();
+        self.add_pending(vec![first]);
+        if let Err(e) = self.run_pipeline(ctx) {
+            self.append_log(&format!("[ERROR] {}\n", e));
         }
+    }
 
-        // sources
-        let sources: Vec<PathBuf> = if self.pending_files.is_empty() {
-            let mut v = vec![];
-            if let Ok(rd) = fs::read_dir(&user_input_dir) {
-                for e in rd.flatten() {
-                    let p = e.path();
-                    if p.is_file() { v.push(p); }
+    // quick mode's central panel: the one verdict, large, with its reason and bbox image
+    fn show_quick_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let running = self.run_rx.is_some();
+        egui::ScrollArea::vertical().id_source("quick_scroll").auto_shrink([false; 2]).show(ui, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(24.0);
+                if running {
+                    let name = self.pending_files.first().and_then(|p| p.file_name()).map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    ui.spinner();
+                    ui.heading(format!("{} {}", tr("Checking"), name));
+                    if let Some(t) = &self.run_timing { ui.label(egui::RichText::new(t.status_line()).weak()); }
+                    ctx.request_repaint_after(std::time::Duration::from_secs(1));
+                    return;
                 }
-            }
-            v
-        } else {
-            self.pending_files.clone()
-        };
-
-        // copy into run_input with unique names
-        self.append_log("[STEP] copying into work input dir...\n");
-        let mut used_names: HashSet<String> = HashSet::new();
-        for src in &sources {
-            if !src.exists() {
-                self.append_log(&format!("[WARN] source missing, skip: {}\n", src.display()));
-                continue;
-            }
-            let base = src.file_name().unwrap().to_string_lossy().to_string();
-            let mut final_name = base.clone();
-            let mut counter = 1;
-            while used_names.contains(&final_name) || run_input.join(&final_name).exists() {
-                let (stem, ext) = split_name_ext(&base);
-                final_name = format!("{}_{}{}", stem, counter, ext);
-                counter += 1;
-            }
-            let dst = run_input.join(&final_name);
-            if let Err(e) = fs::copy(src, &dst) {
-                self.append_log(&format!("[WARN] copy failed (skip): {} -> {} ({})\n", src.display(), dst.display(), e));
-            } else {
-                used_names.insert(final_name);
-            }
-        }
-
-        // YOLO → run_bbox
-        self.append_log("[STEP] running YOLO inference...\n");
-        let mut cmd = Command::new(&python);
-        cmd.arg(&yolo_script)
-           .arg("--weights").arg(&weights_abs)
-           .arg("--source").arg(&run_input)
-           .arg("--outdir").arg(&run_bbox);
-        self.exec_and_log_in_dir(cmd, "[YOLO] ", &project_root)?;
-
-        // Gemini
-        self.append_log("[STEP] running Gemini judgment...\n");
-        let out_json = results_dir.join(format!("result_{}.json", ts));
-        let mut cmd2 = Command::new(&python);
-        cmd2.arg(&gemini_script)
-            .arg("--images_dir").arg(&run_bbox)
-            .arg("--out_json").arg(&out_json);
-        self.exec_and_log_in_dir(cmd2, "[GEMINI] ", &project_root)?;
-
-        // load results
-        self.append_log("[STEP] loading results...\n");
-        let data = fs::read_to_string(&out_json).with_context(|| "failed to read result json")?;
-        let parsed: WheelResultFile = serde_json::from_str(&data).with_context(|| "failed to parse result json")?;
-        self.results = parsed.results;
-        self.last_json_path = Some(out_json.clone());
-        self.last_run_bbox_dir = Some(run_bbox.clone());
+                let Some(entry) = self.selected_entry().cloned() else {
+                    ui.heading(tr("Drop an image here to check it"));
+                    ui.label(egui::RichText::new(tr("Switch to Batch for the results table")).weak());
+                    return;
+                };
+                let (sym, text, color) = verdict_style(shown_verdict(&entry.result, self.min_confidence()), self.settings.colorblind_palette);
+                ui.label(egui::RichText::new(format!("{} {}", sym, text)).size(40.0).strong().color(color));
+                ui.label(egui::RichText::new(&entry.image).weak());
+                if let Some(c) = entry.result.confidence {
+                    ui.label(format!("{} {:.2}", tr("confidence"), c));
+                }
+                ui.add_space(12.0);
+                let width = ui.available_width().min(900.0);
+                ui.allocate_ui(egui::vec2(width, 0.0), |ui| {
+                    ui.label(egui::RichText::new(&entry.result.reason).size(16.0));
+                });
+                ui.add_space(12.0);
+                if let Some(p) = self.find_bbox_image_for_selected() {
+                    self.show_preview(ui, ctx, &p, egui::vec2(width, f32::INFINITY), PreviewZoom::Width);
+                }
+            });
+        });
+    }
+
+    // continue the interrupted run recorded in `resume_offer`
+    fn resume_run(&mut self, ctx: &egui::Context) -> Result<()> {
+        if self.run_rx.is_some() { anyhow::bail!("A run is already in progress"); }
+        let (cp, _) = self.resume_offer.take().with_context(|| "no interrupted run to resume")?;
+        let project_root = self.resolve_project_root()?;
+        self.results.clear();
+        self.flush_run_info();
+        self.sidecar = Sidecar::default();
+        self.sources.clear();
+        self.last_json_path = None;
+        self.last_run_bbox_dir = None;
+        self.selected = None;
+        self.tex_cache.clear();
+        self.thumb_prefetch = None;
+        let cfg = pipeline::RunConfig {
+            weights_path: cp.weights.to_string_lossy().to_string(),
+            label: cp.label.clone(),
+            ..self.run_config(project_root)?
+        };
+        self.begin_run(&cfg);
+        self.run_rx = Some(pipeline::spawn_resume(cfg, cp, ctx.clone()));
+        Ok(())
+    }
 
-        // auto-select first item
-        if self.selected_image.is_none() {
-            if let Some(first) = self.results.first() {
-                self.selected_image = Some(first.image.clone());
+    // per-run bookkeeping shared by fresh and resumed runs: log file, cancel flag, timing
+    fn begin_run(&mut self, cfg: &pipeline::RunConfig) {
+        let log_path = cfg.project_root.join(".runner_work").join("logs")
+            .join(format!("run_{}.log", Local::now().format("%Y%m%d_%H%M%S")));
+        match RunLogFile::create(&log_path) {
+            Ok(f) => {
+                self.log_file = Some(f);
+                self.last_log_path = Some(log_path.clone());
+                self.append_log(&format!("[INFO] log file: {}\n", log_path.display()));
+            }
+            Err(e) => {
+                self.append_log(&format!("[WARN] cannot create log file {} ({}); keeping the log in memory only\n", log_path.display(), e));
             }
         }
+        self.run_cancel = Some(cfg.cancel.clone());
+        self.run_pause = cfg.weights_b.is_none().then(|| cfg.pause.clone());
+        self.run_timing = Some(RunTiming::new());
+        self.run_usage = Some((cfg.gemini.model.clone(), cost::Usage::default()));
+        self.command_log.clear();
+        self.command_log_started = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    }
 
-        // clear caches for new run
-        self.tex_cache.clear();
+    // snapshot of the settings for a worker run (single weights file)
+    fn run_config(&self, project_root: PathBuf) -> Result<pipeline::RunConfig> {
+        let extra_yolo_args = script::split_args(&self.settings.extra_yolo_args)
+            .map_err(|e| anyhow::anyhow!("extra YOLO args: {}", e))?;
+        let extra_gemini_args = script::split_args(&self.settings.extra_gemini_args)
+            .map_err(|e| anyhow::anyhow!("extra Gemini args: {}", e))?;
+        self.settings.gemini.check().map_err(|e| anyhow::anyhow!("Gemini model settings: {:#}", e))?;
+        Ok(pipeline::RunConfig {
+            project_root,
+            python_bin: self.settings.python_bin.clone(),
+            weights_path: self.settings.weights_path.clone(),
+            pending_files: self.pending_files.clone(),
+            weights_b: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            pause: Arc::new(AtomicBool::new(false)),
+            stage_timeout_secs: self.settings.stage_timeouts,
+            stage_failure: self.settings.stage_failure,
+            retention_days: self.settings.retention_days,
+            keep_last_runs: self.settings.keep_last_n_runs,
+            extra_yolo_args,
+            extra_gemini_args,
+            stream_results: self.settings.stream_results,
+            prefix_folder_names: self.settings.prefix_folder_names,
+            ffmpeg_bin: self.settings.ffmpeg_bin.clone(),
+            frame_interval_secs: self.settings.frame_interval_secs,
+            gemini_chunk_size: self.settings.gemini_chunk_size,
+            chunk_failure: self.settings.chunk_failure,
+            prompt_template: Some(self.settings.prompt_template.clone()).filter(|t| !t.trim().is_empty()),
+            bypass_cache: self.settings.bypass_cache,
+            gemini: self.settings.gemini.clone(),
+            backend: backend::find(&self.settings.backend),
+            native_inference: self.settings.native_inference,
+            native_draw_boxes: self.settings.native_draw_boxes,
+            webhook: webhook::Webhook::from_settings(&self.settings.webhook_url, &self.settings.webhook_token),
+            export: None,
+            label: String::new(),
+        })
+    }
+
+    // images of the loaded result without a verdict: undetermined entries and bbox images with no entry
+    fn undetermined_images(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.results.iter()
+            .filter(|w| w.result.accessible.is_none())
+            .map(|w| w.image.clone())
+            .collect();
+        if let Some(dir) = &self.last_run_bbox_dir {
+            let known: HashSet<&str> = self.results.iter().map(|w| w.image.as_str()).collect();
+            let mut missing: Vec<String> = fs::read_dir(dir).into_iter().flatten().flatten()
+                .filter(|e| e.path().is_file())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|n| !known.contains(n.as_str()))
+                .collect();
+            missing.sort();
+            names.extend(missing);
+        }
+        names.dedup();
+        names
+    }
+
+    // Gemini again for the undetermined images of the loaded result, merged back into its JSON
+    fn retry_undetermined(&mut self, ctx: &egui::Context) -> Result<()> {
+        let names = self.undetermined_images();
+        if names.is_empty() { anyhow::bail!("every image of this result has a verdict"); }
+        self.rejudge_images(ctx, names)
+    }
 
-        self.append_log("[DONE] Completed.\n");
+    // judge `names` again (bypassing the cache) and merge the answers into the loaded result file
+    fn rejudge_images(&mut self, ctx: &egui::Context, names: Vec<String>) -> Result<()> {
+        if self.run_rx.is_some() { anyhow::bail!("A run is already in progress"); }
+        let json = self.last_json_path.clone().with_context(|| "no result file loaded")?;
+        let bbox_dir = self.last_run_bbox_dir.clone().filter(|d| d.is_dir())
+            .with_context(|| "the bbox images of this result are gone; re-run YOLO first")?;
+        let project_root = self.resolve_project_root()?;
+        let cfg = self.run_config(project_root)?;
+        self.begin_run(&cfg);
+        self.run_pause = None;
+        self.run_rx = Some(pipeline::spawn_rejudge(cfg, json, bbox_dir, names, ctx.clone()));
         Ok(())
     }
 
-    fn exec_and_log_in_dir(&mut self, mut cmd: Command, prefix: &str, workdir: &Path) -> Result<()> {
-        cmd.current_dir(workdir);
-        let out = cmd.output().with_context(|| "failed to spawn process")?;
-        if !out.stdout.is_empty() { self.append_log(&format!("{}{}", prefix, String::from_utf8_lossy(&out.stdout))); }
-        if !out.stderr.is_empty() { self.append_log(&format!("{}[stderr] {}", prefix, String::from_utf8_lossy(&out.stderr))); }
-        if !out.status.success() { anyhow::bail!("subprocess failed with code {:?}", out.status.code()); }
+    // re-run YOLO for the loaded result file into its (deleted) bbox dir
+    fn regenerate_bbox(&mut self, ctx: &egui::Context) -> Result<()> {
+        if self.run_rx.is_some() { anyhow::bail!("A run is already in progress"); }
+        let json = self.last_json_path.clone().with_context(|| "no result file loaded")?;
+        let bbox_dir = self.last_run_bbox_dir.clone().with_context(|| "no bbox dir known for this result")?;
+        let project_root = self.resolve_project_root()?;
+        // the work input still holds the copied images unless another run replaced them
+        let work_input = project_root.join(".runner_work").join("input");
+        let source = if self.results.iter().any(|w| work_input.join(&w.image).exists()) {
+            work_input
+        } else {
+            FileDialog::new().set_title("Folder with the original images").pick_folder()
+                .with_context(|| "no image folder selected")?
+        };
+        self.append_log(&format!("[STEP] regenerating bbox images from {} into {}\n", source.display(), bbox_dir.display()));
+        let cfg = self.run_config(project_root)?;
+        self.run_cancel = Some(cfg.cancel.clone());
+        self.run_timing = Some(RunTiming::new());
+        self.bbox_missing_warned = None;
+        self.tex_cache.clear();
+        self.thumb_prefetch = None;
+        self.run_rx = Some(pipeline::spawn_redetect(cfg, source, json, bbox_dir, ctx.clone()));
         Ok(())
     }
 
-    fn resolve_python(&mut self, project_root: &Path) -> Result<String> {
-        let mut candidates: Vec<String> = vec![
-            project_root.join(".venv").join("bin").join("python").to_string_lossy().to_string(),
-            project_root.join(".venv").join("Scripts").join("python.exe").to_string_lossy().to_string(),
-        ];
-        if !self.python_bin.trim().is_empty() { candidates.push(self.python_bin.clone()); }
-        candidates.push("python3".to_string());
-        candidates.push("python".to_string());
+    // warn once per bbox dir that disappears from disk
+    fn check_bbox_dir(&mut self) {
+        let Some(dir) = &self.last_run_bbox_dir else { return; };
+        if self.run_rx.is_some() || self.bbox_missing_warned.as_ref() == Some(dir) || dir.is_dir() { return; }
+        let dir = dir.clone();
+        self.append_log(&format!("[WARN] bbox dir no longer exists, thumbnails are unavailable: {}\n", dir.display()));
+        self.bbox_missing_warned = Some(dir);
+    }
 
-        for cand in candidates {
-            if Command::new(&cand).arg("--version").output().is_ok() { return Ok(cand); }
+    // bbox images of the rows shown, copied or converted on a worker thread
+    fn export_images(&mut self, ctx: &egui::Context) {
+        let mut files = vec![];
+        let mut missing = 0;
+        for i in self.visible_rows() {
+            match self.find_bbox_image_path(&self.results[i].image) {
+                Some(p) if !files.contains(&p) => files.push(p),
+                Some(_) => {}
+                None => missing += 1,
+            }
         }
-        Err(anyhow::anyhow!(
-            "No working Python found. Create venv at {}/.venv or set an explicit path.",
-            project_root.display()
-        ))
+        if files.is_empty() {
+            self.append_log("[WARN] no bbox images found for the rows shown\n");
+            return;
+        }
+        let Some(dir) = FileDialog::new().set_title("Export bbox images to").pick_folder() else { return; };
+        if missing > 0 { self.append_log(&format!("[INFO] {} rows have no bbox image and are skipped\n", missing)); }
+        self.append_log(&format!("[STEP] exporting {} images to {} as {}...\n",
+            files.len(), dir.display(), self.settings.image_export_format.label()));
+        self.image_export_progress = (0, files.len());
+        self.image_export_rx = Some(imgexport::spawn(files, dir, self.settings.image_export_format, self.settings.image_export_quality, ctx.clone()));
     }
 
-    fn resolve_project_root(&mut self) -> Result<PathBuf> {
-        let mut cands: Vec<PathBuf> = vec![PathBuf::from(self.project_root.clone())];
-        let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        cands.push(cwd.clone());
-        for p in cwd.ancestors().skip(1).take(5) { cands.push(p.to_path_buf()); }
-        cands.sort(); cands.dedup();
-        for cand in cands {
-            if looks_like_repo_root(&cand) {
-                self.project_root = cand.to_string_lossy().to_string();
-                return Ok(PathBuf::from(&self.project_root));
+    fn poll_image_export(&mut self) {
+        let Some(rx) = &self.image_export_rx else { return; };
+        let events: Vec<imgexport::ExportEvent> = rx.try_iter().collect();
+        for ev in events {
+            match ev {
+                imgexport::ExportEvent::Progress(done, total) => self.image_export_progress = (done, total),
+                imgexport::ExportEvent::Log(s) => self.append_log(&s),
+                imgexport::ExportEvent::Finished(res) => {
+                    self.image_export_rx = None;
+                    match res {
+                        Ok((written, 0)) => self.append_log(&format!("[DONE] exported {} images\n", written)),
+                        Ok((written, failed)) => self.append_log(&format!("[WARN] exported {} images, {} failed\n", written, failed)),
+                        Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+                    }
+                }
             }
         }
-        Err(anyhow::anyhow!("Could not locate project root containing yolov8/run.py and gemini/run.py"))
     }
 
-    // small thumb in table
-    fn show_bbox_thumb(&mut self, ui: &mut egui::Ui, filename: &str, ctx: &egui::Context) {
-        if let Some(p) = self.find_bbox_image_path(filename) {
-            let key = format!("thumb:{}", p.display());
-            if !self.tex_cache.contains_key(&key) {
-                if let Some(tex) = load_texture_from_path(ctx, &p) {
-                    self.tex_cache.insert(key.clone(), tex);
+    fn export_bundle(&mut self, ctx: &egui::Context) {
+        let Some(json) = self.last_json_path.clone() else { return; };
+        let stem = json.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let Some(dest) = FileDialog::new()
+            .set_title(tr("Export run bundle"))
+            .add_filter("zip", &["zip"])
+            .set_file_name(format!("{}.zip", stem))
+            .save_file() else { return; };
+        if self.last_run_bbox_dir.as_ref().is_none_or(|d| !d.is_dir()) {
+            self.append_log("[WARN] no bbox dir for these results; the bundle will hold the JSON files only\n");
+        }
+        self.append_log(&format!("[STEP] writing run bundle {}...\n", dest.display()));
+        self.bundle_rx = Some((bundle::spawn_export(json, self.last_run_bbox_dir.clone(), dest, ctx.clone()), false));
+    }
+
+    fn import_bundle(&mut self, ctx: &egui::Context) {
+        let root = match self.resolve_project_root() {
+            Ok(r) => r,
+            Err(e) => { self.append_log(&format!("[ERROR] {}\n", e)); return; }
+        };
+        let Some(zip) = FileDialog::new().set_title(tr("Import run bundle")).add_filter("zip", &["zip"]).pick_file() else { return; };
+        self.append_log(&format!("[STEP] importing run bundle {} into {}...\n", zip.display(), root.display()));
+        self.bundle_rx = Some((bundle::spawn_import(zip, root, ctx.clone()), true));
+    }
+
+    fn poll_bundle(&mut self) {
+        let Some((rx, import)) = &self.bundle_rx else { return; };
+        let import = *import;
+        let events: Vec<bundle::BundleEvent> = rx.try_iter().collect();
+        for ev in events {
+            match ev {
+                bundle::BundleEvent::Log(s) => self.append_log(&s),
+                bundle::BundleEvent::Finished(res) => {
+                    self.bundle_rx = None;
+                    match res {
+                        Ok(json) if import => {
+                            self.append_log(&format!("[DONE] imported {}\n", json.display()));
+                            if let Err(e) = self.load_results_file(&json, None) {
+                                self.append_log(&format!("[ERROR] {:#}\n", e));
+                            }
+                        }
+                        Ok(zip) => self.append_log(&format!("[DONE] saved {}\n", zip.display())),
+                        Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+                    }
                 }
             }
-            if let Some(tex) = self.tex_cache.get(&key) {
-                let sized = egui::load::SizedTexture::from_handle(tex);
-                egui::Image::new(sized).max_width(72.0).max_height(54.0).ui(ui);
+        }
+    }
+
+    fn start_share(&mut self) {
+        match share::ShareServer::start(self.settings.share_port) {
+            Ok(s) => {
+                self.append_log(&format!("[INFO] share server running at {}\n", s.url));
+                self.share = Some(s);
+                self.refresh_share();
+            }
+            Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+        }
+    }
+
+    fn stop_share(&mut self) {
+        if let Some(mut s) = self.share.take() {
+            s.stop();
+            self.append_log("[INFO] share server stopped\n");
+        }
+    }
+
+    // the page follows whatever result file is loaded
+    fn refresh_share(&self) {
+        let Some(s) = &self.share else { return; };
+        let title = self.last_json_path.as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        s.update(&self.results, self.last_run_bbox_dir.as_deref(), &title);
+    }
+
+    fn send_webhook(&mut self, ctx: &egui::Context) {
+        let (Some(hook), Some(json)) = (
+            webhook::Webhook::from_settings(&self.settings.webhook_url, &self.settings.webhook_token),
+            self.last_json_path.clone(),
+        ) else { return; };
+        self.append_log(&format!("[STEP] sending {} to the webhook...\n", json.display()));
+        self.webhook_rx = Some(webhook::spawn_send(hook, json, ctx.clone()));
+    }
+
+    fn poll_webhook(&mut self) {
+        let Some(rx) = &self.webhook_rx else { return; };
+        let mut lines = vec![];
+        let done = loop {
+            match rx.try_recv() {
+                Ok(s) => lines.push(s),
+                Err(std::sync::mpsc::TryRecvError::Empty) => break false,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => break true,
+            }
+        };
+        for l in lines { self.append_log(&l); }
+        if done { self.webhook_rx = None; }
+    }
+
+    // drain worker events; called every frame
+    fn poll_run(&mut self) {
+        let Some(rx) = self.run_rx.take() else { return; };
+        let mut finished = false;
+        while let Ok(ev) = rx.try_recv() {
+            match ev {
+                RunEvent::Log(s) => self.append_log(&s),
+                RunEvent::Stage(name, total) => {
+                    if let Some(t) = self.run_timing.as_mut() { t.start_stage(&name, total); }
+                }
+                RunEvent::Command(step) => self.command_log.push(step),
+                RunEvent::Progress(done) => {
+                    if let Some(t) = self.run_timing.as_mut() { t.progress(done); }
+                }
+                RunEvent::Usage(u) => {
+                    if let Some((_, total)) = self.run_usage.as_mut() { total.add(u); }
+                }
+                RunEvent::BboxReady(dir) => {
+                    self.last_run_bbox_dir = Some(dir);
+                    self.load_detections();
+                }
+                RunEvent::Result(one) => {
+                    match self.results.iter_mut().find(|w| w.image == one.image) {
+                        Some(w) => *w = one,
+                        None => self.results.push(one),
+                    }
+                    if self.selected.is_none() { self.selected = Some(0); }
+                }
+                RunEvent::Finished(res) => {
+                    finished = true;
+                    if let Some(t) = self.run_timing.take() {
+                        self.append_log(&format!("[INFO] timing: {}\n", t.finish()));
+                    }
+                    // only scripts that print USAGE lines have a cost
+                    let run_cost = self.run_usage.take()
+                        .filter(|(_, u)| u.images > 0)
+                        .map(|(model, u)| cost::RunCost::new(&model, u, &self.settings.model_prices));
+                    if let Some(c) = &run_cost {
+                        self.append_log(&format!("[INFO] Gemini usage: {}\n", c.describe()));
+                    }
+                    // the saved JSON is authoritative over the streamed rows
+                    let res = res.and_then(|out| {
+                        self.append_log("[STEP] loading results...\n");
+                        self.load_results_file(&out.json, Some(out.bbox_dir.clone()))?;
+                        if !out.gps.is_empty() || !out.meta.is_empty() || out.prompt_file.is_some() || !out.gemini.is_empty() || out.backend.is_some() || run_cost.is_some() || !out.label.is_empty() {
+                            self.sidecar.gps.extend(out.gps);
+                            self.sidecar.meta.extend(out.meta);
+                            if out.prompt_file.is_some() { self.sidecar.prompt_file = out.prompt_file; }
+                            if !out.gemini.is_empty() { self.sidecar.gemini = Some(out.gemini); }
+                            if let Some(id) = out.backend { self.sidecar.backend = Some(id.to_string()); }
+                            if !out.label.is_empty() { self.sidecar.label = out.label; }
+                            match (self.sidecar.cost.as_mut(), run_cost) {
+                                (Some(total), Some(c)) => total.add(c),
+                                (None, c) => self.sidecar.cost = c,
+                                (_, None) => {}
+                            }
+                            self.sidecar.save(&out.json)?;
+                            apply_gps(&mut self.results, &self.sidecar);
+                        }
+                        let finished_at = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                        self.record_run_in_db(&out.json, &finished_at);
+                        self.record_run_in_history(&out.json, Some(out.bbox_dir.clone()), finished_at);
+                        if let Some(ab) = out.ab {
+                            let rows = compare::load_rows(&out.json, &ab.json_b)?;
+                            self.comparison = Some(ComparisonView {
+                                rows,
+                                bbox_a: out.bbox_dir.clone(),
+                                bbox_b: ab.bbox_b,
+                                summary_path: ab.comparison,
+                                selected: None,
+                            });
+                            self.central_tab = CentralTab::Compare;
+                        }
+                        Ok(())
+                    });
+                    let paused = self.run_pause.as_ref().is_some_and(|p| p.load(Ordering::Relaxed));
+                    match res {
+                        Ok(()) => self.append_log("[DONE] Completed.\n"),
+                        Err(e) if paused => self.append_log(&format!("[INFO] {}\n", e)),
+                        Err(e) => {
+                            self.append_log(&format!("[ERROR] {}\n", e));
+                            if let Some(kind) = RunnerError::find(&e) {
+                                let hint = tr(kind.guidance());
+                                self.append_log(&format!("[INFO] {}\n", hint));
+                                self.show_toast(hint.to_string());
+                            }
+                        }
+                    }
+                    // a paused or failed run leaves its checkpoint behind
+                    if let Ok(root) = preflight::project_root(&self.settings.project_root) {
+                        self.resume_offer = resume_offer_in(&root);
+                    }
+                }
+            }
+        }
+        if finished {
+            self.run_cancel = None;
+            self.run_pause = None;
+            self.log_file = None;
+        } else {
+            self.run_rx = Some(rx);
+        }
+    }
+
+    // load a result JSON (+ its sidecar) into the table; bbox dir is inferred from result_<ts>.json when not given
+    fn load_results_file(&mut self, json: &Path, bbox_dir: Option<PathBuf>) -> Result<()> {
+        let data = fs::read_to_string(json).with_context(|| "failed to read result json")?;
+        let parsed = parse_result_file(&data).with_context(|| format!("failed to parse {}", json.display()))?;
+        self.flush_run_info();
+        self.results = parsed.results;
+        self.duplicates.clear();
+        self.sidecar = Sidecar::load(json);
+        self.sources = sources::load(json);
+        apply_gps(&mut self.results, &self.sidecar);
+        self.last_json_path = Some(json.to_path_buf());
+        self.last_run_bbox_dir = bbox_dir.or_else(|| guess_bbox_dir(json));
+        recent::touch(&mut self.settings.recent_results, json);
+        self.load_detections();
+        self.refresh_share();
+
+        // keep selection only if it is still in range, otherwise auto-select first item
+        if self.selected.is_none_or(|i| i >= self.results.len()) {
+            self.selected = (!self.results.is_empty()).then_some(0);
+        }
+
+        // clear caches for new results
+        self.tex_cache.clear();
+        self.thumb_prefetch = None;
+        self.prefetch_due = true;
+        Ok(())
+    }
+
+    // (re)starts or stops the results/ watcher to match the setting and project root, and loads
+    // what it reports; our own runs load their result when they finish
+    fn sync_results_watcher(&mut self, ctx: &egui::Context) {
+        if !self.settings.watch_results {
+            self.results_watcher = None;
+            return;
+        }
+        let root = { let c = self.root_check(); if c.valid { Some(PathBuf::from(&c.text)) } else { c.detected.clone() } };
+        let Some(dir) = root.map(|r| r.join("results")) else {
+            self.results_watcher = None;
+            return;
+        };
+        if self.results_watcher.as_ref().is_none_or(|w| w.dir != dir) {
+            match watch::ResultsWatcher::start(&dir, ctx) {
+                Ok(w) => {
+                    self.append_log(&format!("[INFO] watching {} for new results\n", dir.display()));
+                    self.results_watcher = Some(w);
+                }
+                Err(e) => {
+                    self.append_log(&format!("[WARN] cannot watch the results folder: {:#}\n", e));
+                    self.settings.watch_results = false;
+                    return;
+                }
+            }
+        }
+        let Some(json) = self.results_watcher.as_mut().and_then(|w| w.poll(ctx)) else { return; };
+        if self.run_rx.is_some() || self.last_json_path.as_ref() == Some(&json) { return; }
+        match self.load_results_file(&json, None) {
+            Ok(()) => {
+                self.append_log(&format!("[INFO] loaded new results: {}\n", json.display()));
+                self.show_toast(format!("Loaded {}", json.file_name().unwrap_or_default().to_string_lossy()));
+            }
+            Err(e) => self.append_log(&format!("[WARN] {:#}\n", e)),
+        }
+    }
+
+    fn start_downloads(&mut self, ctx: &egui::Context) {
+        let urls = download::parse_urls(&self.url_input);
+        let root = match self.resolve_project_root() {
+            Ok(r) => r,
+            Err(e) => { self.append_log(&format!("[ERROR] {}\n", e)); return; }
+        };
+        let dir = download::staging_dir(&root.join(".runner_work"));
+        let max_bytes = u64::from(self.settings.download_max_mb) * 1024 * 1024;
+        self.append_log(&format!("[STEP] downloading {} URLs...\n", urls.len()));
+        self.download_rx = Some(download::spawn(urls, dir, max_bytes, ctx.clone()));
+    }
+
+    // the whole log, unfiltered; formatting and writing happen off the UI thread
+    fn save_log(&mut self, ctx: &egui::Context) {
+        let Some(dest) = FileDialog::new()
+            .set_title(tr("Save log..."))
+            .add_filter("text", &["txt"])
+            .set_file_name(format!("runner_log_{}.txt", Local::now().format("%Y%m%d_%H%M%S")))
+            .save_file() else { return; };
+        let entries: Vec<applog::LogEntry> = self.log.iter().cloned().collect();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let res = applog::save(&dest, &entries).map(|_| entries.len());
+            let _ = tx.send((dest, res));
+            ctx.request_repaint();
+        });
+        self.log_save_rx = Some(rx);
+    }
+
+    fn poll_log_save(&mut self) {
+        let Some(rx) = &self.log_save_rx else { return; };
+        let (path, res) = match rx.try_recv() {
+            Ok(done) => done,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.log_save_rx = None;
+                self.append_log("[ERROR] the log save stopped without a result\n");
                 return;
             }
+        };
+        self.log_save_rx = None;
+        match res {
+            Ok(n) => self.append_log(&format!("[DONE] saved {} log lines to {}\n", n, path.display())),
+            Err(e) => self.append_log(&format!("[ERROR] failed to save the log to {}: {}\n", path.display(), e)),
         }
-        ui.label("—");
     }
 
-    // resolve selected image full path (robust to extension mismatches)
-    fn find_bbox_image_for_selected(&self) -> Option<PathBuf> {
-        if let Some(sel) = self.selected_image.as_ref() {
-            self.find_bbox_image_path(sel)
-        } else { None }
+    fn start_gpu_probe(&mut self, ctx: &egui::Context) {
+        let python = match self.resolve_project_root().and_then(|root| preflight::python(&self.settings.python_bin, &root)) {
+            Ok(p) => p,
+            Err(e) => { self.append_log(&format!("[ERROR] {:#}\n", e)); return; }
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(preflight::gpu(&python).map_err(|e| format!("{:#}", e)));
+            ctx.request_repaint();
+        });
+        self.gpu_probe_rx = Some(rx);
     }
 
-    fn find_bbox_image_path(&self, filename: &str) -> Option<PathBuf> {
-        let dir = self.last_run_bbox_dir.as_ref()?;
-        let direct = dir.join(filename);
-        if direct.exists() { return Some(direct); }
-        // fallback: search by stem across extensions
-        let stem = Path::new(filename).file_stem()?.to_string_lossy().to_string();
-        let exts = ["jpg","jpeg","png","webp","bmp"];
-        for e in &exts {
-            let cand = dir.join(format!("{}.{}", stem, e));
-            if cand.exists() { return Some(cand); }
+    fn poll_gpu_probe(&mut self) {
+        let Some(res) = self.gpu_probe_rx.as_ref().and_then(|rx| rx.try_recv().ok()) else { return; };
+        self.gpu_probe_rx = None;
+        match res {
+            Ok(a @ preflight::Accel::Cuda(_)) => self.append_log(&format!("[OK] gpu: {}\n", a.describe())),
+            Ok(a) => self.append_log(&format!("[WARN] gpu: {}\n", a.describe())),
+            Err(e) => self.append_log(&format!("[ERROR] gpu: {}\n", e)),
         }
-        // as a last resort, scan all files in dir and match stem
-        if let Ok(rd) = fs::read_dir(dir) {
-            for ent in rd.flatten() {
-                let p = ent.path();
-                if p.is_file() {
-                    if let Some(s) = p.file_stem().map(|s| s.to_string_lossy().to_string()) {
-                        if s == stem { return Some(p); }
-                    }
+    }
+
+    fn poll_downloads(&mut self) {
+        let Some(rx) = &self.download_rx else { return; };
+        let events: Vec<download::DownloadEvent> = rx.try_iter().collect();
+        for ev in events {
+            match ev {
+                download::DownloadEvent::Log(s) => self.append_log(&s),
+                download::DownloadEvent::Saved(p) => self.pending_files.push(p),
+                download::DownloadEvent::Finished => {
+                    self.download_rx = None;
+                    self.url_input.clear();
                 }
             }
         }
-        None
     }
-}
 
-fn looks_like_repo_root(dir: &Path) -> bool {
-    dir.join("yolov8").join("run.py").exists() &&
-    dir.join("gemini").join("run.py").exists()
-}
+    // clipboard image → staged PNG in pending_files; `quiet` skips the note when there is no image
+    // (a Ctrl+V of plain text)
+    fn paste_image(&mut self, quiet: bool) {
+        let res = self.resolve_project_root()
+            .and_then(|root| clipboard::stage_image(&root.join(".runner_work")));
+        match res {
+            Ok(Some(p)) => {
+                self.append_log(&format!("[INFO] pasted image saved as {}\n", p.display()));
+                self.pending_files.push(p);
+            }
+            Ok(None) if quiet => {}
+            Ok(None) => self.append_log("[INFO] the clipboard has no image to paste; copy an image or screenshot first\n"),
+            Err(e) => self.append_log(&format!("[WARN] could not paste an image: {:#}\n", e)),
+        }
+    }
 
-fn split_name_ext(name: &str) -> (String, String) {
-    let p = Path::new(name);
-    let stem = p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| name.to_string());
-    let ext  = p.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
-    (stem, ext)
-}
+    // queues images and remembers the folders they came from
+    fn add_pending(&mut self, files: Vec<PathBuf>) {
+        let mut dirs: Vec<PathBuf> = files.iter().filter_map(|p| p.parent().map(|d| d.to_path_buf())).collect();
+        dirs.dedup();
+        for d in dirs.iter().rev() { recent::touch(&mut self.settings.recent_input_dirs, d); }
+        self.pending_files.extend(files);
+    }
 
-fn load_texture_from_path(ctx: &egui::Context, path: &Path) -> Option<egui::TextureHandle> {
-    let data = fs::read(path).ok()?;
-    let img = image::load_from_memory(&data).ok()?;
-    let rgba = img.to_rgba8();
-    let size = [img.width() as usize, img.height() as usize];
-    let color_img = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
-    Some(ctx.load_texture(
-        path.file_name()?.to_string_lossy(),
-        color_img,
-        egui::TextureOptions::default(),
-    ))
+    fn recent_menu(&mut self, ui: &mut egui::Ui) {
+        // files deleted or moved since they were used are dropped as the menu opens
+        recent::prune_missing(&mut self.settings.recent_results);
+        recent::prune_missing(&mut self.settings.recent_input_dirs);
+        ui.label(egui::RichText::new(tr("Result files")).strong());
+        if self.settings.recent_results.is_empty() { ui.weak(tr("none")); }
+        let mut open: Option<PathBuf> = None;
+        for e in &self.settings.recent_results {
+            let name = e.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            if ui.button(format!("{}  ({})", name, e.at)).on_hover_text(e.path.display().to_string()).clicked() {
+                open = Some(e.path.clone());
+            }
+        }
+        ui.separator();
+        ui.label(egui::RichText::new(tr("Input folders")).strong());
+        if self.settings.recent_input_dirs.is_empty() { ui.weak(tr("none")); }
+        let mut add_dir: Option<PathBuf> = None;
+        for e in &self.settings.recent_input_dirs {
+            if ui.button(format!("{}  ({})", e.path.display(), e.at)).on_hover_text(tr("Add the images in this folder")).clicked() {
+                add_dir = Some(e.path.clone());
+            }
+        }
+        ui.separator();
+        if ui.button(tr("Clear recent")).clicked() {
+            self.settings.recent_results.clear();
+            self.settings.recent_input_dirs.clear();
+            ui.close_menu();
+        }
+        if let Some(p) = open {
+            ui.close_menu();
+            if let Err(e) = self.load_results_file(&p, None) {
+                self.append_log(&format!("[ERROR] {}\n", e));
+            }
+        }
+        if let Some(dir) = add_dir {
+            ui.close_menu();
+            let files = images_in_dir(&dir);
+            self.append_log(&format!("[INFO] added {} images from {}\n", files.len(), dir.display()));
+            self.add_pending(files);
+        }
+    }
+
+    fn show_removal_dialog(&mut self, ctx: &egui::Context) {
+        let Some(idx) = self.pending_removal else { return; };
+        let Some(image) = self.results.get(idx).map(|w| w.image.clone()) else {
+            self.pending_removal = None;
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr("Remove entry"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(format!("{} \"{}\"", tr("Remove from the loaded results:"), image));
+                match &self.last_json_path {
+                    Some(p) => { ui.label(format!("{} {}", tr("Rewrites"), p.display())); }
+                    None => { ui.label(tr("No result JSON is loaded; only the table is affected.")); }
+                }
+                ui.checkbox(&mut self.remove_bbox_too, tr("Also delete the bbox image file"));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Remove")).clicked() { confirmed = true; }
+                    if ui.button(tr("Cancel")).clicked() { cancelled = true; }
+                });
+            });
+        if confirmed {
+            self.pending_removal = None;
+            if let Err(e) = self.remove_entry(idx) {
+                self.append_log(&format!("[ERROR] {}\n", e));
+            }
+        } else if cancelled {
+            self.pending_removal = None;
+        }
+    }
+
+    fn remove_entry(&mut self, idx: usize) -> Result<()> {
+        let image = self.results[idx].image.clone();
+        let bbox = self.find_bbox_image_path(&image);
+        if let Some(json) = self.last_json_path.clone() {
+            remove_entry_from_json(&json, idx, &image)?;
+            self.append_log(&format!("[INFO] removed {} from {}\n", image, json.display()));
+        }
+        self.results.remove(idx);
+        self.duplicates.clear();
+        // notes are keyed by name, so only drop them once no entry with that name is left
+        if !self.results.iter().any(|w| w.image == image) && self.sidecar.notes.remove(&image).is_some() {
+            if let Some(json) = &self.last_json_path { self.sidecar.save(json)?; }
+        }
+        self.selected = match self.selected {
+            Some(s) if s == idx => (!self.results.is_empty()).then_some(idx.min(self.results.len() - 1)),
+            Some(s) if s > idx => Some(s - 1),
+            other => other,
+        };
+        if self.remove_bbox_too {
+            if let Some(p) = bbox {
+                fs::remove_file(&p).with_context(|| format!("failed to delete {}", p.display()))?;
+                self.tex_cache.retain(|k, _| !k.ends_with(&p.display().to_string()));
+                self.append_log(&format!("[INFO] deleted bbox image {}\n", p.display()));
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_results(&mut self) {
+        let results_dir = self.resolve_project_root()
+            .map(|r| r.join("results"))
+            .unwrap_or_else(|_| PathBuf::from("results"));
+        let Some(files) = FileDialog::new()
+            .add_filter("json", &["json"])
+            .set_directory(&results_dir)
+            .pick_files() else { return; };
+        let res = merge::merge_result_files(&files, &results_dir).and_then(|out| {
+            for line in &out.log { self.append_log(&format!("{}\n", line)); }
+            self.load_results_file(&out.path, None)?;
+            Ok(out)
+        });
+        match res {
+            Ok(out) => self.append_log(&format!("[DONE] merged {} entries into {}\n", out.entries, out.path.display())),
+            Err(e) => self.append_log(&format!("[ERROR] merge failed: {:#}\n", e)),
+        }
+    }
+
+    // opens .runner_work/runner.db on first use; failures disable the DB for the session with one warning
+    fn ensure_db(&mut self) -> Option<&mut RunDb> {
+        if self.db.is_none() && !self.db_unavailable {
+            let opened = self.resolve_project_root()
+                .and_then(|root| RunDb::open(&root.join(".runner_work").join("runner.db")));
+            match opened {
+                Ok(db) => self.db = Some(db),
+                Err(e) => {
+                    self.db_unavailable = true;
+                    self.append_log(&format!("[WARN] run database disabled: {:#}\n", e));
+                }
+            }
+        }
+        self.db.as_mut()
+    }
+
+    fn record_run_in_db(&mut self, json: &Path, created_at: &str) {
+        let results = self.results.clone();
+        let Some(db) = self.ensure_db() else { return; };
+        if let Err(e) = db.insert_run(&json.to_string_lossy(), created_at, &results) {
+            self.append_log(&format!("[WARN] could not record run in database: {:#}\n", e));
+        }
+        if self.chart_recent_runs { self.refresh_chart_history(); }
+    }
+
+    fn refresh_chart_history(&mut self) {
+        self.chart_history.clear();
+        if !self.chart_recent_runs { return; }
+        let Some(db) = self.ensure_db() else { return; };
+        match db.verdict_counts_by_run(CHART_RECENT_RUNS) {
+            Ok(runs) => {
+                self.chart_history = runs.into_iter()
+                    .map(|(json, created_at, counts)| (json, chart::BarGroup { label: created_at, counts }))
+                    .collect();
+            }
+            Err(e) => self.append_log(&format!("[WARN] run database query failed: {:#}\n", e)),
+        }
+    }
+
+    // the loaded table, after the DB runs when those are enabled; recomputed each frame so edits show up
+    fn chart_groups(&self) -> Vec<chart::BarGroup> {
+        // the loaded file is usually also in the DB; show it once, with live counts
+        let current = self.last_json_path.as_ref().map(|p| p.to_string_lossy().to_string());
+        let mut groups: Vec<chart::BarGroup> = self.chart_history.iter()
+            .filter(|(json, _)| Some(json) != current.as_ref())
+            .map(|(_, g)| g.clone())
+            .collect();
+        if !self.results.is_empty() {
+            let (yes, no, unknown) = verdict_counts(&self.results, self.min_confidence());
+            let label = self.last_json_path.as_ref()
+                .and_then(|p| p.file_stem())
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "current".to_string());
+            groups.push(chart::BarGroup { label, counts: [yes, no, unknown] });
+        }
+        groups
+    }
+
+    // backfill the DB from results/*.json
+    fn import_existing_results(&mut self) {
+        let Ok(root) = self.resolve_project_root() else { return; };
+        let Ok(rd) = fs::read_dir(root.join("results")) else { return; };
+        let mut files: Vec<PathBuf> = rd.flatten().map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "json") && !p.to_string_lossy().ends_with(".sidecar.json"))
+            .collect();
+        files.sort();
+        let (mut added, mut skipped) = (0, 0);
+        let mut warnings = vec![];
+        if let Some(db) = self.ensure_db() {
+            for f in &files {
+                let parsed = fs::read_to_string(f).ok()
+                    .and_then(|d| serde_json::from_str::<WheelResultFile>(&d).ok());
+                let Some(parsed) = parsed else {
+                    warnings.push(format!("[WARN] not a result JSON, skipped: {}\n", f.display()));
+                    continue;
+                };
+                match db.insert_run(&f.to_string_lossy(), &run_created_at(f), &parsed.results) {
+                    Ok(true) => added += 1,
+                    Ok(false) => skipped += 1,
+                    Err(e) => warnings.push(format!("[WARN] import failed for {}: {:#}\n", f.display(), e)),
+                }
+            }
+        }
+        for w in warnings { self.append_log(&w); }
+        self.append_log(&format!("[DONE] imported {} run(s), {} already present\n", added, skipped));
+    }
+
+    fn record_run_in_history(&mut self, json: &Path, bbox_dir: Option<PathBuf>, at: String) {
+        // the model's verdicts, not the confidence rule of the moment
+        let (accessible, inaccessible, undetermined) = verdict_counts(&self.results, None);
+        let label = self.sidecar.label().map(str::to_string);
+        let rec = history::RunRecord { at, images: self.results.len(), accessible, inaccessible, undetermined, json: json.to_path_buf(), bbox_dir, label };
+        history::push(&mut self.run_history, rec);
+        if self.settings.persist_history {
+            if let Err(e) = history::save(&self.run_history) {
+                self.append_log(&format!("[WARN] failed to save the run history: {:#}\n", e));
+            }
+        }
+    }
+
+    // label and notes of the loaded run, saved to its sidecar once the field is left
+    fn show_run_info(&mut self, ui: &mut egui::Ui, json: &Path) {
+        let mut changed = false;
+        let mut left = false;
+        egui::Grid::new("run_info_grid").num_columns(2).show(ui, |ui| {
+            ui.label(tr("Result file"));
+            ui.monospace(json.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default())
+                .on_hover_text(json.display().to_string());
+            ui.end_row();
+            ui.label(tr("Run label"));
+            let resp = ui.add(egui::TextEdit::singleline(&mut self.sidecar.label).desired_width(320.0));
+            (changed, left) = (changed | resp.changed(), left | resp.lost_focus());
+            ui.end_row();
+            ui.label(tr("Notes"));
+            let resp = ui.add(egui::TextEdit::multiline(&mut self.sidecar.run_notes)
+                .hint_text("e.g. surveyed after rain, north exit closed")
+                .desired_rows(3)
+                .desired_width(320.0));
+            (changed, left) = (changed | resp.changed(), left | resp.lost_focus());
+            ui.end_row();
+        });
+        self.run_info_dirty |= changed;
+        if left { self.flush_run_info(); }
+    }
+
+    fn flush_run_info(&mut self) {
+        if !std::mem::take(&mut self.run_info_dirty) { return; }
+        let Some(json) = self.last_json_path.clone() else { return; };
+        let json = json.as_path();
+        if let Err(e) = self.sidecar.save(json) {
+            self.append_log(&format!("[WARN] run info not saved: {}\n", e));
+        }
+        // the History entry shows the label too
+        let label = self.sidecar.label().map(str::to_string);
+        let Some(rec) = self.run_history.iter_mut().find(|r| r.json == json) else { return; };
+        if rec.label == label { return; }
+        rec.label = label;
+        if self.settings.persist_history {
+            if let Err(e) = history::save(&self.run_history) {
+                self.append_log(&format!("[WARN] failed to save the run history: {:#}\n", e));
+            }
+        }
+    }
+
+    // the loaded run's label (or result file name) in the title bar
+    fn sync_window_title(&mut self, ctx: &egui::Context) {
+        let run = self.sidecar.label().map(str::to_string).or_else(|| {
+            self.last_json_path.as_ref()?.file_name().map(|n| n.to_string_lossy().to_string())
+        });
+        let app = match settings::portable_dir() {
+            Some(_) => format!("{} ({})", APP_TITLE, tr("portable")),
+            None => APP_TITLE.to_string(),
+        };
+        let title = match run.filter(|_| self.last_json_path.is_some()) {
+            Some(run) => format!("{} — {}", run, app),
+            None => app,
+        };
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+    }
+
+    fn show_history_tab(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(tr("Run history"));
+            if ui.checkbox(&mut self.settings.persist_history, tr("Keep across sessions")).changed() {
+                if self.settings.persist_history {
+                    // this session's runs come first, then what an earlier session saved
+                    for rec in history::load().into_iter().rev() {
+                        if !self.run_history.iter().any(|r| r.json == rec.json) { self.run_history.push(rec); }
+                    }
+                    self.run_history.truncate(history::MAX_HISTORY);
+                    if let Err(e) = history::save(&self.run_history) {
+                        self.append_log(&format!("[WARN] failed to save the run history: {:#}\n", e));
+                    }
+                } else {
+                    history::delete_saved();
+                }
+            }
+            if ui.add_enabled(!self.run_history.is_empty(), egui::Button::new(tr("Clear"))).clicked() {
+                self.run_history.clear();
+                history::delete_saved();
+            }
+        });
+        if self.run_history.is_empty() {
+            ui.weak(tr("No finished runs yet. Each completed run is listed here; click one to reload its results."));
+            return;
+        }
+        let mut open = None;
+        let colorblind = self.settings.colorblind_palette;
+        TableBuilder::new(ui)
+            .striped(true)
+            .column(Column::auto())
+            .column(Column::auto())
+            .columns(Column::auto(), 3)
+            .column(Column::remainder())
+            .header(20.0, |mut header| {
+                for h in ["Finished", "Images", "✔", "✖", "?", "Run"] {
+                    header.col(|ui| { ui.strong(tr(h)); });
+                }
+            })
+            .body(|mut body| {
+                for (i, r) in self.run_history.iter().enumerate() {
+                    body.row(20.0, |mut row| {
+                        row.col(|ui| { ui.label(&r.at); });
+                        row.col(|ui| { ui.label(r.images.to_string()); });
+                        for (v, n) in [(Some(true), r.accessible), (Some(false), r.inaccessible), (None, r.undetermined)] {
+                            row.col(|ui| { ui.colored_label(verdict_style(v, colorblind).2, n.to_string()); });
+                        }
+                        row.col(|ui| {
+                            let current = self.last_json_path.as_deref() == Some(r.json.as_path());
+                            if ui.selectable_label(current, r.title()).on_hover_text(r.json.display().to_string()).clicked() {
+                                open = Some(i);
+                            }
+                        });
+                    });
+                }
+            });
+        if let Some(i) = open {
+            let r = self.run_history[i].clone();
+            match self.load_results_file(&r.json, r.bbox_dir.clone().filter(|d| d.is_dir())) {
+                Ok(()) => self.central_tab = CentralTab::Results,
+                Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+            }
+        }
+    }
+
+    fn show_query_tab(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("Run database"));
+        if self.db_unavailable {
+            ui.label(tr("The run database could not be opened; see the log."));
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("Verdict:"));
+            verdict_filter_combo(ui, "db_verdict", &mut self.db_query.verdict);
+            ui.label(tr("From:"));
+            ui.add(egui::TextEdit::singleline(&mut self.db_query.date_from).hint_text("YYYY-MM-DD").desired_width(90.0));
+            ui.label(tr("To:"));
+            ui.add(egui::TextEdit::singleline(&mut self.db_query.date_to).hint_text("YYYY-MM-DD").desired_width(90.0));
+            ui.label(tr("Reason contains:"));
+            ui.add(egui::TextEdit::singleline(&mut self.db_query.reason_contains).desired_width(140.0));
+        });
+        ui.horizontal(|ui| {
+            if ui.button(tr("Search")).clicked() {
+                let q = self.db_query.clone();
+                let res = self.ensure_db().map(|db| db.query(&q));
+                match res {
+                    Some(Ok(rows)) => self.db_rows = rows,
+                    Some(Err(e)) => self.append_log(&format!("[ERROR] query failed: {:#}\n", e)),
+                    None => {}
+                }
+            }
+            if ui.button(tr("Import existing results")).clicked() {
+                self.import_existing_results();
+            }
+            if ui.add_enabled(!self.db_rows.is_empty(), egui::Button::new(tr("Export CSV..."))).clicked() {
+                self.export_db_rows_csv();
+            }
+            ui.label(format!("{} {}", self.db_rows.len(), tr("rows")));
+        });
+        ui.add_space(6.0);
+
+        let rows = self.db_rows.clone(); // avoid borrow conflicts
+        let mut open_json: Option<PathBuf> = None;
+        egui::ScrollArea::vertical()
+            .id_source("db_scroll")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(Column::auto().at_least(200.0))   // Image name
+                    .column(Column::auto().at_least(110.0))   // Accessible
+                    .column(Column::auto().at_least(140.0))   // Run time
+                    .column(Column::remainder())               // Reason
+                    .header(22.0, |mut header| {
+                        header.col(|ui| { ui.strong(tr("Image")); });
+                        header.col(|ui| { ui.strong(tr("Accessible")); });
+                        header.col(|ui| { ui.strong(tr("Run")); });
+                        header.col(|ui| { ui.strong(tr("Reason")); });
+                    })
+                    .body(|mut body| {
+                        for r in &rows {
+                            body.row(24.0, |mut row| {
+                                row.col(|ui| {
+                                    if ui.link(&r.image).on_hover_text(format!("{} {}", tr("Open"), r.json_path)).clicked() {
+                                        open_json = Some(PathBuf::from(&r.json_path));
+                                    }
+                                });
+                                row.col(|ui| {
+                                    verdict_label(ui, r.accessible, self.settings.colorblind_palette);
+                                });
+                                row.col(|ui| { ui.label(&r.created_at); });
+                                row.col(|ui| {
+                                    reason_cell(ui, &r.reason);
+                                });
+                            });
+                        }
+                    });
+            });
+        if let Some(json) = open_json {
+            match self.load_results_file(&json, None) {
+                Ok(()) => self.central_tab = CentralTab::Results,
+                Err(e) => self.append_log(&format!("[ERROR] {}\n", e)),
+            }
+        }
+    }
+
+    fn show_compare_tab(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some(view) = self.comparison.as_ref() else { return; };
+        let summary = compare::summarize(&view.rows);
+        ui.heading(tr("A/B comparison"));
+        ui.label(format!("{} {}/{} ({:.1}%) — {}", tr("Agreement"), summary.agree, summary.total, summary.rate() * 100.0, view.summary_path.display()));
+        ui.add_space(6.0);
+
+        let rows = view.rows.clone(); // avoid borrow conflicts
+        let selected = view.selected;
+        let mut clicked: Option<usize> = None;
+        let colorblind = self.settings.colorblind_palette;
+        let verdict = |ui: &mut egui::Ui, j: &Option<WheelJudge>| match j {
+            Some(j) => { verdict_label(ui, j.accessible, colorblind); }
+            None => { ui.weak(tr("missing")); }
+        };
+        egui::ScrollArea::vertical()
+            .id_source("compare_scroll")
+            .max_height(ui.available_height() * 0.45)
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(Column::auto().at_least(200.0))   // Image name
+                    .column(Column::auto().at_least(110.0))   // Verdict A
+                    .column(Column::auto().at_least(110.0))   // Verdict B
+                    .column(Column::remainder())
+                    .header(22.0, |mut header| {
+                        header.col(|ui| { ui.strong(tr("Image")); });
+                        header.col(|ui| { ui.strong(tr("Verdict A")); });
+                        header.col(|ui| { ui.strong(tr("Verdict B")); });
+                        header.col(|_ui| {});
+                    })
+                    .body(|mut body| {
+                        for (i, r) in rows.iter().enumerate() {
+                            body.row(24.0, |mut row| {
+                                row.col(|ui| {
+                                    if ui.selectable_label(selected == Some(i), &r.image).clicked() { clicked = Some(i); }
+                                });
+                                row.col(|ui| verdict(ui, &r.a));
+                                row.col(|ui| verdict(ui, &r.b));
+                                row.col(|ui| {
+                                    if !r.agrees() {
+                                        ui.colored_label(Color32::from_rgb(230,159,0), tr("≠ disagree"));
+                                    }
+                                });
+                            });
+                        }
+                    });
+            });
+        if let (Some(i), Some(view)) = (clicked, self.comparison.as_mut()) { view.selected = Some(i); }
+
+        ui.separator();
+        let Some(view) = self.comparison.as_ref() else { return; };
+        let Some(row) = view.selected.and_then(|i| view.rows.get(i)).cloned() else {
+            ui.label(tr("Select a row to compare both bbox images side by side."));
+            return;
+        };
+        let sides = [
+            ("A", find_image_in_dir(&view.bbox_a, &row.image), row.a),
+            ("B", find_image_in_dir(&view.bbox_b, &row.image), row.b),
+        ];
+        let half = (ui.available_width() / 2.0 - 12.0).max(100.0);
+        ui.horizontal_top(|ui| {
+            for (tag, path, judge) in sides {
+                ui.vertical(|ui| {
+                    ui.set_width(half);
+                    ui.strong(format!("{} {}", tr("Variant"), tag));
+                    if let Some(j) = &judge { ui.label(&j.reason); }
+                    egui::ScrollArea::both()
+                        .id_source(format!("compare_preview_{}", tag))
+                        .show(ui, |ui| match &path {
+                            Some(p) => { self.show_image_file(ui, ctx, p, egui::vec2(half, f32::INFINITY), PreviewZoom::Width); }
+                            None => { ui.label(tr("No bbox image.")); }
+                        });
+                });
+            }
+        });
+    }
+
+    // full-resolution image scaled by `zoom` into `avail`, cached under a `big:` key
+    // the original with boxes drawn from the YOLO labels when the run has them,
+    // otherwise the bbox image YOLO rendered
+    fn show_preview(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, bbox_image: &Path, avail: egui::Vec2, zoom: PreviewZoom) {
+        let Some(image) = self.selected_entry().map(|w| w.image.clone()) else { return; };
+        // the photo from the run's sources map, when it still exists
+        let original = sources::original(&self.sources, &image).filter(|p| p.is_file()).map(Path::to_path_buf);
+        let overlay = self.labels.as_mut().zip(original).and_then(|(l, original)| {
+            // HEIC originals can't be decoded here, nor frames (they map to their video); the bbox image has the boxes drawn in
+            if convert::needs_conversion(&original.to_string_lossy()) || video::is_video(&original) { return None; }
+            Some((original, l.detections(&image).to_vec()))
+        });
+        let (shown, dets) = match overlay {
+            Some((original, dets)) => (original, Some(dets)),
+            None => (bbox_image.to_path_buf(), None),
+        };
+        if let Some(dets) = &dets {
+            let mut classes: Vec<&str> = dets.iter().map(|d| d.class.as_str()).collect();
+            classes.sort_unstable();
+            classes.dedup();
+            ui.horizontal_wrapped(|ui| {
+                ui.checkbox(&mut self.settings.show_boxes, "Show boxes");
+                ui.add_enabled_ui(self.settings.show_boxes, |ui| {
+                    for c in &classes {
+                        let mut shown = !self.hidden_classes.contains(*c);
+                        let text = egui::RichText::new(*c).color(class_color(c));
+                        if ui.checkbox(&mut shown, text).changed() {
+                            if shown { self.hidden_classes.remove(*c); } else { self.hidden_classes.insert(c.to_string()); }
+                        }
+                    }
+                });
+                if dets.is_empty() { ui.weak("no detections"); }
+            });
+        }
+        let Some(resp) = self.show_image_file(ui, ctx, &shown, avail, zoom) else { return; };
+        if let Some(dets) = dets.filter(|_| self.settings.show_boxes) {
+            paint_detections(&ui.painter_at(resp.rect), resp.rect, &dets, &self.hidden_classes);
+        }
+        self.measure_region(ui, &resp, &shown);
+    }
+
+    // drag a rectangle on the preview to read its size in pixels of the image file (the preview
+    // may be scaled down and scrolled; the response rect is where the image actually is on screen)
+    fn measure_region(&mut self, ui: &mut egui::Ui, resp: &egui::Response, shown: &Path) {
+        let Some(size) = self.tex_cache.get(&format!("big:{}", shown.display())).map(|t| t.size_vec2()) else { return; };
+        let resp = resp.interact(egui::Sense::drag());
+        let rect = resp.rect;
+        let to_px = |p: egui::Pos2| egui::pos2(
+            ((p.x - rect.min.x) / rect.width() * size.x).clamp(0.0, size.x).round(),
+            ((p.y - rect.min.y) / rect.height() * size.y).clamp(0.0, size.y).round(),
+        );
+        // Space+drag pans instead (see show_image_file)
+        let space = ui.input(|i| i.key_down(egui::Key::Space));
+        if resp.drag_started_by(egui::PointerButton::Primary) && !space {
+            if let Some(p) = resp.interact_pointer_pos() {
+                self.measure = Some(Measure { image: shown.to_path_buf(), a: to_px(p), b: to_px(p) });
+            }
+        } else if resp.dragged_by(egui::PointerButton::Primary) && !space {
+            if let (Some(m), Some(p)) = (&mut self.measure, resp.interact_pointer_pos()) { m.b = to_px(p); }
+        }
+        let Some(sel) = self.measure.as_ref().filter(|m| m.image == shown).map(Measure::rect) else {
+            ui.weak(tr("Drag on the image to measure a region."));
+            return;
+        };
+        let scale = rect.size() / size;
+        let on_screen = egui::Rect::from_min_max(rect.min + sel.min.to_vec2() * scale, rect.min + sel.max.to_vec2() * scale);
+        ui.painter_at(rect).rect_stroke(on_screen, 0.0, egui::Stroke::new(1.5, Color32::YELLOW));
+        ui.horizontal(|ui| {
+            ui.label(format!("{} {} × {} px @ ({}, {})", tr("Selection:"), sel.width(), sel.height(), sel.min.x, sel.min.y));
+            let empty = sel.width() < 1.0 || sel.height() < 1.0;
+            if ui.add_enabled(!empty, egui::Button::new(tr("Export crop..."))).clicked() {
+                self.export_crop(shown, sel);
+            }
+            if ui.small_button(tr("Clear")).clicked() { self.measure = None; }
+        });
+    }
+
+    fn export_crop(&mut self, image: &Path, sel: egui::Rect) {
+        let (x, y, w, h) = (sel.min.x as u32, sel.min.y as u32, sel.width() as u32, sel.height() as u32);
+        let stem = image.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let Some(path) = FileDialog::new()
+            .add_filter("PNG", &["png"])
+            .set_file_name(format!("{}_crop_{}_{}_{}x{}.png", stem, x, y, w, h))
+            .save_file() else { return; };
+        // the selection is in the preview's pixels, which are EXIF-rotated
+        let res = fs::read(image).ok().and_then(|d| exif_info::decode_upright(&d))
+            .with_context(|| format!("failed to open {}", image.display()))
+            .and_then(|img| Ok(img.crop_imm(x, y, w, h).save(&path)?));
+        match res {
+            Ok(()) => self.append_log(&format!("[OK] saved {}×{} crop to {}\n", w, h, path.display())),
+            Err(e) => self.append_log(&format!("[ERROR] crop export failed: {:#}\n", e)),
+        }
+    }
+
+    fn show_image_file(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, p: &Path, avail: egui::Vec2, zoom: PreviewZoom) -> Option<egui::Response> {
+        let key = format!("big:{}", p.display());
+        if !self.tex_cache.contains_key(&key) {
+            if let Some(tex) = load_texture_from_path(ctx, p) {
+                self.tex_cache.insert(key.clone(), tex);
+            }
+        }
+        match self.tex_cache.get(&key) {
+            Some(tex) => {
+                let size = tex.size_vec2();
+                // what is left of `avail` here, less a row under the image for the measure line
+                let row = ui.spacing().interact_size.y + ui.spacing().item_spacing.y;
+                let avail = avail.min(ui.available_size() - egui::vec2(0.0, row)).max(egui::Vec2::splat(1.0));
+                let scale = zoom.scale(size, avail);
+                let sized = egui::load::SizedTexture::from_handle(tex);
+                let resp = egui::Image::new(sized)
+                    .fit_to_exact_size(size * scale)
+                    .ui(ui);
+                // middle-drag, or Space held with the primary button, scrolls the enclosing area
+                let drag = resp.interact(egui::Sense::drag());
+                let space = ui.input(|i| i.key_down(egui::Key::Space));
+                if drag.dragged_by(egui::PointerButton::Middle) || (space && drag.dragged_by(egui::PointerButton::Primary)) {
+                    ui.scroll_with_delta(drag.drag_delta());
+                    ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+                } else if space && drag.hovered() {
+                    ctx.set_cursor_icon(egui::CursorIcon::Grab);
+                }
+                Some(resp)
+            }
+            None => { ui.label("Failed to load image."); None }
+        }
+    }
+
+    fn export_db_rows_csv(&mut self) {
+        let Some(path) = FileDialog::new().add_filter("csv", &["csv"]).set_file_name("query.csv").save_file() else { return; };
+        let table: Vec<Vec<String>> = self.db_rows.iter().map(|r| vec![
+            r.image.clone(),
+            export::verdict_cell(r.accessible).to_string(),
+            r.reason.clone(),
+            r.created_at.clone(),
+            r.json_path.clone(),
+        ]).collect();
+        match export::write_csv_table(&path, &["image", "accessible", "reason", "run_time", "result_json"], &table) {
+            Ok(()) => self.append_log(&format!("[DONE] exported CSV: {}\n", path.display())),
+            Err(e) => self.append_log(&format!("[ERROR] {}\n", e)),
+        }
+    }
+
+    fn export_csv(&mut self) {
+        let default_name = self.last_json_path.as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| format!("{}.csv", s.to_string_lossy()))
+            .unwrap_or_else(|| "results.csv".to_string());
+        let Some(path) = FileDialog::new().add_filter("csv", &["csv"]).set_file_name(default_name).save_file() else { return; };
+        match export::write_csv(&path, &self.results, &self.sidecar) {
+            Ok(()) => self.append_log(&format!("[DONE] exported CSV: {}\n", path.display())),
+            Err(e) => self.append_log(&format!("[ERROR] {}\n", e)),
+        }
+    }
+
+    fn starred_rows(&self) -> Vec<WheelOne> {
+        self.results.iter().filter(|w| self.sidecar.is_starred(&w.image)).cloned().collect()
+    }
+
+    // "<result stem>_starred.<ext>"
+    fn starred_file_name(&self, ext: &str) -> String {
+        let stem = self.last_json_path.as_ref().and_then(|p| p.file_stem()).map(|s| s.to_string_lossy().to_string());
+        format!("{}_starred.{}", stem.as_deref().unwrap_or("results"), ext)
+    }
+
+    fn export_starred_csv(&mut self) {
+        let Some(path) = FileDialog::new().add_filter("csv", &["csv"]).set_file_name(self.starred_file_name("csv")).save_file() else { return; };
+        let rows = self.starred_rows();
+        match export::write_csv(&path, &rows, &self.sidecar) {
+            Ok(()) => self.append_log(&format!("[DONE] exported {} starred rows: {}\n", rows.len(), path.display())),
+            Err(e) => self.append_log(&format!("[ERROR] {}\n", e)),
+        }
+    }
+
+    // starred.csv, index.html and the rows' bbox images under bbox/, written in the background
+    fn export_starred_zip(&mut self, ctx: &egui::Context) {
+        let Some(dest) = FileDialog::new().add_filter("zip", &["zip"]).set_file_name(self.starred_file_name("zip")).save_file() else { return; };
+        let rows = self.starred_rows();
+        let mut images: HashMap<String, String> = HashMap::new();
+        let mut entries = vec![];
+        for r in &rows {
+            let Some(p) = self.find_bbox_image_path(&r.image) else { continue; };
+            let Some(name) = p.file_name().map(|n| n.to_string_lossy().to_string()) else { continue; };
+            if !images.values().any(|n| *n == name) {
+                entries.push((bundle::ZipSource::File(p), format!("bbox/{}", name)));
+            }
+            images.insert(r.image.clone(), name);
+        }
+        if images.len() < rows.len() {
+            self.append_log(&format!("[INFO] {} starred rows have no bbox image; the page lists them without one\n", rows.len() - images.len()));
+        }
+        let title = match self.sidecar.label() {
+            Some(l) => format!("{} — starred", l),
+            None => "Starred results".to_string(),
+        };
+        let page = share::results_page(&rows, &title, |r| images.get(&r.image).map(|n| format!("bbox/{}", share::percent_encode(n))));
+        entries.push((bundle::ZipSource::Bytes(export::csv(&rows, &self.sidecar).into_bytes()), "starred.csv".to_string()));
+        entries.push((bundle::ZipSource::Bytes(page.into_bytes()), "index.html".to_string()));
+        self.append_log(&format!("[STEP] writing {} starred rows to {}...\n", rows.len(), dest.display()));
+        self.bundle_rx = Some((bundle::spawn_zip(entries, dest, ctx.clone()), false));
+    }
+
+    fn export_xlsx(&mut self) {
+        let default_name = self.last_json_path.as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| format!("{}.xlsx", s.to_string_lossy()))
+            .unwrap_or_else(|| "results.xlsx".to_string());
+        let Some(path) = FileDialog::new().add_filter("Excel", &["xlsx"]).set_file_name(default_name).save_file() else { return; };
+        match export::write_xlsx(&path, &self.results, &self.sidecar) {
+            Ok(truncated) => {
+                for image in truncated {
+                    self.append_log(&format!("[WARN] xlsx: reason of {} was longer than an Excel cell allows and was truncated\n", image));
+                }
+                self.append_log(&format!("[DONE] exported XLSX: {}\n", path.display()));
+            }
+            Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+        }
+    }
+
+    fn export_geojson(&mut self) {
+        let default_name = self.last_json_path.as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| format!("{}.geojson", s.to_string_lossy()))
+            .unwrap_or_else(|| "results.geojson".to_string());
+        let Some(path) = FileDialog::new().add_filter("GeoJSON", &["geojson", "json"]).set_file_name(default_name).save_file() else { return; };
+        match export::write_geojson(&path, &self.results, &self.sidecar) {
+            Ok((written, skipped)) => {
+                if skipped > 0 { self.append_log(&format!("[INFO] {} entries without GPS coordinates skipped\n", skipped)); }
+                self.append_log(&format!("[DONE] exported {} features to GeoJSON: {}\n", written, path.display()));
+            }
+            Err(e) => self.append_log(&format!("[ERROR] {}\n", e)),
+        }
+    }
+
+    fn run_script(&self, kind: ScriptKind) -> String {
+        let title = format!("replays the wheel_city_ai2 run started {}", self.command_log_started);
+        script::render(&self.command_log, kind, &title)
+    }
+
+    fn save_run_script(&mut self) {
+        let default_name = if ScriptKind::native() == ScriptKind::Bat { "rerun.bat" } else { "rerun.sh" };
+        let Some(path) = FileDialog::new()
+            .add_filter("shell script", &["sh"])
+            .add_filter("batch file", &["bat", "cmd"])
+            .set_file_name(default_name)
+            .save_file() else { return; };
+        let text = self.run_script(ScriptKind::for_path(&path));
+        match fs::write(&path, text) {
+            Ok(()) => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o755));
+                }
+                self.append_log(&format!("[DONE] saved run script: {}\n", path.display()));
+            }
+            Err(e) => self.append_log(&format!("[ERROR] failed to write {}: {}\n", path.display(), e)),
+        }
+    }
+
+    fn root_check(&mut self) -> &RootCheck {
+        if self.root_check.as_ref().is_none_or(|c| c.text != self.settings.project_root) {
+            let valid = looks_like_repo_root(&expand_path(&self.settings.project_root));
+            self.root_check = Some(RootCheck {
+                text: self.settings.project_root.clone(),
+                valid,
+                detected: if valid { None } else { find_project_root(&self.settings.project_root) },
+            });
+        }
+        self.root_check.as_ref().unwrap()
+    }
+
+    fn open_log_path(&mut self, action: PathAction, path: &Path) {
+        if !path.exists() {
+            self.show_toast(format!("No longer exists: {}", path.display()));
+            return;
+        }
+        let res = match action {
+            // directories open directly; files are selected in their folder
+            PathAction::Reveal if path.is_dir() => open_external(&path.to_string_lossy()),
+            PathAction::Reveal => reveal_in_file_manager(path),
+            PathAction::Open => open_external(&path.to_string_lossy()),
+        };
+        if let Err(e) = res {
+            self.show_toast(format!("{:#}", e));
+        }
+    }
+
+    fn show_toast(&mut self, msg: String) {
+        self.toast = Some((msg, std::time::Instant::now()));
+    }
+
+    fn draw_toast(&mut self, ctx: &egui::Context) {
+        let Some((msg, shown)) = &self.toast else { return; };
+        if shown.elapsed() > TOAST_DURATION {
+            self.toast = None;
+            return;
+        }
+        egui::Area::new(egui::Id::new("toast"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| { ui.label(msg.as_str()); });
+            });
+        ctx.request_repaint_after(TOAST_DURATION.saturating_sub(shown.elapsed()));
+    }
+
+    fn resolve_project_root(&mut self) -> Result<PathBuf> {
+        let root = preflight::project_root(&self.settings.project_root)?;
+        // a root written as `~/...` stays that way when that is where it was found
+        if expand_path(&self.settings.project_root) != root {
+            self.settings.project_root = root.to_string_lossy().to_string();
+        }
+        Ok(root)
+    }
+
+    // small thumb in table
+    // `size` is the box in points; each size has its own cache entry
+    fn show_bbox_thumb(&mut self, ui: &mut egui::Ui, filename: &str, ctx: &egui::Context, size: egui::Vec2) {
+        if let Some(p) = self.find_bbox_image_path(filename) {
+            let key = thumb_key(size, &p);
+            if !self.tex_cache.contains_key(&key) {
+                let prefetching = self.thumb_prefetch.as_ref().is_some_and(|pf| pf.is_pending(&key));
+                if prefetching || self.thumb_decodes >= THUMB_DECODES_PER_FRAME {
+                    ui.weak("…");
+                    ctx.request_repaint();
+                    return;
+                }
+                self.thumb_decodes += 1;
+                // sharp on high-DPI screens without holding the full image
+                let ppp = ctx.pixels_per_point();
+                let cache_dir = self.last_run_bbox_dir.as_deref().and_then(thumbs::cache_dir);
+                if let Some(t) = thumbs::load(&p, (size.x * ppp) as u32, (size.y * ppp) as u32, cache_dir.as_deref()) {
+                    self.thumb_stats.add(&t);
+                    if let Some(tex) = upload_rgba(ctx, &p, &t.image) {
+                        self.tex_cache.insert(key.clone(), tex);
+                    }
+                }
+            }
+            if let Some(tex) = self.tex_cache.get(&key) {
+                let sized = egui::load::SizedTexture::from_handle(tex);
+                egui::Image::new(sized).max_width(size.x).max_height(size.y).ui(ui);
+                return;
+            }
+        }
+        ui.label("—");
+    }
+
+    // queued inputs as captioned thumbnails; hovering a tile shows its path and a remove button
+    fn show_pending_grid(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        self.poll_pending_prefetch(ctx);
+        let mut missing: Vec<(String, PathBuf)> = vec![];
+        let mut remove_idx: Option<usize> = None;
+        egui::ScrollArea::vertical().id_source("pending_grid").max_height(260.0).show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for i in 0..self.pending_files.len() {
+                    let p = self.pending_files[i].clone();
+                    let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    let resp = ui.vertical(|ui| {
+                        ui.set_width(PENDING_TILE.x);
+                        ui.allocate_ui_with_layout(PENDING_TILE, egui::Layout::centered_and_justified(egui::Direction::TopDown), |ui| {
+                            self.pending_thumb(ui, &p, &mut missing);
+                        });
+                        ui.add(egui::Label::new(egui::RichText::new(&name).small()).truncate(true).wrap(false));
+                    }).response;
+                    let rect = resp.rect;
+                    resp.on_hover_text(p.display().to_string());
+                    if ui.rect_contains_pointer(rect) {
+                        let at = egui::Rect::from_min_size(rect.right_top() - egui::vec2(22.0, 0.0), egui::vec2(22.0, 20.0));
+                        if ui.put(at, egui::Button::new("✖").small()).on_hover_text(tr("Remove")).clicked() {
+                            remove_idx = Some(i);
+                        }
+                    }
+                }
+            });
+        });
+        if let Some(i) = remove_idx { self.pending_files.remove(i); }
+        // tiles added while a batch decodes wait for the next one
+        if !missing.is_empty() && self.pending_prefetch.as_ref().is_none_or(|pf| pf.is_finished()) {
+            let ppp = ctx.pixels_per_point();
+            self.pending_prefetch = Some(decode::Prefetch::start(missing, (PENDING_TILE.x * ppp) as u32, (PENDING_TILE.y * ppp) as u32, None, ctx));
+        }
+    }
+
+    // a placeholder until the tile's thumbnail is decoded; tiles not decoding yet go to `missing`
+    fn pending_thumb(&mut self, ui: &mut egui::Ui, path: &Path, missing: &mut Vec<(String, PathBuf)>) {
+        if video::is_video(path) || self.pending_thumb_failed.contains(path) {
+            ui.label(egui::RichText::new(if video::is_video(path) { "🎞" } else { "🖼" }).size(28.0).weak());
+            return;
+        }
+        let key = pending_thumb_key(path);
+        match self.tex_cache.get(&key) {
+            Some(tex) => {
+                let sized = egui::load::SizedTexture::from_handle(tex);
+                egui::Image::new(sized).max_width(PENDING_TILE.x).max_height(PENDING_TILE.y).ui(ui);
+            }
+            None => {
+                if !self.pending_prefetch.as_ref().is_some_and(|pf| pf.is_pending(&key)) {
+                    missing.push((key, path.to_path_buf()));
+                }
+                ui.weak("…");
+            }
+        }
+    }
+
+    // upload the queued tiles the background decode finished
+    fn poll_pending_prefetch(&mut self, ctx: &egui::Context) {
+        let Some(pf) = self.pending_prefetch.as_mut() else { return; };
+        for (key, img) in pf.poll() {
+            let Some(path) = key.strip_prefix("pending:").map(PathBuf::from) else { continue; };
+            match img {
+                Some(img) => { self.tex_cache.insert(key.clone(), ctx.load_texture(key, img, egui::TextureOptions::default())); }
+                None => { self.pending_thumb_failed.insert(path); }
+            }
+        }
+        if pf.is_finished() { self.pending_prefetch = None; }
+    }
+
+    // only the rows in view are laid out; `results` is moved out while drawing so rows can borrow
+    // their entry while the cells update the caches and selection
+    fn show_results_table(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, rows: &[(usize, usize, usize)]) {
+        let results = std::mem::take(&mut self.results);
+        let pointer = ctx.pointer_hover_pos();
+        let mut hovered_row = None;
+        let mut row_action = None;
+        let mut review_toggle = None;
+        let mut star_toggle = None;
+        let can_rerun = self.run_rx.is_none() && self.last_json_path.is_some()
+            && self.last_run_bbox_dir.as_ref().is_some_and(|d| d.is_dir());
+        egui::ScrollArea::vertical()
+            .id_source("results_scroll")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                let hidden = self.settings.hidden_columns.clone();
+                let on = |key: &str| !hidden.contains(key);
+                // a width the user dragged to wins over the auto size
+                let saved = &self.settings.column_widths;
+                let col = |key: &str, min: f32| match saved.get(key) {
+                    Some(&w) => Column::initial(w.max(min)).at_least(min),
+                    None => Column::auto().at_least(min),
+                };
+                // one narrow column per criterion any loaded result has; none for older result files
+                let criteria = if on("criteria") { export::criteria_keys(&results) } else { vec![] };
+                let crit_keys: Vec<String> = criteria.iter().map(|c| format!("criterion:{}", c)).collect();
+                // built from the columns left on in "Columns ▾"; the fixed-width ones have no saved width
+                let mut keys = vec!["thumb", "image"];
+                let mut table = TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(true)
+                    .column(col("thumb", 78.0))    // BBox thumb
+                    .column(col("image", 200.0));  // Image name
+                for k in ["reveal", "reviewed", "starred"] {
+                    if on(k) {
+                        keys.push("");
+                        table = table.column(Column::exact(28.0).resizable(false));
+                    }
+                }
+                keys.push("accessible");
+                table = table.column(col("accessible", 110.0));
+                for (k, min) in [("latlon", 150.0), ("size", 80.0), ("file", 64.0), ("taken", 130.0)] {
+                    if on(k) {
+                        keys.push(k);
+                        table = table.column(col(k, min));
+                    }
+                }
+                for k in &crit_keys {
+                    keys.push(k);
+                    table = table.column(col(k, 40.0));
+                }
+                // the reason column takes what is left, so it isn't saved
+                if on("reason") {
+                    keys.push("");
+                    table = table.column(Column::remainder());
+                }
+                if on("remove") {
+                    keys.push("");
+                    table = table.column(Column::exact(28.0).resizable(false));
+                }
+                let mut widths = vec![];
+                table
+                    .header(22.0, |mut header| {
+                        let mut cell = |add: &dyn Fn(&mut egui::Ui)| widths.push(header.col(|ui| add(ui)).0.width());
+                        cell(&|ui| { ui.strong(tr("BBox")); });
+                        cell(&|ui| { ui.strong(tr("Image")); });
+                        if on("reveal") { cell(&|_ui| {}); }
+                        if on("reviewed") { cell(&|ui| { ui.strong("✔").on_hover_text(tr("Reviewed")); }); }
+                        if on("starred") { cell(&|ui| { ui.strong("★").on_hover_text(tr("Starred")); }); }
+                        cell(&|ui| { ui.strong(tr("Accessible")); });
+                        for (k, title) in [("latlon", "Lat, Lon"), ("size", "Size"), ("file", "File"), ("taken", "Taken")] {
+                            if on(k) { cell(&|ui| { ui.strong(tr(title)); }); }
+                        }
+                        for c in &criteria {
+                            cell(&|ui| { ui.strong(c.as_str()).on_hover_text(format!("Criterion: {}", c)); });
+                        }
+                        if on("reason") { cell(&|ui| { ui.strong(tr("Reason")); }); }
+                        if on("remove") { cell(&|_ui| {}); }
+                    })
+                    // only the rows in view are laid out (and their thumbnails loaded)
+                    .body(|body| {
+                        body.rows(28.0, rows.len(), |mut row| {
+                            let (idx, nth, total) = rows[row.index()];
+                            let r = &results[idx];
+                            let is_selected = self.selected == Some(idx);
+                            // thumb
+                            let (thumb_rect, _) = row.col(|ui| { self.show_bbox_thumb(ui, &r.image, ctx, THUMB_SIZE); });
+                            // filename (click to select)
+                            let (name_rect, _) = row.col(|ui| {
+                                let name = r.label.as_deref().unwrap_or(&r.image);
+                                let label = match self.dup_mode {
+                                    DupMode::ShowAll if total > 1 => format!("{}  #{}", name, nth),
+                                    _ => name.to_string(),
+                                };
+                                let mut resp = ui.selectable_label(is_selected, label);
+                                if resp.clicked() {
+                                    self.selected = Some(idx);
+                                }
+                                if r.label.is_some() { resp = resp.on_hover_text(&r.image); }
+                                let original = sources::original(&self.sources, &r.image);
+                                let resp = match original {
+                                    Some(p) => resp.on_hover_ui(|ui| original_hover(ui, p)),
+                                    None => resp,
+                                };
+                                // acts on this row, selected or not
+                                resp.context_menu(|ui| {
+                                    if let Some(a) = row_menu(ui, can_rerun, original, self.settings.colorblind_palette) {
+                                        row_action = Some((idx, a));
+                                    }
+                                });
+                                if self.dup_mode == DupMode::NewestOnly && total > 1 {
+                                    ui.label(egui::RichText::new(format!("×{}", total)).weak())
+                                        .on_hover_text(format!("{} entries share this name; showing the newest", total));
+                                }
+                                if let Some(note) = self.sidecar.note(&r.image) {
+                                    ui.label(egui::RichText::new("●").color(Color32::from_rgb(230,180,0)))
+                                        .on_hover_text(note);
+                                }
+                                if let Some(truth) = self.disagrees_with_ground_truth(r) {
+                                    let (sym, text, _) = verdict_style(Some(truth), self.settings.colorblind_palette);
+                                    ui.label(egui::RichText::new("≠").strong().color(ERROR_COLOR))
+                                        .on_hover_text(format!("Ground truth: {} {}", sym, text));
+                                }
+                            });
+                            if pointer.is_some_and(|p| thumb_rect.contains(p) || name_rect.contains(p)) {
+                                hovered_row = Some(idx);
+                            }
+                            // reveal bbox file in the file manager
+                            if on("reveal") {
+                                row.col(|ui| {
+                                    match self.find_bbox_image_path(&r.image) {
+                                        Some(p) => {
+                                            if ui.small_button("📂").on_hover_text(format!("Reveal {}", p.display())).clicked() {
+                                                if let Err(e) = reveal_in_file_manager(&p) {
+                                                    self.append_log(&format!("[WARN] reveal failed: {} ({})\n", p.display(), e));
+                                                }
+                                            }
+                                        }
+                                        None => {
+                                            ui.add_enabled(false, egui::Button::new("📂").small())
+                                                .on_disabled_hover_text("No bbox image found for this entry in the last run dir.");
+                                        }
+                                    }
+                                });
+                            }
+                            // reviewed
+                            if on("reviewed") {
+                                row.col(|ui| {
+                                    let mut checked = self.sidecar.is_reviewed(&r.image);
+                                    if ui.checkbox(&mut checked, "").on_hover_text(tr("Reviewed")).changed() {
+                                        review_toggle = Some((r.image.clone(), checked));
+                                    }
+                                });
+                            }
+                            if on("starred") {
+                                row.col(|ui| {
+                                    let starred = self.sidecar.is_starred(&r.image);
+                                    let text = if starred { egui::RichText::new("★").color(Color32::GOLD) } else { egui::RichText::new("☆").weak() };
+                                    if ui.add(egui::Button::new(text).frame(false)).on_hover_text(tr("Star (S)")).clicked() {
+                                        star_toggle = Some((r.image.clone(), !starred));
+                                    }
+                                });
+                            }
+                            // accessible
+                            row.col(|ui| {
+                                if let Some(e) = &r.error {
+                                    error_badge(ui, e);
+                                    return;
+                                }
+                                let shown = shown_verdict(&r.result, self.min_confidence());
+                                verdict_label(ui, shown, self.settings.colorblind_palette);
+                                if shown != r.result.accessible {
+                                    let (sym, text, _) = verdict_style(r.result.accessible, self.settings.colorblind_palette);
+                                    ui.weak("*").on_hover_text(format!(
+                                        "Model said {} {} with confidence {:.2}",
+                                        sym, text, r.result.confidence.unwrap_or_default()));
+                                }
+                                if r.cached {
+                                    ui.weak("⟲").on_hover_text("From the Gemini cache (same image, prompt and model); not sent to Gemini again");
+                                }
+                            });
+                            // coordinates
+                            if on("latlon") {
+                                row.col(|ui| {
+                                    match (r.lat, r.lon) {
+                                        (Some(lat), Some(lon)) => { ui.label(format!("{:.6}, {:.6}", lat, lon)); }
+                                        _ => { ui.weak("—").on_hover_text("No GPS EXIF in the original photo"); }
+                                    }
+                                });
+                            }
+                            // original image info; blank for results from before it was recorded
+                            let meta = self.sidecar.meta(&r.image);
+                            if on("size") { row.col(|ui| { dimensions_label(ui, meta); }); }
+                            if on("file") {
+                                row.col(|ui| {
+                                    match meta.filter(|m| m.bytes > 0) {
+                                        Some(m) => { ui.label(preflight::fmt_bytes(m.bytes)); }
+                                        None => { ui.weak("—"); }
+                                    }
+                                });
+                            }
+                            if on("taken") {
+                                row.col(|ui| {
+                                    match meta.and_then(|m| m.taken_at.as_deref()) {
+                                        Some(t) => { ui.label(t); }
+                                        None => { ui.weak("—"); }
+                                    }
+                                });
+                            }
+                            for c in &criteria {
+                                row.col(|ui| { criterion_label(ui, r.result.criteria.get(c).copied().flatten(), self.settings.colorblind_palette); });
+                            }
+                            // reason (single line, ellipsized to avoid overlap; full text on hover)
+                            if on("reason") {
+                                row.col(|ui| {
+                                    reason_cell(ui, r.error.as_deref().unwrap_or(&r.result.reason));
+                                });
+                            }
+                            // remove entry (confirmed in a dialog)
+                            if on("remove") {
+                                row.col(|ui| {
+                                    if ui.small_button("🗑").on_hover_text("Remove this entry from the result JSON").clicked() {
+                                        self.pending_removal = Some(idx);
+                                    }
+                                });
+                            }
+                        });
+                    });
+                self.track_column_widths(ctx, &keys, &widths);
+            });
+        self.results = results;
+        self.show_hover_preview(ctx, hovered_row);
+        if let Some((idx, action)) = row_action {
+            if let Err(e) = self.apply_row_action(ctx, idx, action) {
+                self.append_log(&format!("[ERROR] {:#}\n", e));
+            }
+        }
+        if let Some((image, on)) = review_toggle {
+            self.set_reviewed(&image, on);
+        }
+        if let Some((image, on)) = star_toggle {
+            self.set_starred(&image, on);
+        }
+    }
+
+    fn set_starred(&mut self, image: &str, on: bool) {
+        if on { self.sidecar.starred.insert(image.to_string()); } else { self.sidecar.starred.remove(image); }
+        if let Some(json) = self.last_json_path.clone() {
+            if let Err(e) = self.sidecar.save(&json) {
+                self.append_log(&format!("[WARN] star not saved: {}\n", e));
+            }
+        }
+    }
+
+    fn toggle_star_selected(&mut self) {
+        let Some(image) = self.selected_entry().map(|w| w.image.clone()) else { return; };
+        let on = !self.sidecar.is_starred(&image);
+        self.set_starred(&image, on);
+    }
+
+    // kept in the sidecar, so the progress comes back with the result file
+    fn set_reviewed(&mut self, image: &str, on: bool) {
+        if on { self.sidecar.reviewed.insert(image.to_string()); } else { self.sidecar.reviewed.remove(image); }
+        if let Some(json) = self.last_json_path.clone() {
+            if let Err(e) = self.sidecar.save(&json) {
+                self.append_log(&format!("[WARN] review state not saved: {}\n", e));
+            }
+        }
+    }
+
+    // marks the selected row reviewed and selects the next unreviewed row the table shows,
+    // wrapping to the top; the selection stays put once everything is reviewed
+    fn review_and_advance(&mut self) {
+        let Some(cur) = self.selected.filter(|&i| i < self.results.len()) else { return; };
+        let rows = self.visible_rows();
+        let image = self.results[cur].image.clone();
+        self.set_reviewed(&image, true);
+        let after = rows.iter().position(|&i| i == cur).map_or(0, |p| p + 1);
+        let next = rows[after..].iter().chain(&rows[..after]).copied()
+            .find(|&i| !self.sidecar.is_reviewed(&self.results[i].image));
+        if next.is_some() { self.selected = next; }
+    }
+
+    fn apply_row_action(&mut self, ctx: &egui::Context, idx: usize, action: RowAction) -> Result<()> {
+        let w = self.results.get(idx).with_context(|| "that row is gone")?;
+        let image = w.image.clone();
+        match action {
+            RowAction::Open | RowAction::Reveal => {
+                let p = self.find_bbox_image_path(&image)
+                    .with_context(|| format!("no bbox image found for {} in the last run dir", image))?;
+                if action == RowAction::Open { open_external(&p.display().to_string())?; } else { reveal_in_file_manager(&p)?; }
+            }
+            RowAction::OpenOriginal | RowAction::RevealOriginal => {
+                let p = sources::original(&self.sources, &image)
+                    .with_context(|| format!("the original of {} was not recorded", image))?
+                    .to_path_buf();
+                if !p.exists() { anyhow::bail!("the original of {} is no longer at {}", image, p.display()); }
+                if action == RowAction::OpenOriginal { open_external(&p.display().to_string())?; } else { reveal_in_file_manager(&p)?; }
+            }
+            RowAction::CopyJson => {
+                let text = serde_json::to_string_pretty(w)?;
+                ctx.output_mut(|o| o.copied_text = text);
+                self.show_toast(format!("Copied JSON for {}", image));
+            }
+            RowAction::CopyReason => {
+                let text = w.error.clone().unwrap_or_else(|| w.result.reason.clone());
+                ctx.output_mut(|o| o.copied_text = text);
+                self.show_toast(format!("Copied the reason for {}", image));
+            }
+            RowAction::Rerun => self.rejudge_images(ctx, vec![image])?,
+            RowAction::SetVerdict(v) => self.set_verdict(idx, v)?,
+        }
+        Ok(())
+    }
+
+    // the selected row's label; typing edits a buffer, leaving the field saves it
+    fn show_label_edit(&mut self, ui: &mut egui::Ui) {
+        let Some(idx) = self.selected.filter(|&i| i < self.results.len()) else {
+            ui.label(tr("Select a row to give it a label."));
+            return;
+        };
+        let image = self.results[idx].image.clone();
+        // selecting another row while typing still saves what was typed
+        if self.label_edit.as_ref().is_none_or(|(i, img, _)| *i != idx || *img != image) {
+            self.commit_label_edit();
+            self.label_edit = Some((idx, image.clone(), self.results[idx].label.clone().unwrap_or_default()));
+        }
+        let Some((_, _, text)) = self.label_edit.as_mut() else { return; };
+        let resp = ui.add(egui::TextEdit::singleline(text).hint_text(tr("e.g. Main St ramp #3")).desired_width(320.0))
+            .on_hover_text(format!("{} {}", tr("Shown in the table instead of"), image));
+        if resp.lost_focus() { self.commit_label_edit(); }
+    }
+
+    fn commit_label_edit(&mut self) {
+        let Some((idx, image, text)) = self.label_edit.clone() else { return; };
+        let label = Some(text.trim().to_string()).filter(|t| !t.is_empty());
+        // the row may have moved (removed entries) or the results been replaced
+        let Some(w) = self.results.get(idx).filter(|w| w.image == image) else { return; };
+        if w.label == label { return; }
+        if let Err(e) = self.set_label(idx, label) {
+            self.append_log(&format!("[ERROR] {:#}\n", e));
+        }
+    }
+
+    // kept on the entry in the result JSON, like a manual verdict
+    fn set_label(&mut self, idx: usize, label: Option<String>) -> Result<()> {
+        let image = self.results[idx].image.clone();
+        if let Some(json) = &self.last_json_path {
+            edit_entry_in_json(json, idx, &image, |entry| {
+                match &label {
+                    Some(l) => { entry.insert("label".to_string(), l.as_str().into()); }
+                    None => { entry.remove("label"); }
+                }
+                Ok(())
+            })?;
+        }
+        match &label {
+            Some(l) => self.append_log(&format!("[INFO] labeled {} \"{}\"\n", image, l)),
+            None => self.append_log(&format!("[INFO] removed the label of {}\n", image)),
+        }
+        self.results[idx].label = label;
+        Ok(())
+    }
+
+    // a manual verdict replaces the model's; its confidence and any error go with it
+    fn set_verdict(&mut self, idx: usize, accessible: Option<bool>) -> Result<()> {
+        let image = self.results[idx].image.clone();
+        if let Some(json) = &self.last_json_path {
+            set_verdict_in_json(json, idx, &image, accessible)?;
+        }
+        let w = &mut self.results[idx];
+        w.result.accessible = accessible;
+        w.result.confidence = None;
+        w.error = None;
+        w.cached = false;
+        let (sym, text, _) = verdict_style(accessible, false);
+        self.append_log(&format!("[INFO] set {} to {} {}\n", image, sym, text));
+        Ok(())
+    }
+
+    // a header width that changes while the pointer is held is a column drag; the widths are
+    // saved once it is released (not on every frame of the drag)
+    fn track_column_widths(&mut self, ctx: &egui::Context, keys: &[&str], widths: &[f32]) {
+        let now: Vec<(String, f32)> = keys.iter().zip(widths)
+            .filter(|(k, _)| !k.is_empty())
+            .map(|(k, w)| (k.to_string(), w.round()))
+            .collect();
+        let down = ctx.input(|i| i.pointer.any_down());
+        let prev = std::mem::replace(&mut self.column_widths_seen, now.clone());
+        if down && !prev.is_empty() && prev != now && prev.iter().map(|p| &p.0).eq(now.iter().map(|n| &n.0)) {
+            self.column_resized = true;
+        }
+        if !down && std::mem::take(&mut self.column_resized) {
+            self.settings.column_widths.extend(now);
+        }
+    }
+
+    // results as a wrapping grid of thumbnails framed in the verdict color; only the lines
+    // scrolled into view are laid out, so thumbnails decode as they appear
+    fn show_results_grid(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, rows: &[(usize, usize, usize)]) {
+        const MARGIN: f32 = 4.0;
+        let spacing = ui.spacing().item_spacing;
+        let tile_w = GRID_TILE.x + 2.0 * MARGIN;
+        let cols = (((ui.available_width() + spacing.x) / (tile_w + spacing.x)).floor() as usize).max(1);
+        let line_h = GRID_TILE.y + 2.0 * MARGIN + spacing.y + ui.text_style_height(&egui::TextStyle::Body);
+        egui::ScrollArea::vertical()
+            .id_source("results_grid_scroll")
+            .auto_shrink([false; 2])
+            .show_rows(ui, line_h, rows.len().div_ceil(cols), |ui, lines| {
+                for line in lines {
+                    ui.horizontal_top(|ui| {
+                        ui.set_min_height(line_h);
+                        for &(idx, nth, total) in rows.iter().skip(line * cols).take(cols) {
+                            self.grid_tile(ui, ctx, idx, nth, total, MARGIN);
+                        }
+                    });
+                }
+            });
+    }
+
+    fn grid_tile(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, idx: usize, nth: usize, total: usize, margin: f32) {
+        let image = self.results[idx].image.clone();
+        let name = self.results[idx].label.clone().unwrap_or_else(|| image.clone());
+        let (_, _, color) = verdict_style(shown_verdict(&self.results[idx].result, self.min_confidence()), self.settings.colorblind_palette);
+        let color = if self.results[idx].error.is_some() { ERROR_COLOR } else { color };
+        let selected = self.selected == Some(idx);
+        let label = match self.dup_mode {
+            DupMode::ShowAll if total > 1 => format!("{}  #{}", name, nth),
+            _ => name,
+        };
+        let mut frame = egui::Frame::none()
+            .stroke(egui::Stroke::new(if selected { 3.0 } else { 1.5 }, color))
+            .rounding(3.0)
+            .inner_margin(margin - if selected { 1.5 } else { 0.0 });
+        if selected { frame = frame.fill(ui.visuals().selection.bg_fill.linear_multiply(0.4)); }
+        let resp = frame.show(ui, |ui| {
+            ui.set_width(GRID_TILE.x);
+            ui.vertical(|ui| {
+                ui.allocate_ui_with_layout(GRID_TILE, egui::Layout::centered_and_justified(egui::Direction::TopDown), |ui| {
+                    self.show_bbox_thumb(ui, &image, ctx, GRID_TILE);
+                });
+                ui.add(egui::Label::new(label).truncate(true).wrap(false));
+            });
+        }).response;
+        if resp.interact(egui::Sense::click()).on_hover_text(&image).clicked() {
+            self.selected = Some(idx);
+        }
+    }
+
+    // hover preview of a row's bbox image, after the pointer rests on its thumbnail or filename;
+    // leaving the row (or opening a menu) hides it at once
+    fn show_hover_preview(&mut self, ctx: &egui::Context, hovered_row: Option<usize>) {
+        let hovered_row = hovered_row.filter(|_| !ctx.memory(|m| m.any_popup_open()));
+        let Some(idx) = hovered_row else { self.hover = None; return; };
+        let since = match self.hover {
+            Some((i, t)) if i == idx => t,
+            _ => {
+                let now = std::time::Instant::now();
+                self.hover = Some((idx, now));
+                now
+            }
+        };
+        let rested = since.elapsed();
+        if rested < HOVER_DELAY {
+            ctx.request_repaint_after(HOVER_DELAY - rested);
+            return;
+        }
+        let Some(p) = self.find_bbox_image_path(&self.results[idx].image) else { return; };
+        let key = format!("hover:{}", p.display());
+        let tex = self.tex_cache.get(&key).cloned();
+        if tex.is_none() && self.decoder.has_failed(&key) { return; }
+        if tex.is_none() && !self.decoder.is_pending(&key) {
+            self.decoder.request(&key, p, (HOVER_SIZE * ctx.pixels_per_point()) as u32, ctx);
+        }
+        let Some(pos) = ctx.pointer_hover_pos() else { return; };
+        egui::Area::new(egui::Id::new("hover_preview"))
+            .order(egui::Order::Tooltip)
+            .interactable(false)
+            .fixed_pos(pos + egui::vec2(16.0, 16.0))
+            .constrain(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| match &tex {
+                    Some(tex) => {
+                        let sized = egui::load::SizedTexture::from_handle(tex);
+                        egui::Image::new(sized).max_width(HOVER_SIZE).max_height(HOVER_SIZE).ui(ui);
+                    }
+                    None => { ui.spinner(); }
+                });
+            });
+    }
+
+    // upload previews the background decoder finished
+    fn poll_decoded(&mut self, ctx: &egui::Context) {
+        for (key, img) in self.decoder.poll() {
+            if let Some(img) = img {
+                let tex = ctx.load_texture(key.clone(), img, egui::TextureOptions::LINEAR);
+                self.tex_cache.insert(key, tex);
+            }
+        }
+    }
+
+    // decode every thumbnail of the loaded result in the background, in the view's tile size
+    fn start_thumb_prefetch(&mut self, ctx: &egui::Context) {
+        self.prefetch_due = false;
+        if self.results.len() > THUMB_PREFETCH_MAX {
+            self.append_log(&format!("[INFO] {} results: thumbnails load as the table scrolls (prefetch is limited to {})\n",
+                self.results.len(), THUMB_PREFETCH_MAX));
+            return;
+        }
+        let size = if self.settings.results_grid { GRID_TILE } else { THUMB_SIZE };
+        let mut seen = HashSet::new();
+        let jobs: Vec<(String, PathBuf)> = self.results.iter()
+            .filter_map(|w| self.find_bbox_image_path(&w.image))
+            .map(|p| (thumb_key(size, &p), p))
+            .filter(|(key, _)| !self.tex_cache.contains_key(key) && seen.insert(key.clone()))
+            .collect();
+        if jobs.is_empty() { return; }
+        let ppp = ctx.pixels_per_point();
+        let cache_dir = self.last_run_bbox_dir.as_deref().and_then(thumbs::cache_dir);
+        self.thumb_prefetch = Some(decode::Prefetch::start(jobs, (size.x * ppp) as u32, (size.y * ppp) as u32, cache_dir, ctx));
+    }
+
+    // upload the thumbnails the prefetch finished
+    fn poll_prefetch(&mut self, ctx: &egui::Context) {
+        if self.prefetch_due { self.start_thumb_prefetch(ctx); }
+        let Some(pf) = self.thumb_prefetch.as_mut() else { return; };
+        for (key, img) in pf.poll() {
+            if let (Some(img), false) = (img, self.tex_cache.contains_key(&key)) {
+                let tex = ctx.load_texture(key.clone(), img, egui::TextureOptions::default());
+                self.tex_cache.insert(key, tex);
+            }
+        }
+        if pf.is_finished() {
+            let (n, took) = pf.summary();
+            self.thumb_prefetch = None;
+            self.append_log(&format!("[INFO] prefetched {} thumbnails in {:.1}s\n", n, took.as_secs_f32()));
+        }
+    }
+
+    // the ground truth label when it differs from the verdict the table shows
+    fn disagrees_with_ground_truth(&self, w: &WheelOne) -> Option<bool> {
+        let truth = self.ground_truth.as_ref()?.lookup(&w.image)?;
+        (shown_verdict(&w.result, self.min_confidence()) != Some(truth)).then_some(truth)
+    }
+
+    fn load_ground_truth(&mut self) {
+        let Some(path) = FileDialog::new().add_filter("csv", &["csv"]).pick_file() else { return; };
+        match groundtruth::GroundTruth::load(&path) {
+            Ok(gt) => {
+                self.append_log(&format!("[INFO] loaded {} ground truth labels from {} ({} unlabeled rows skipped)\n",
+                    gt.labels.len(), path.display(), gt.unlabeled));
+                self.ground_truth = Some(gt);
+            }
+            Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+        }
+    }
+
+    fn evaluation(&self) -> Option<groundtruth::Evaluation> {
+        let gt = self.ground_truth.as_ref()?;
+        let preds: Vec<(String, Option<bool>)> = self.results.iter()
+            .map(|w| (w.image.clone(), shown_verdict(&w.result, self.min_confidence())))
+            .collect();
+        Some(groundtruth::evaluate(gt, &preds))
+    }
+
+    fn show_ground_truth_panel(&mut self, ui: &mut egui::Ui) {
+        let Some(eval) = self.evaluation() else { return; };
+        let m = eval.matrix;
+        let pct = |v: Option<f64>| v.map(|v| format!("{:.1}%", v * 100.0)).unwrap_or_else(|| "—".to_string());
+        if let Some(gt) = &self.ground_truth { ui.weak(gt.path.display().to_string()); }
+        ui.horizontal(|ui| {
+            ui.label(format!("{} {}", tr("scored"), m.total()));
+            ui.separator();
+            ui.label(format!("{} {}", tr("accuracy"), pct(m.accuracy())));
+            ui.label(format!("{} {}", tr("precision"), pct(m.precision())));
+            ui.label(format!("{} {}", tr("recall"), pct(m.recall())));
+        }).response.on_hover_text("\"accessible\" is the positive class; undetermined verdicts are not scored");
+        egui::Grid::new("gt_confusion").striped(true).show(ui, |ui| {
+            ui.label("");
+            ui.strong(tr("Truth: accessible"));
+            ui.strong(tr("Truth: not accessible"));
+            ui.end_row();
+            ui.strong(tr("Model: accessible"));
+            ui.label(m.tp.to_string());
+            ui.label(m.fp.to_string());
+            ui.end_row();
+            ui.strong(tr("Model: not accessible"));
+            ui.label(m.fn_.to_string());
+            ui.label(m.tn.to_string());
+            ui.end_row();
+        });
+        if eval.undetermined > 0 {
+            ui.label(format!("{} {}", eval.undetermined, tr("labeled images were left undetermined by the model")));
+        }
+        for (title, list) in [("Labels without a result", &eval.missing_results), ("Results without a label", &eval.missing_labels)] {
+            if list.is_empty() { continue; }
+            egui::CollapsingHeader::new(format!("{} ({})", tr(title), list.len())).id_source(title).show(ui, |ui| {
+                egui::ScrollArea::vertical().id_source(title).max_height(120.0).show(ui, |ui| {
+                    for image in list { ui.label(image); }
+                });
+            });
+        }
+        ui.horizontal(|ui| {
+            let disagree = eval.rows.iter().filter(|r| !r.agrees()).count();
+            ui.checkbox(&mut self.gt_disagreements_only, format!("{} ({})", tr("Only disagreements"), disagree));
+            if ui.button(tr("Export evaluation...")).clicked() {
+                self.export_evaluation(&eval);
+            }
+            if ui.button(tr("Unload")).clicked() {
+                self.ground_truth = None;
+                self.gt_disagreements_only = false;
+            }
+        });
+    }
+
+    fn export_evaluation(&mut self, eval: &groundtruth::Evaluation) {
+        let Some(gt) = &self.ground_truth else { return; };
+        let default_name = self.last_json_path.as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| format!("{}.eval.csv", s.to_string_lossy()))
+            .unwrap_or_else(|| "evaluation.csv".to_string());
+        let Some(path) = FileDialog::new().add_filter("csv", &["csv"]).set_file_name(default_name).save_file() else { return; };
+        match groundtruth::write_csv(&path, eval, gt) {
+            Ok(()) => self.append_log(&format!("[DONE] exported evaluation: {}\n", path.display())),
+            Err(e) => self.append_log(&format!("[ERROR] {:#}\n", e)),
+        }
+    }
+
+    fn min_confidence(&self) -> Option<f64> {
+        self.settings.confidence_rule.then_some(self.settings.min_confidence)
+    }
+
+    // label files of the current bbox dir when the run saved them, else the classes sidecar
+    fn load_detections(&mut self) {
+        let Some(dir) = self.last_run_bbox_dir.clone() else {
+            self.labels = None;
+            self.classes.clear();
+            return;
+        };
+        let root = preflight::project_root(&self.settings.project_root).ok();
+        self.labels = labels::Labels::load(&dir, labels::class_names(root.as_deref(), &dir));
+        self.classes = match &self.labels {
+            Some(l) => l.images_by_class(),
+            None => classes::load(&dir),
+        };
+        self.hidden_classes.retain(|c| self.classes.contains_key(c));
+    }
+
+    fn selected_entry(&self) -> Option<&WheelOne> {
+        self.results.get(self.selected?)
+    }
+
+    // indices into `results` that the table shows: duplicate mode and verdict filter, then the class chips
+    // (with any class off, a row needs at least one detected class that is still on)
+    fn visible_rows(&self) -> Vec<usize> {
+        let rows = self.filtered_rows();
+        if self.hidden_classes.is_empty() { return rows; }
+        let shown: Vec<&[String]> = self.classes.iter()
+            .filter(|(c, _)| !self.hidden_classes.contains(*c))
+            .map(|(_, imgs)| imgs.as_slice())
+            .collect();
+        rows.into_iter()
+            .filter(|&i| shown.iter().any(|imgs| classes::contains(imgs, &self.results[i].image)))
+            .collect()
+    }
+
+    // (index, occurrence #, total with this name) of the rows the table/grid shows; the
+    // duplicate counts are only recounted when `results` changed, not every frame
+    fn table_rows(&mut self) -> Vec<(usize, usize, usize)> {
+        if self.duplicates.len() != self.results.len() {
+            self.duplicates = duplicate_info(&self.results);
+        }
+        self.visible_rows().into_iter()
+            .map(|i| (i, self.duplicates[i].0, self.duplicates[i].1))
+            .collect()
+    }
+
+    // rows after the duplicate mode, the verdict and criterion filters and the reviewed, starred and ground truth toggles
+    fn filtered_rows(&self) -> Vec<usize> {
+        let rows: Vec<usize> = match self.dup_mode {
+            DupMode::ShowAll => (0..self.results.len()).collect(),
+            DupMode::NewestOnly => {
+                let mut last: HashMap<&str, usize> = HashMap::new();
+                for (i, w) in self.results.iter().enumerate() { last.insert(&w.image, i); }
+                let mut v: Vec<usize> = last.into_values().collect();
+                v.sort_unstable();
+                v
+            }
+        };
+        let crit = &self.result_criterion;
+        rows.into_iter()
+            .filter(|&i| self.result_verdict.accepts(shown_verdict(&self.results[i].result, self.min_confidence())))
+            .filter(|&i| crit.is_empty()
+                || self.result_criterion_verdict.accepts(self.results[i].result.criteria.get(crit).copied().flatten()))
+            .filter(|&i| !self.hide_reviewed || !self.sidecar.is_reviewed(&self.results[i].image))
+            .filter(|&i| !self.starred_only || self.sidecar.is_starred(&self.results[i].image))
+            .filter(|&i| !self.gt_disagreements_only || self.disagrees_with_ground_truth(&self.results[i]).is_some())
+            .collect()
+    }
+
+    // same stem matching as `find_image_in_dir`, so re-encoded bbox images (x.webp → x.jpg) count
+    fn check_result_integrity(&self) -> IntegrityReport {
+        let bbox_dir = self.last_run_bbox_dir.clone().filter(|d| d.is_dir());
+        let mut files: Vec<String> = bbox_dir.as_ref()
+            .and_then(|d| fs::read_dir(d).ok())
+            .into_iter().flatten().flatten()
+            .filter(|e| e.path().is_file())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        let stem = |n: &str| Path::new(n).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let mut used = vec![false; files.len()];
+        let mut missing = vec![];
+        for w in &self.results {
+            let hit = files.iter().position(|f| *f == w.image)
+                .or_else(|| (0..files.len()).find(|&i| !used[i] && stem(&files[i]) == stem(&w.image)));
+            match hit {
+                Some(i) => used[i] = true,
+                None => missing.push(w.image.clone()),
+            }
+        }
+        let orphans = files.iter().zip(&used).filter(|(_, u)| !**u).map(|(f, _)| f.clone()).collect();
+        IntegrityReport { bbox_dir, results: self.results.len(), bbox_images: files.len(), missing, orphans }
+    }
+
+    fn log_integrity(&mut self, r: &IntegrityReport) {
+        let mut lines = match &r.bbox_dir {
+            Some(d) => format!("[INFO] integrity: {} results, {} bbox images in {}\n", r.results, r.bbox_images, d.display()),
+            None => "[WARN] integrity: no bbox dir for the loaded results\n".to_string(),
+        };
+        for m in &r.missing { lines.push_str(&format!("[WARN] no bbox image for result: {}\n", m)); }
+        for o in &r.orphans { lines.push_str(&format!("[WARN] bbox image without a result: {}\n", o)); }
+        if r.missing.is_empty() && r.orphans.is_empty() {
+            lines.push_str("[DONE] integrity: results and bbox images match\n");
+        } else {
+            lines.push_str(&format!("[WARN] integrity: {} missing bbox images, {} orphan bbox images\n", r.missing.len(), r.orphans.len()));
+        }
+        self.append_log(&lines);
+    }
+
+    // resolve selected image full path (robust to extension mismatches)
+    fn find_bbox_image_for_selected(&self) -> Option<PathBuf> {
+        self.find_bbox_image_path(&self.selected_entry()?.image)
+    }
+
+    fn find_bbox_image_path(&self, filename: &str) -> Option<PathBuf> {
+        find_image_in_dir(self.last_run_bbox_dir.as_ref()?, filename)
+    }
+}
+
+// the interrupted run under `project_root`, with how many images already have a verdict
+fn resume_offer_in(project_root: &Path) -> Option<(Checkpoint, usize)> {
+    let cp = Checkpoint::load(&project_root.join(".runner_work"))?;
+    let judged = cp.judged().len();
+    Some((cp, judged))
+}
+
+// extensions offered in the image picker and picked up from folders
+// (HEIC/HEIF are converted to PNG when copied, see `convert`)
+const IMAGE_EXTS: &[&str] = &["jpg", "jpeg", "png", "webp", "bmp", "tif", "tiff", "heic", "heif"];
+
+// image files directly inside `dir`, sorted by name
+fn images_in_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut v: Vec<PathBuf> = fs::read_dir(dir).into_iter().flatten().flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().and_then(|e| e.to_str())
+            .is_some_and(|e| IMAGE_EXTS.iter().any(|x| x.eq_ignore_ascii_case(e))))
+        .collect();
+    v.sort();
+    v
+}
+
+fn find_image_in_dir(dir: &Path, filename: &str) -> Option<PathBuf> {
+    let direct = dir.join(filename);
+    if direct.exists() { return Some(direct); }
+    // fallback: search by stem across extensions
+    let stem = Path::new(filename).file_stem()?.to_string_lossy().to_string();
+    for e in IMAGE_EXTS {
+        let cand = dir.join(format!("{}.{}", stem, e));
+        if cand.exists() { return Some(cand); }
+    }
+    // as a last resort, scan all files in dir and match stem
+    if let Ok(rd) = fs::read_dir(dir) {
+        for ent in rd.flatten() {
+            let p = ent.path();
+            if p.is_file() {
+                if let Some(s) = p.file_stem().map(|s| s.to_string_lossy().to_string()) {
+                    if s == stem { return Some(p); }
+                }
+            }
+        }
+    }
+    None
+}
+
+// symbol + text + color for a verdict, so it never relies on color alone
+fn verdict_style(accessible: Option<bool>, colorblind: bool) -> (&'static str, &'static str, Color32) {
+    match (accessible, colorblind) {
+        (Some(true), false)  => ("✔", "true", Color32::from_rgb(0,160,0)),
+        (Some(false), false) => ("✖", "false", Color32::from_rgb(200,0,0)),
+        // Okabe-Ito blue / orange
+        (Some(true), true)   => ("✔", "true", Color32::from_rgb(0,114,178)),
+        (Some(false), true)  => ("✖", "false", Color32::from_rgb(230,159,0)),
+        (None, _)            => ("?", "null", Color32::GRAY),
+    }
+}
+
+// `accessible: null` is Gemini declining to decide, not a failed run
+const NULL_VERDICT_HINT: &str = "null: the model could not determine accessibility or returned no judgment for this image.\nThis is not an error; review the image manually.";
+
+// long reasons wrap in the tooltip instead of spanning the screen
+const REASON_TOOLTIP_WIDTH: f32 = 420.0;
+
+// reason cell: one ellipsized line, the whole reason on hover. Painted by hand because a truncated
+// Label adds its own unwrapped tooltip
+fn reason_cell(ui: &mut egui::Ui, reason: &str) {
+    let color = ui.visuals().text_color();
+    let mut job = egui::text::LayoutJob::single_section(
+        reason.to_string(),
+        egui::TextFormat { font_id: egui::TextStyle::Body.resolve(ui.style()), color, ..Default::default() },
+    );
+    job.wrap = egui::text::TextWrapping::truncate_at_width(ui.available_width());
+    let galley = ui.fonts(|f| f.layout_job(job));
+    let (rect, resp) = ui.allocate_exact_size(galley.size(), egui::Sense::hover());
+    ui.painter().galley(rect.min, galley, color);
+    if !reason.trim().is_empty() {
+        resp.on_hover_ui(|ui| {
+            ui.set_max_width(REASON_TOOLTIP_WIDTH);
+            ui.label(reason);
+        });
+    }
+}
+
+const ERROR_COLOR: Color32 = Color32::from_rgb(240, 130, 0);
+
+// verdict cell of an entry the script failed on
+fn error_badge(ui: &mut egui::Ui, error: &str) {
+    ui.colored_label(ERROR_COLOR, "⚠ error").on_hover_text(error);
+}
+
+// verdict cell; null gets an explanation on hover
+fn verdict_label(ui: &mut egui::Ui, accessible: Option<bool>, colorblind: bool) {
+    let (sym, text, color) = verdict_style(accessible, colorblind);
+    let r = ui.colored_label(color, format!("{} {}", sym, text));
+    if accessible.is_none() { r.on_hover_text(NULL_VERDICT_HINT); }
+}
+
+// results table columns "Columns ▾" can turn off: (key, title); BBox, Image and Accessible always show
+const OPTIONAL_COLUMNS: [(&str, &str); 10] = [
+    ("reveal", "Reveal"),
+    ("reviewed", "Reviewed"),
+    ("starred", "Starred"),
+    ("latlon", "Lat, Lon"),
+    ("size", "Size"),
+    ("file", "File"),
+    ("taken", "Taken"),
+    ("criteria", "Criteria"),
+    ("reason", "Reason"),
+    ("remove", "Remove"),
+];
+
+fn columns_menu(ui: &mut egui::Ui, hidden: &mut std::collections::BTreeSet<String>) {
+    for title in ["BBox", "Image", "Accessible"] {
+        ui.add_enabled(false, egui::Checkbox::new(&mut true, tr(title)));
+    }
+    for (key, title) in OPTIONAL_COLUMNS {
+        let mut shown = !hidden.contains(key);
+        if ui.checkbox(&mut shown, tr(title)).changed() {
+            if shown { hidden.remove(key); } else { hidden.insert(key.to_string()); }
+        }
+    }
+}
+
+// what a results row's right-click menu asked for; applied once the table is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RowAction {
+    Open,
+    Reveal,
+    OpenOriginal,
+    RevealOriginal,
+    CopyJson,
+    CopyReason,
+    Rerun,
+    SetVerdict(Option<bool>),
+}
+
+fn row_menu(ui: &mut egui::Ui, can_rerun: bool, original: Option<&Path>, colorblind: bool) -> Option<RowAction> {
+    let mut picked = None;
+    let mut item = |ui: &mut egui::Ui, enabled: bool, text: &str, action: RowAction| {
+        if ui.add_enabled(enabled, egui::Button::new(tr(text))).clicked() {
+            picked = Some(action);
+            ui.close_menu();
+        }
+    };
+    item(ui, true, "Open", RowAction::Open);
+    item(ui, true, "Show in folder", RowAction::Reveal);
+    // greyed out when the photo was moved or deleted since the run
+    let found = original.is_some_and(|p| p.exists());
+    item(ui, found, "Open original", RowAction::OpenOriginal);
+    item(ui, found, "Reveal original", RowAction::RevealOriginal);
+    ui.separator();
+    item(ui, true, "Copy JSON", RowAction::CopyJson);
+    item(ui, true, "Copy reason", RowAction::CopyReason);
+    ui.separator();
+    item(ui, can_rerun, "Re-run this image", RowAction::Rerun);
+    ui.menu_button(tr("Set verdict"), |ui| {
+        for v in [Some(true), Some(false), None] {
+            let (sym, text, color) = verdict_style(v, colorblind);
+            if ui.button(egui::RichText::new(format!("{} {}", sym, text)).color(color)).clicked() {
+                picked = Some(RowAction::SetVerdict(v));
+                ui.close_menu();
+            }
+        }
+    });
+    picked
+}
+
+// the photo a result row was copied from; checked for existence only while hovered
+fn original_hover(ui: &mut egui::Ui, path: &Path) {
+    ui.label(format!("{} {}", tr("Original:"), path.display()));
+    if !path.exists() { ui.colored_label(ERROR_COLOR, tr("moved or deleted since the run")); }
+}
+
+// compact ✓/✗/— for a criteria column; a criterion the model left out shows as —
+fn criterion_label(ui: &mut egui::Ui, value: Option<bool>, colorblind: bool) {
+    let (_, text, color) = verdict_style(value, colorblind);
+    let sym = match value {
+        Some(true) => "✓",
+        Some(false) => "✗",
+        None => "—",
+    };
+    ui.colored_label(color, sym).on_hover_text(text);
+}
+
+fn verdict_filter_combo(ui: &mut egui::Ui, id: &str, value: &mut VerdictFilter) {
+    egui::ComboBox::from_id_source(id)
+        .selected_text(value.label())
+        .show_ui(ui, |ui| {
+            for f in VerdictFilter::ALL {
+                ui.selectable_value(value, f, f.label());
+            }
+        });
+}
+
+// like `serde_json::from_str::<WheelResultFile>`, but says which keys were there when `results` is missing
+fn parse_result_file(data: &str) -> Result<WheelResultFile> {
+    let root: serde_json::Value = serde_json::from_str(data).with_context(|| "invalid JSON")?;
+    match root.as_object() {
+        Some(obj) if !obj.contains_key("results") => {
+            let keys: Vec<&str> = obj.keys().map(|k| k.as_str()).collect();
+            anyhow::bail!("no `results` key (found: {})", if keys.is_empty() { "none".to_string() } else { keys.join(", ") });
+        }
+        None => anyhow::bail!("top level is not an object"),
+        _ => {}
+    }
+    serde_json::from_value(root).with_context(|| "does not match the result schema")
+}
+
+// boxes and class labels over an image shown at `rect`
+fn paint_detections(painter: &egui::Painter, rect: egui::Rect, dets: &[labels::Detection], hidden: &HashSet<String>) {
+    for d in dets.iter().filter(|d| !hidden.contains(&d.class)) {
+        let [x0, y0, x1, y1] = d.pixel_rect(rect.width(), rect.height());
+        let r = egui::Rect::from_min_max(rect.min + egui::vec2(x0, y0), rect.min + egui::vec2(x1, y1));
+        let color = class_color(&d.class);
+        painter.rect_stroke(r, 0.0, egui::Stroke::new(2.0, color));
+        let text = match d.conf {
+            Some(c) => format!("{} {:.2}", d.class, c),
+            None => d.class.clone(),
+        };
+        let galley = painter.layout_no_wrap(text, egui::FontId::proportional(12.0), Color32::BLACK);
+        let label = egui::Rect::from_min_size(r.min, galley.size() + egui::vec2(6.0, 2.0));
+        painter.rect_filled(label, 0.0, color);
+        painter.galley(label.min + egui::vec2(3.0, 1.0), galley, Color32::BLACK);
+    }
+}
+
+// a stable, distinct color per class name
+fn class_color(class: &str) -> Color32 {
+    let h = class.bytes().fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(u32::from(b)));
+    egui::ecolor::Hsva::new((h % 360) as f32 / 360.0, 0.85, 0.95, 1.0).into()
+}
+
+// below this many pixels on the short side an input is flagged as a likely thumbnail
+const LOW_RES_PX: u32 = 480;
+
+fn dimensions_label(ui: &mut egui::Ui, meta: Option<&exif_info::ImageMeta>) {
+    match meta.and_then(|m| m.width.zip(m.height)) {
+        Some((w, h)) if w.min(h) < LOW_RES_PX => {
+            ui.colored_label(Color32::from_rgb(230, 140, 0), format!("{}×{}", w, h))
+                .on_hover_text("Low resolution input; the verdict may be unreliable");
+        }
+        Some((w, h)) => { ui.label(format!("{}×{}", w, h)); }
+        None => { ui.weak("—"); }
+    }
+}
+
+// fills lat/lon from the sidecar; coordinates already in the result JSON are kept otherwise
+fn apply_gps(results: &mut [WheelOne], sidecar: &Sidecar) {
+    for r in results {
+        if let Some((lat, lon)) = sidecar.gps(&r.image) {
+            r.lat = Some(lat);
+            r.lon = Some(lon);
+        }
+    }
+}
+
+// the verdict shown for `judge`: with a minimum confidence, a verdict scored below it is treated
+// as undetermined; entries without a score keep the model's verdict. The stored verdict never changes.
+fn shown_verdict(judge: &WheelJudge, min_confidence: Option<f64>) -> Option<bool> {
+    match (min_confidence, judge.confidence) {
+        (Some(min), Some(c)) if c < min => None,
+        _ => judge.accessible,
+    }
+}
+
+fn verdict_counts(results: &[WheelOne], min_confidence: Option<f64>) -> (usize, usize, usize) {
+    results.iter().fold((0, 0, 0), |(y, n, u), w| match shown_verdict(&w.result, min_confidence) {
+        Some(true)  => (y + 1, n, u),
+        Some(false) => (y, n + 1, u),
+        None        => (y, n, u + 1),
+    })
+}
+
+// drop one entry by position, editing the raw JSON tree so unknown fields and key order survive
+fn remove_entry_from_json(json: &Path, idx: usize, expected_image: &str) -> Result<()> {
+    let data = fs::read_to_string(json).with_context(|| "failed to read result json")?;
+    let mut root: serde_json::Value = serde_json::from_str(&data).with_context(|| "failed to parse result json")?;
+    let arr = root.get_mut("results")
+        .and_then(|v| v.as_array_mut())
+        .with_context(|| "result json has no `results` array")?;
+    // guard against the file having changed since it was loaded
+    let at = arr.get(idx).and_then(|e| e.get("image")).and_then(|v| v.as_str());
+    if at != Some(expected_image) {
+        anyhow::bail!("{} changed on disk (entry {} is not {}); reload it first", json.display(), idx, expected_image);
+    }
+    arr.remove(idx);
+    write_atomic(json, serde_json::to_string_pretty(&root)?.as_bytes())?;
+    Ok(())
+}
+
+fn set_verdict_in_json(json: &Path, idx: usize, expected_image: &str, accessible: Option<bool>) -> Result<()> {
+    edit_entry_in_json(json, idx, expected_image, |entry| {
+        entry.remove("error");
+        entry.remove("cached");
+        let result = entry.entry("result").or_insert_with(|| serde_json::json!({ "reason": "" }));
+        let result = result.as_object_mut().with_context(|| format!("entry {} has a malformed result", idx))?;
+        result.insert("accessible".to_string(), accessible.into());
+        result.remove("confidence");
+        Ok(())
+    })
+}
+
+// change one entry in place, keeping unknown fields and key order
+fn edit_entry_in_json(json: &Path, idx: usize, expected_image: &str,
+    edit: impl FnOnce(&mut serde_json::Map<String, serde_json::Value>) -> Result<()>) -> Result<()>
+{
+    let data = fs::read_to_string(json).with_context(|| "failed to read result json")?;
+    let mut root: serde_json::Value = serde_json::from_str(&data).with_context(|| "failed to parse result json")?;
+    let entry = root.get_mut("results")
+        .and_then(|v| v.as_array_mut())
+        .and_then(|arr| arr.get_mut(idx))
+        .and_then(|e| e.as_object_mut())
+        .with_context(|| format!("result json has no entry {}", idx))?;
+    if entry.get("image").and_then(|v| v.as_str()) != Some(expected_image) {
+        anyhow::bail!("{} changed on disk (entry {} is not {}); reload it first", json.display(), idx, expected_image);
+    }
+    edit(entry)?;
+    write_atomic(json, serde_json::to_string_pretty(&root)?.as_bytes())?;
+    Ok(())
+}
+
+// per-entry occurrence number (1-based) and total count of its image name
+fn duplicate_info(results: &[WheelOne]) -> Vec<(usize, usize)> {
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    let occurrence: Vec<usize> = results.iter().map(|w| {
+        let n = totals.entry(w.image.as_str()).or_insert(0);
+        *n += 1;
+        *n
+    }).collect();
+    results.iter().zip(occurrence).map(|(w, n)| (n, totals[w.image.as_str()])).collect()
+}
+
+// write to a temp file in the same dir, then rename over the target
+fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, data).with_context(|| format!("failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+// run time for a result file: the timestamp in result_<ts>.json, else its mtime
+fn run_created_at(json: &Path) -> String {
+    let from_name = json.file_stem()
+        .and_then(|s| s.to_string_lossy().strip_prefix("result_").map(|t| t.to_string()))
+        .and_then(|ts| chrono::NaiveDateTime::parse_from_str(&ts, "%Y%m%d_%H%M%S").ok());
+    match from_name {
+        Some(t) => t.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => fs::metadata(json).and_then(|m| m.modified())
+            .map(|t| chrono::DateTime::<Local>::from(t).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default(),
+    }
+}
+
+// results/result_<ts>.json → .runner_work/bbox/<ts>
+fn guess_bbox_dir(json: &Path) -> Option<PathBuf> {
+    let stem = json.file_stem()?.to_string_lossy().to_string();
+    let ts = stem.strip_prefix("result_")?;
+    let root = json.parent()?.parent()?;
+    let dir = root.join(".runner_work").join("bbox").join(ts);
+    dir.is_dir().then_some(dir)
+}
+
+// `~`, `$HOME` / `${HOME}` and `%USERPROFILE%` expanded, so a settings file that names paths
+// that way works for another user or machine; unknown variables are left as written
+fn expand_path(s: &str) -> PathBuf {
+    PathBuf::from(expand_vars(s, |name| env::var(name).ok()))
+}
+
+fn expand_vars(s: &str, var: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    if let Some(after) = rest.strip_prefix('~').filter(|a| a.is_empty() || a.starts_with(['/', '\\'])) {
+        if let Some(home) = var("HOME").or_else(|| var("USERPROFILE")) {
+            out.push_str(&home);
+            rest = after;
+        }
+    }
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    while let Some(i) = rest.find(['$', '%']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        // (variable name, length of the whole reference)
+        let reference = if let Some(braced) = tail.strip_prefix("${") {
+            braced.find('}').map(|end| (&braced[..end], end + 3))
+        } else if let Some(t) = tail.strip_prefix('$') {
+            let end = t.find(|c: char| !is_name(c)).unwrap_or(t.len());
+            (end > 0).then(|| (&t[..end], end + 1))
+        } else {
+            let t = &tail[1..];
+            t.find('%').filter(|&end| end > 0 && t[..end].chars().all(is_name)).map(|end| (&t[..end], end + 2))
+        };
+        match reference.and_then(|(name, len)| Some((var(name)?, len))) {
+            Some((value, len)) => { out.push_str(&value); rest = &tail[len..]; }
+            None => { out.push_str(&tail[..1]); rest = &tail[1..]; }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// the configured root, else the cwd or one of its nearby ancestors
+fn find_project_root(configured: &str) -> Option<PathBuf> {
+    let mut cands: Vec<PathBuf> = vec![expand_path(configured)];
+    let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    cands.push(cwd.clone());
+    for p in cwd.ancestors().skip(1).take(5) { cands.push(p.to_path_buf()); }
+    cands.sort(); cands.dedup();
+    cands.into_iter().find(|c| looks_like_repo_root(c))
+}
+
+fn looks_like_repo_root(dir: &Path) -> bool {
+    dir.join("yolov8").join("run.py").exists() &&
+    dir.join("gemini").join("run.py").exists()
+}
+
+// `%XX` escapes in a URL path; invalid escapes are kept literally
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok());
+        match hex {
+            Some(b) => { out.push(b); i += 3; }
+            None => { out.push(bytes[i]); i += 1; }
+        }
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+// `{lat}` / `{lon}` in a map URL template, 6 decimals (~10 cm)
+fn map_url(template: &str, lat: f64, lon: f64) -> String {
+    template.replace("{lat}", &format!("{:.6}", lat)).replace("{lon}", &format!("{:.6}", lon))
+}
+
+// open a URL or file with the platform default handler
+fn open_external(target: &str) -> Result<()> {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut c = Command::new("rundll32");
+        c.arg("url.dll,FileProtocolHandler").arg(target);
+        c
+    } else if cfg!(target_os = "macos") {
+        let mut c = Command::new("open");
+        c.arg(target);
+        c
+    } else {
+        let mut c = Command::new("xdg-open");
+        c.arg(target);
+        c
+    };
+    cmd.spawn().with_context(|| format!("failed to open {}", target))?;
+    Ok(())
+}
+
+// open the platform file manager with `path` selected (or its folder, where selection isn't supported)
+fn reveal_in_file_manager(path: &Path) -> Result<()> {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut c = Command::new("explorer");
+        c.arg(format!("/select,{}", path.display()));
+        c
+    } else if cfg!(target_os = "macos") {
+        let mut c = Command::new("open");
+        c.arg("-R").arg(path);
+        c
+    } else {
+        let mut c = Command::new("xdg-open");
+        c.arg(path.parent().unwrap_or(path));
+        c
+    };
+    cmd.spawn().with_context(|| "failed to launch file manager")?;
+    Ok(())
+}
+
+fn load_texture_from_path(ctx: &egui::Context, path: &Path) -> Option<egui::TextureHandle> {
+    let data = fs::read(path).ok()?;
+    let img = exif_info::decode_upright(&data)?;
+    upload_rgba(ctx, path, &img.to_rgba8())
+}
+
+// tex_cache key of a bbox thumbnail fitted to `size` points
+fn thumb_key(size: egui::Vec2, path: &Path) -> String {
+    format!("thumb:{}x{}:{}", size.x, size.y, path.display())
+}
+
+// tex_cache key of a queued input's tile; `poll_pending_prefetch` reads the path back out of it
+fn pending_thumb_key(path: &Path) -> String {
+    format!("pending:{}", path.display())
+}
+
+fn upload_rgba(ctx: &egui::Context, path: &Path, rgba: &image::RgbaImage) -> Option<egui::TextureHandle> {
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    let color_img = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
+    Some(ctx.load_texture(
+        path.file_name()?.to_string_lossy(),
+        color_img,
+        egui::TextureOptions::default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_vars_handles_tilde_and_both_variable_styles() {
+        let unix = |name: &str| match name {
+            "HOME" => Some("/home/kim".to_string()),
+            "DATA" => Some("/mnt/data".to_string()),
+            _ => None,
+        };
+        assert_eq!(expand_vars("~/wheel_city_ai2", unix), "/home/kim/wheel_city_ai2");
+        assert_eq!(expand_vars("~", unix), "/home/kim");
+        assert_eq!(expand_vars("$HOME/w/best.pt", unix), "/home/kim/w/best.pt");
+        assert_eq!(expand_vars("${DATA}_backup/x", unix), "/mnt/data_backup/x");
+        // not a home reference, an unknown variable, a lone sign
+        assert_eq!(expand_vars("~kim/x", unix), "~kim/x");
+        assert_eq!(expand_vars("$NOPE/x", unix), "$NOPE/x");
+        assert_eq!(expand_vars("50%/$", unix), "50%/$");
+
+        let windows = |name: &str| match name {
+            "USERPROFILE" => Some(r"C:\Users\kim".to_string()),
+            "APPDATA" => Some(r"C:\Users\kim\AppData\Roaming".to_string()),
+            _ => None,
+        };
+        assert_eq!(expand_vars(r"~\wheel_city_ai2", windows), r"C:\Users\kim\wheel_city_ai2");
+        assert_eq!(expand_vars(r"%USERPROFILE%\w\best.pt", windows), r"C:\Users\kim\w\best.pt");
+        assert_eq!(expand_vars(r"%APPDATA%\runner", windows), r"C:\Users\kim\AppData\Roaming\runner");
+        assert_eq!(expand_vars(r"%NOPE%\x", windows), r"%NOPE%\x");
+        assert_eq!(expand_path("relative/best.pt"), PathBuf::from("relative/best.pt"));
+    }
+
+    // image names drawn in a frame's output
+    fn drawn_names(shapes: &[egui::epaint::ClippedShape]) -> Vec<String> {
+        fn walk(shape: &egui::Shape, out: &mut Vec<String>) {
+            match shape {
+                egui::Shape::Vec(v) => v.iter().for_each(|s| walk(s, out)),
+                egui::Shape::Text(t) if t.galley.text().starts_with("img_") => out.push(t.galley.text().to_string()),
+                _ => {}
+            }
+        }
+        let mut out = vec![];
+        shapes.iter().for_each(|c| walk(&c.shape, &mut out));
+        out
+    }
+
+    fn text_results(n: usize) -> Vec<WheelOne> {
+        (0..n).map(|i| WheelOne {
+            image: format!("img_{:05}.jpg", i),
+            result: WheelJudge { accessible: [Some(true), Some(false), None][i % 3], reason: "curb without a ramp; ".repeat(10), confidence: None, criteria: Default::default() },
+            lat: None,
+            lon: None,
+            cached: false,
+            error: None,
+            label: None,
+        }).collect()
+    }
+
+    // one frame of the results table in a 1280×800 window, scrolled by `scroll` points
+    fn table_frame(ctx: &egui::Context, app: &mut AppState, scroll: f32) -> egui::FullOutput {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1280.0, 800.0))),
+            events: vec![
+                egui::Event::PointerMoved(egui::pos2(400.0, 400.0)),
+                egui::Event::Scroll(egui::vec2(0.0, scroll)),
+            ],
+            ..Default::default()
+        };
+        // as `update` does at the start of every frame
+        app.thumb_decodes = 0;
+        ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let rows = app.table_rows();
+                app.show_results_table(ui, ctx, &rows);
+            });
+        })
+    }
+
+    // a frame over 10k results lays out only the rows in view, and scrolling moves that window
+    #[test]
+    fn large_result_table_lays_out_only_visible_rows() {
+        let mut app = AppState { results: text_results(10_000), ..Default::default() };
+        let ctx = egui::Context::default();
+        for _ in 0..3 { table_frame(&ctx, &mut app, 0.0); }
+        let top = drawn_names(&table_frame(&ctx, &mut app, 0.0).shapes);
+        // 800 points of 28-point rows, plus the few egui keeps around the viewport
+        assert!(!top.is_empty() && top.len() < 40, "{} rows drawn", top.len());
+        assert_eq!(top[0], "img_00000.jpg");
+
+        for _ in 0..10 { table_frame(&ctx, &mut app, -600.0); }
+        let scrolled = drawn_names(&table_frame(&ctx, &mut app, 0.0).shapes);
+        assert!(!scrolled.is_empty() && scrolled.len() < 40, "{} rows drawn", scrolled.len());
+        assert!(!scrolled.contains(&top[0]), "still drawing the first row after scrolling: {:?}", scrolled);
+    }
+
+    // frame time over 2000 results with bbox thumbnails on disk, scrolling through the table;
+    // `cargo test -- --ignored results_table_frame_time`. The budget holds for a debug build
+    // (about 22 ms at p95 on a desktop), so a regression like decoding on the UI thread fails it
+    #[test]
+    #[ignore = "benchmark"]
+    fn results_table_frame_time_2000_rows() {
+        let dir = std::env::temp_dir().join(format!("wheel_city_table_bench_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let results = text_results(2_000);
+        let img = image::RgbImage::from_fn(320, 240, |x, y| image::Rgb([(x % 256) as u8, (y % 256) as u8, 128]));
+        for r in &results { img.save(dir.join(&r.image)).unwrap(); }
+        let mut app = AppState { results, last_run_bbox_dir: Some(dir.clone()), ..Default::default() };
+        let ctx = egui::Context::default();
+        let mut times = vec![];
+        for i in 0..300 {
+            let start = std::time::Instant::now();
+            table_frame(&ctx, &mut app, if i < 30 { 0.0 } else { -200.0 });
+            times.push(start.elapsed());
+        }
+        let _ = fs::remove_dir_all(&dir);
+        let avg = times.iter().sum::<std::time::Duration>() / times.len() as u32;
+        times.sort();
+        let p95 = times[times.len() * 95 / 100];
+        assert!(p95 < std::time::Duration::from_millis(50),
+            "2000 rows: average {:?}, p95 {:?}, max {:?}", avg, p95, times[times.len() - 1]);
+    }
 }
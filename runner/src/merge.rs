@@ -0,0 +1,91 @@
+// 여러 결과 JSON을 하나로 병합 (같은 이미지 이름은 가장 최신 파일의 결과를 유지)
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{sidecar::Sidecar, write_atomic, WheelResultFile};
+
+pub struct MergeOutcome {
+    pub path: PathBuf,
+    pub entries: usize,
+    // human-readable lines for the app log
+    pub log: Vec<String>,
+}
+
+pub fn merge_result_files(files: &[PathBuf], out_dir: &Path) -> Result<MergeOutcome> {
+    let mut log = vec![];
+
+    // oldest first, so later files win on conflicts
+    let mut ordered: Vec<(SystemTime, &PathBuf)> = files.iter()
+        .map(|p| (fs::metadata(p).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH), p))
+        .collect();
+    ordered.sort_by_key(|(t, _)| *t);
+
+    let mut merged: Vec<serde_json::Value> = vec![];
+    let mut by_name: HashMap<String, (usize, PathBuf)> = HashMap::new();
+    let mut sidecar = Sidecar::default();
+    let mut used_files = 0;
+
+    for (_, path) in ordered {
+        let entries = match read_results_array(path) {
+            Ok(v) => v,
+            Err(e) => {
+                log.push(format!("[WARN] skipping {}: {:#}", path.display(), e));
+                continue;
+            }
+        };
+        used_files += 1;
+        for entry in entries {
+            let Some(name) = entry.get("image").and_then(|v| v.as_str()).map(|s| s.to_string()) else {
+                log.push(format!("[WARN] entry without `image` in {}, skipped", path.display()));
+                continue;
+            };
+            match by_name.get(&name) {
+                Some((i, prev)) => {
+                    log.push(format!("[WARN] conflict: {} from {} replaced by {}", name, prev.display(), path.display()));
+                    merged[*i] = entry;
+                    by_name.insert(name, (*i, path.clone()));
+                }
+                None => {
+                    by_name.insert(name, (merged.len(), path.clone()));
+                    merged.push(entry);
+                }
+            }
+        }
+        for (image, note) in Sidecar::load(path).notes {
+            if note.trim().is_empty() { continue; }
+            if let Some(prev) = sidecar.notes.get(&image) {
+                if prev != &note {
+                    log.push(format!("[WARN] conflicting notes for {}, keeping {}", image, path.display()));
+                }
+            }
+            sidecar.notes.insert(image, note);
+        }
+    }
+    if used_files == 0 { anyhow::bail!("no readable result JSON among {} file(s)", files.len()); }
+
+    fs::create_dir_all(out_dir).ok();
+    let ts = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let out = out_dir.join(format!("result_merged_{}.json", ts));
+    let entries = merged.len();
+    let payload = serde_json::json!({ "results": merged });
+    write_atomic(&out, serde_json::to_string_pretty(&payload)?.as_bytes())?;
+    if !sidecar.notes.is_empty() { sidecar.save(&out)?; }
+    log.push(format!("[INFO] merged {} entries from {} file(s)", entries, used_files));
+    Ok(MergeOutcome { path: out, entries, log })
+}
+
+fn read_results_array(path: &Path) -> Result<Vec<serde_json::Value>> {
+    let data = fs::read_to_string(path).with_context(|| "failed to read")?;
+    let root: serde_json::Value = serde_json::from_str(&data).with_context(|| "invalid JSON")?;
+    serde_json::from_value::<WheelResultFile>(root.clone()).with_context(|| "does not match the result schema")?;
+    match root.get("results") {
+        Some(serde_json::Value::Array(a)) => Ok(a.clone()),
+        _ => anyhow::bail!("no `results` array"),
+    }
+}
@@ -0,0 +1,377 @@
+// 네이티브 YOLO 추론: .onnx 가중치를 ort 크레이트로 직접 돌려 Python/ultralytics 설치 없이 검출
+// 전처리(letterbox), NMS, 라벨 파일 형식은 ultralytics 의 predict 와 같게 맞춤 (conf 0.25, iou 0.7, 114 회색 여백)
+// ort 는 `native-inference` 기능으로만 빌드되고, 그 외 전처리/후처리는 항상 컴파일되어 테스트됨
+// without the feature, only the tests use the pre/post-processing
+#![cfg_attr(not(feature = "native-inference"), allow(dead_code))]
+use anyhow::Result;
+use image::{imageops::FilterType, DynamicImage, Rgb, RgbImage};
+use std::{collections::BTreeMap, fmt::Write as _, path::Path};
+
+// ultralytics predict defaults
+pub const CONF_THRESHOLD: f32 = 0.25;
+pub const IOU_THRESHOLD: f32 = 0.7;
+const MAX_DETECTIONS: usize = 300;
+const PAD_VALUE: u8 = 114;
+
+pub fn is_onnx(weights: &str) -> bool {
+    Path::new(weights.trim()).extension().is_some_and(|e| e.eq_ignore_ascii_case("onnx"))
+}
+
+// whether this build can run ONNX models
+pub fn available() -> bool {
+    cfg!(feature = "native-inference")
+}
+
+// how an image was fitted into the model input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    pub scale: f32,
+    pub pad_x: f32,
+    pub pad_y: f32,
+}
+
+// resize keeping the aspect ratio and pad to `width` x `height`, centered like ultralytics' LetterBox;
+// returns RGB planes (CHW) scaled to 0..1
+pub fn letterbox(img: &DynamicImage, width: u32, height: u32) -> (Vec<f32>, Letterbox) {
+    let (w0, h0) = (img.width().max(1), img.height().max(1));
+    let scale = (width as f32 / w0 as f32).min(height as f32 / h0 as f32);
+    let (w1, h1) = ((w0 as f32 * scale).round() as u32, (h0 as f32 * scale).round() as u32);
+    let (dw, dh) = ((width - w1.min(width)) as f32 / 2.0, (height - h1.min(height)) as f32 / 2.0);
+    let (left, top) = ((dw - 0.1).round().max(0.0) as u32, (dh - 0.1).round().max(0.0) as u32);
+    let resized = img.resize_exact(w1, h1, FilterType::Triangle).to_rgb8();
+    let mut canvas = RgbImage::from_pixel(width, height, Rgb([PAD_VALUE; 3]));
+    image::imageops::replace(&mut canvas, &resized, left as i64, top as i64);
+
+    let plane = (width * height) as usize;
+    let mut data = vec![0f32; 3 * plane];
+    for (i, p) in canvas.pixels().enumerate() {
+        for c in 0..3 {
+            data[c * plane + i] = p[c] as f32 / 255.0;
+        }
+    }
+    (data, Letterbox { scale, pad_x: left as f32, pad_y: top as f32 })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxDet {
+    pub class: usize,
+    pub conf: f32,
+    // corners, in model-input pixels until `unletterbox`
+    pub x0: f32,
+    pub y0: f32,
+    pub x1: f32,
+    pub y1: f32,
+}
+
+impl BoxDet {
+    fn area(&self) -> f32 {
+        (self.x1 - self.x0).max(0.0) * (self.y1 - self.y0).max(0.0)
+    }
+
+    fn iou(&self, other: &BoxDet) -> f32 {
+        let w = (self.x1.min(other.x1) - self.x0.max(other.x0)).max(0.0);
+        let h = (self.y1.min(other.y1) - self.y0.max(other.y0)).max(0.0);
+        let inter = w * h;
+        let union = self.area() + other.area() - inter;
+        if union <= 0.0 { 0.0 } else { inter / union }
+    }
+}
+
+// YOLOv8 output [4 + classes, anchors] (batch of one): cx, cy, w, h, then one score per class
+pub fn decode(output: &[f32], n_classes: usize, conf: f32) -> Vec<BoxDet> {
+    let rows = 4 + n_classes;
+    let anchors = output.len() / rows;
+    if n_classes == 0 || anchors * rows != output.len() { return vec![]; }
+    let at = |row: usize, a: usize| output[row * anchors + a];
+    let mut out = vec![];
+    for a in 0..anchors {
+        let (class, score) = (0..n_classes)
+            .map(|c| (c, at(4 + c, a)))
+            .fold((0, f32::MIN), |best, x| if x.1 > best.1 { x } else { best });
+        if score <= conf { continue; }
+        let (cx, cy, w, h) = (at(0, a), at(1, a), at(2, a), at(3, a));
+        out.push(BoxDet { class, conf: score, x0: cx - w / 2.0, y0: cy - h / 2.0, x1: cx + w / 2.0, y1: cy + h / 2.0 });
+    }
+    out
+}
+
+// greedy per-class NMS, highest confidence first
+pub fn nms(mut boxes: Vec<BoxDet>, iou: f32) -> Vec<BoxDet> {
+    boxes.sort_by(|a, b| b.conf.total_cmp(&a.conf));
+    let mut kept: Vec<BoxDet> = vec![];
+    for b in boxes {
+        if kept.len() >= MAX_DETECTIONS { break; }
+        if kept.iter().all(|k| k.class != b.class || k.iou(&b) <= iou) { kept.push(b); }
+    }
+    kept
+}
+
+// model-input pixels → pixels of the `width` x `height` original, clipped to it
+pub fn unletterbox(b: BoxDet, lb: Letterbox, width: u32, height: u32) -> BoxDet {
+    let (w, h) = (width as f32, height as f32);
+    let fx = |x: f32| ((x - lb.pad_x) / lb.scale).clamp(0.0, w);
+    let fy = |y: f32| ((y - lb.pad_y) / lb.scale).clamp(0.0, h);
+    BoxDet { x0: fx(b.x0), y0: fy(b.y0), x1: fx(b.x1), y1: fy(b.y1), ..b }
+}
+
+// a YOLO label file (`class cx cy w h conf`, normalized), as ultralytics writes with save_conf
+pub fn label_lines(boxes: &[BoxDet], width: u32, height: u32) -> String {
+    let (w, h) = (width as f32, height as f32);
+    let mut out = String::new();
+    for b in boxes {
+        let _ = writeln!(out, "{} {} {} {} {} {}", b.class,
+            fmt_g((b.x0 + b.x1) / 2.0 / w), fmt_g((b.y0 + b.y1) / 2.0 / h),
+            fmt_g((b.x1 - b.x0) / w), fmt_g((b.y1 - b.y0) / h), fmt_g(b.conf));
+    }
+    out
+}
+
+// like Python's "%g": six significant digits, no trailing zeros
+fn fmt_g(v: f32) -> String {
+    if v == 0.0 { return "0".to_string(); }
+    let decimals = (5 - v.abs().log10().floor() as i32).max(0) as usize;
+    let s = format!("{:.*}", decimals, v);
+    if s.contains('.') { s.trim_end_matches('0').trim_end_matches('.').to_string() } else { s }
+}
+
+// ultralytics exports the class names as ONNX metadata: "{0: 'ramp', 1: 'curb'}"
+pub fn parse_names(meta: &str) -> BTreeMap<usize, String> {
+    let inner = meta.trim().trim_start_matches('{').trim_end_matches('}');
+    let mut out = BTreeMap::new();
+    // names may contain ", " themselves; split at the next "<digits>:" key instead
+    let mut rest = inner;
+    while let Some((key, after)) = rest.split_once(':') {
+        let Ok(id) = key.trim().trim_start_matches(',').trim().parse::<usize>() else { break; };
+        let after = after.trim_start();
+        let quote = after.chars().next().filter(|c| *c == '\'' || *c == '"');
+        let (name, next) = match quote {
+            Some(q) => match after[1..].find(q) {
+                Some(end) => (&after[1..1 + end], &after[2 + end..]),
+                None => break,
+            },
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        out.insert(id, name.trim().to_string());
+        rest = next;
+    }
+    out
+}
+
+const BOX_COLORS: [[u8; 3]; 6] = [[255, 56, 56], [72, 249, 10], [0, 194, 255], [255, 157, 151], [207, 210, 49], [146, 204, 23]];
+
+// 2px outlines in a per-class color (no text; class names are in the label files)
+pub fn draw_boxes(img: &mut RgbImage, boxes: &[BoxDet]) {
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    if w == 0 || h == 0 { return; }
+    for b in boxes {
+        let color = Rgb(BOX_COLORS[b.class % BOX_COLORS.len()]);
+        let (x0, y0) = (b.x0 as i64, b.y0 as i64);
+        let (x1, y1) = ((b.x1 as i64).min(w - 1), (b.y1 as i64).min(h - 1));
+        for t in 0..2 {
+            for x in x0..=x1 {
+                for y in [y0 + t, y1 - t] {
+                    if (0..w).contains(&x) && (0..h).contains(&y) { img.put_pixel(x as u32, y as u32, color); }
+                }
+            }
+            for y in y0..=y1 {
+                for x in [x0 + t, x1 - t] {
+                    if (0..w).contains(&x) && (0..h).contains(&y) { img.put_pixel(x as u32, y as u32, color); }
+                }
+            }
+        }
+    }
+}
+
+// boxes in original-image pixels for one image, given the raw model output
+// what `detect` got through
+#[derive(Debug, Default)]
+pub struct Detected {
+    pub processed: usize,
+    // files in the source folder that could not be decoded as images
+    pub skipped: Vec<String>,
+}
+
+pub fn postprocess(output: &[f32], n_classes: usize, lb: Letterbox, width: u32, height: u32) -> Vec<BoxDet> {
+    nms(decode(output, n_classes, CONF_THRESHOLD), IOU_THRESHOLD)
+        .into_iter()
+        .map(|b| unletterbox(b, lb, width, height))
+        .collect()
+}
+
+#[cfg(feature = "native-inference")]
+mod session {
+    use super::*;
+    use anyhow::Context;
+    use ort::{session::Session, value::Tensor};
+    use std::{
+        collections::HashMap,
+        fs,
+        path::PathBuf,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    // what `detect` leaves in `outdir`, same layout as yolov8/run.py: the images, labels/<stem>.txt,
+    // labels/names.json and the classes sidecar
+    pub fn detect(weights: &Path, source: &Path, outdir: &Path, draw: bool, cancel: &AtomicBool, progress: &dyn Fn(usize)) -> Result<Detected> {
+        let mut session = Session::builder()?.commit_from_file(weights)
+            .with_context(|| format!("failed to load {}", weights.display()))?;
+        let names = session.metadata().ok()
+            .and_then(|m| m.custom("names").ok().flatten())
+            .map(|s| parse_names(&s))
+            .unwrap_or_default();
+        let (in_w, in_h) = input_size(&session);
+        let labels_dir = crate::labels::labels_dir(outdir);
+        fs::create_dir_all(&labels_dir)?;
+
+        let mut images: Vec<PathBuf> = fs::read_dir(source)?.flatten().map(|e| e.path()).filter(|p| p.is_file()).collect();
+        images.sort();
+        let mut classes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut n_classes_seen = names.len();
+        let mut done = Detected::default();
+        for (i, path) in images.iter().enumerate() {
+            if cancel.load(Ordering::Relaxed) { anyhow::bail!("cancelled"); }
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let Some(img) = fs::read(path).ok().and_then(|d| crate::exif_info::decode_upright(&d)) else {
+                done.skipped.push(name);
+                continue;
+            };
+            let (data, lb) = letterbox(&img, in_w, in_h);
+            let input = Tensor::from_array(([1usize, 3, in_h as usize, in_w as usize], data))?;
+            let outputs = session.run(ort::inputs![input])?;
+            let (shape, raw) = outputs[0].try_extract_tensor::<f32>()?;
+            // [1, 4 + classes, anchors]
+            let rows = shape.get(1).copied().unwrap_or(0) as usize;
+            let n_classes = rows.saturating_sub(4);
+            n_classes_seen = n_classes_seen.max(n_classes);
+            let boxes = postprocess(raw, n_classes, lb, img.width(), img.height());
+
+            let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            if !boxes.is_empty() {
+                fs::write(labels_dir.join(format!("{}.txt", stem)), label_lines(&boxes, img.width(), img.height()))?;
+            }
+            let mut found: Vec<String> = boxes.iter()
+                .map(|b| names.get(&b.class).cloned().unwrap_or_else(|| b.class.to_string()))
+                .collect();
+            found.sort();
+            found.dedup();
+            if draw {
+                let mut rgb = img.to_rgb8();
+                draw_boxes(&mut rgb, &boxes);
+                let out_name = format!("{}.jpg", stem);
+                rgb.save_with_format(outdir.join(&out_name), image::ImageFormat::Jpeg)?;
+                classes.insert(out_name, found);
+            } else {
+                fs::copy(path, outdir.join(&name))?;
+                classes.insert(name, found);
+            }
+            done.processed += 1;
+            progress(i + 1);
+        }
+
+        let names_json: HashMap<String, String> = (0..n_classes_seen)
+            .map(|i| (i.to_string(), names.get(&i).cloned().unwrap_or_else(|| i.to_string())))
+            .collect();
+        fs::write(labels_dir.join("names.json"), serde_json::to_string_pretty(&names_json)?)?;
+        fs::write(crate::classes::sidecar_path(outdir), serde_json::to_string_pretty(&classes)?)?;
+        Ok(done)
+    }
+
+    // fixed input size of the exported model; dynamic axes fall back to 640
+    fn input_size(session: &Session) -> (u32, u32) {
+        let dims = session.inputs.first().and_then(|i| i.input_type.tensor_shape()).map(|s| s.to_vec()).unwrap_or_default();
+        let dim = |i: usize| dims.get(i).copied().filter(|d| *d > 0).map_or(640, |d| d as u32);
+        (dim(3), dim(2))
+    }
+}
+
+#[cfg(feature = "native-inference")]
+pub use session::detect;
+
+#[cfg(not(feature = "native-inference"))]
+pub fn detect(_weights: &Path, _source: &Path, _outdir: &Path, _draw: bool, _cancel: &std::sync::atomic::AtomicBool, _progress: &dyn Fn(usize)) -> Result<Detected> {
+    anyhow::bail!("this build has no native inference; rebuild with `cargo build --features native-inference` or use the Python path")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // one YOLOv8-shaped output: 2 classes, anchors as columns
+    fn output(anchors: &[[f32; 6]]) -> Vec<f32> {
+        (0..6).flat_map(|row| anchors.iter().map(move |a| a[row])).collect()
+    }
+
+    #[test]
+    fn letterbox_centers_the_image_on_gray_padding() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 100, Rgb([255, 255, 255])));
+        let (data, lb) = letterbox(&img, 64, 64);
+        assert_eq!(lb, Letterbox { scale: 0.32, pad_x: 0.0, pad_y: 16.0 });
+        assert_eq!(data.len(), 3 * 64 * 64);
+        assert!((data[0] - PAD_VALUE as f32 / 255.0).abs() < 1e-6);
+        assert!((data[32 * 64 + 32] - 1.0).abs() < 1e-6);
+    }
+
+    fn parse_labels(text: &str) -> Vec<Vec<f32>> {
+        text.lines().map(|l| l.split(' ').map(|v| v.parse().unwrap()).collect()).collect()
+    }
+
+    // same classes in the same order, every value within `tol`
+    fn assert_labels_close(ours: &str, expected: &str, tol: f32) {
+        let (ours, expected) = (parse_labels(ours), parse_labels(expected));
+        assert_eq!(ours.len(), expected.len(), "{:?} vs {:?}", ours, expected);
+        for (a, b) in ours.iter().zip(&expected) {
+            assert_eq!(a[0], b[0]);
+            assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < tol), "{:?} vs {:?}", a, b);
+        }
+    }
+
+    // decode + NMS + un-letterboxing of a hand-built raw output, against labels in the save_txt format
+    #[test]
+    fn postprocessing_writes_the_expected_labels() {
+        let fixture: serde_json::Value = serde_json::from_str(include_str!("../tests/fixtures/native_yolo.json")).unwrap();
+        let anchors: Vec<[f32; 6]> = serde_json::from_value(fixture["anchors"].clone()).unwrap();
+        let lb = Letterbox { scale: 0.5, pad_x: 0.0, pad_y: 80.0 };
+        let boxes = postprocess(&output(&anchors), 2, lb, 1280, 960);
+        assert_labels_close(&label_lines(&boxes, 1280, 960), fixture["expected_labels"].as_str().unwrap(), 1e-3);
+    }
+
+    // the whole native path against ultralytics on real images; the fixture and the .onnx come from
+    // tests/fixtures/make_native_yolo.py, which needs ultralytics installed
+    #[cfg(feature = "native-inference")]
+    #[test]
+    #[ignore = "needs tests/fixtures/native_yolo_ultralytics.json and the exported .onnx"]
+    fn native_detection_matches_ultralytics() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/native_yolo_ultralytics.json");
+        let fixture: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(fixture_path).unwrap()).unwrap();
+        let labels = fixture["labels"].as_object().unwrap();
+
+        let tmp = std::env::temp_dir().join(format!("wheel_city_native_test_{}", std::process::id()));
+        let (source, out) = (tmp.join("source"), tmp.join("out"));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(&source).unwrap();
+        for name in labels.keys() {
+            std::fs::copy(root.join("input_images").join(name), source.join(name)).unwrap();
+        }
+        let weights = root.join(fixture["weights"].as_str().unwrap());
+        let done = detect(&weights, &source, &out, false, &std::sync::atomic::AtomicBool::new(false), &|_| {}).unwrap();
+        assert_eq!((done.processed, done.skipped.len()), (labels.len(), 0));
+        for (name, expected) in labels {
+            let stem = Path::new(name).file_stem().unwrap().to_string_lossy().to_string();
+            let ours = std::fs::read_to_string(crate::labels::labels_dir(&out).join(format!("{}.txt", stem))).unwrap_or_default();
+            // resizing differs slightly from OpenCV's, so boxes and confidences move a little
+            assert_labels_close(&ours, expected.as_str().unwrap(), 0.02);
+        }
+        let _ = std::fs::remove_dir_all(&tmp);
+    }
+
+    #[test]
+    fn names_metadata_and_number_format_follow_ultralytics() {
+        let names = parse_names("{0: 'ramp', 1: 'curb, low', 2: \"stairs\"}");
+        assert_eq!(names.get(&1).map(String::as_str), Some("curb, low"));
+        assert_eq!(names.len(), 3);
+        assert_eq!(fmt_g(0.5), "0.5");
+        assert_eq!(fmt_g(0.1234567), "0.123457");
+        assert!(is_onnx("yolov8/best.ONNX") && !is_onnx("best.pt"));
+    }
+}
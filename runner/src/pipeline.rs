@@ -0,0 +1,1389 @@
+// YOLO → Gemini 파이프라인을 백그라운드 스레드에서 실행하고, 진행 상황을 이벤트로 UI에 전달
+use anyhow::{Context, Result};
+use chrono::Local;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    io::{BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    backend::JudgmentBackend,
+    cache::{self, CacheScope, JudgeCache},
+    checkpoint::{self, Checkpoint, CheckpointStage},
+    classes,
+    convert,
+    cost,
+    error::{RunnerError, STDERR_TAIL_LINES},
+    native,
+    prompt,
+    compare, exif_info::{self, ImageMeta}, expand_path, export, parse_result_file, preflight,
+    script::{CommandLine, ScriptStep, PASSTHROUGH_ENV},
+    sources,
+    video,
+    webhook::{self, Webhook},
+    workdir, write_atomic, WheelOne,
+};
+
+// snapshot of the UI config taken when Run is pressed
+pub struct RunConfig {
+    pub project_root: PathBuf,
+    pub python_bin: String,
+    pub weights_path: String,
+    pub pending_files: Vec<PathBuf>,
+    // second weights file for an A/B comparison run
+    pub weights_b: Option<String>,
+    pub cancel: Arc<AtomicBool>,
+    // stop at the next safe point (after copying, after YOLO, or after the Gemini verdict in flight)
+    // and keep the checkpoint so the run can be resumed
+    pub pause: Arc<AtomicBool>,
+    pub stage_timeout_secs: StageTimeouts,
+    pub stage_failure: StageFailure,
+    // logs/bbox under .runner_work older than this many days are deleted at run start (0 = keep)
+    pub retention_days: u32,
+    // bbox dirs of this many newest runs are kept after a successful run (0 = all)
+    pub keep_last_runs: u32,
+    // user-supplied flags appended to each script's command line
+    pub extra_yolo_args: Vec<String>,
+    pub extra_gemini_args: Vec<String>,
+    // add rows to the table as each verdict arrives; the JSONL stream is written either way
+    pub stream_results: bool,
+    // same-named files from different folders are copied as `<folder>_<name>` instead of `<name>_1`
+    pub prefix_folder_names: bool,
+    // where the result JSON is POSTed after a successful run
+    pub webhook: Option<Webhook>,
+    // "Run and export": formats written to a preset folder after a successful run
+    pub export: Option<export::AutoExport>,
+    // .mp4/.mov inputs are turned into one frame every `frame_interval_secs` seconds
+    pub ffmpeg_bin: String,
+    pub frame_interval_secs: u32,
+    // images per Gemini call (0 = all in one call) and what a failed call does to the run
+    pub gemini_chunk_size: usize,
+    pub chunk_failure: ChunkFailure,
+    // ask Gemini about every image even when the cache has a verdict for it
+    pub bypass_cache: bool,
+    // passed to gemini/run.py as --prompt_file (saved per run); None = the script's own prompt
+    pub prompt_template: Option<String>,
+    // --model/--temperature/--max_tokens for gemini/run.py; empty fields keep the script's defaults
+    pub gemini: GeminiParams,
+    // the model that judges the bbox images
+    pub backend: &'static dyn JudgmentBackend,
+    // .onnx weights run in-process (ort) instead of through yolov8/run.py; `native_draw_boxes`
+    // writes box-annotated JPEGs, otherwise the inputs are copied as they are
+    pub native_inference: bool,
+    pub native_draw_boxes: bool,
+    // "Run label" typed next to the Run button; kept in the sidecar, empty = none
+    pub label: String,
+}
+
+// kept as typed so an empty field means "not set"; `check` validates before a run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeminiParams {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub model: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub temperature: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub max_tokens: String,
+}
+
+impl GeminiParams {
+    // what gemini/run.py uses when --model is not passed (and GEMINI_MODEL is unset)
+    pub const DEFAULT_MODEL: &'static str = "gemini-2.5-flash";
+    pub const MODELS: [&'static str; 4] = [Self::DEFAULT_MODEL, "gemini-1.5-flash", "gemini-1.5-pro", "gemini-2.0-flash"];
+
+    pub fn is_empty(&self) -> bool {
+        self.model.trim().is_empty() && self.temperature.trim().is_empty() && self.max_tokens.trim().is_empty()
+    }
+
+    pub fn check(&self) -> Result<()> {
+        self.args().map(|_| ())
+    }
+
+    // the flags for the fields that are set
+    pub fn args(&self) -> Result<Vec<String>> {
+        let mut out = vec![];
+        let model = self.model.trim();
+        if !model.is_empty() {
+            if model.contains(char::is_whitespace) { anyhow::bail!("model name must not contain spaces"); }
+            out.extend(["--model".to_string(), model.to_string()]);
+        }
+        let temperature = self.temperature.trim();
+        if !temperature.is_empty() {
+            let t: f64 = temperature.parse().with_context(|| format!("temperature is not a number: {}", temperature))?;
+            if !(0.0..=2.0).contains(&t) { anyhow::bail!("temperature must be between 0 and 2"); }
+            out.extend(["--temperature".to_string(), temperature.to_string()]);
+        }
+        let max_tokens = self.max_tokens.trim();
+        if !max_tokens.is_empty() {
+            let n: u32 = max_tokens.parse().with_context(|| format!("max output tokens is not a whole number: {}", max_tokens))?;
+            if !(1..=65536).contains(&n) { anyhow::bail!("max output tokens must be between 1 and 65536"); }
+            out.extend(["--max_tokens".to_string(), n.to_string()]);
+        }
+        Ok(out)
+    }
+
+    // "model gemini-1.5-pro, temperature 0.2, max tokens script default"
+    pub fn describe(&self) -> String {
+        let or_default = |s: &str| if s.trim().is_empty() { "script default".to_string() } else { s.trim().to_string() };
+        format!("model {}, temperature {}, max tokens {}", or_default(&self.model), or_default(&self.temperature), or_default(&self.max_tokens))
+    }
+}
+
+// a failed Gemini chunk either ends the run or is skipped so the other chunks still get verdicts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChunkFailure {
+    #[default]
+    Abort,
+    Skip,
+}
+
+// a script that exits non-zero either ends the run or, when it left output behind, is reported and
+// the run continues with what it wrote (images it never got to are marked as errors)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StageFailure {
+    #[default]
+    Abort,
+    Continue,
+}
+
+// per-subprocess wall-clock limits in seconds; 0 disables the limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageTimeouts {
+    pub yolo: u64,
+    pub gemini: u64,
+}
+
+impl Default for StageTimeouts {
+    fn default() -> Self {
+        // run.py already gives up on each request after 60 s; a fixed limit on the whole batch
+        // would cut off ordinary large runs, so Gemini has none unless one is set
+        Self { yolo: 1800, gemini: 0 }
+    }
+}
+
+pub struct RunOutcome {
+    pub json: PathBuf,
+    pub bbox_dir: PathBuf,
+    // copied name → (lat, lon) from the original's EXIF
+    pub gps: HashMap<String, (f64, f64)>,
+    // copied name → dimensions, size and capture time of the original
+    pub meta: HashMap<String, ImageMeta>,
+    pub ab: Option<AbOutcome>,
+    // the prompt file Gemini was given, if any
+    pub prompt_file: Option<PathBuf>,
+    pub gemini: GeminiParams,
+    // id of the judgment backend; None when nothing was judged
+    pub backend: Option<&'static str>,
+    // the run's label (RunConfig::label)
+    pub label: String,
+}
+
+// the B side of an A/B run (A is the regular outcome)
+pub struct AbOutcome {
+    pub json_b: PathBuf,
+    pub bbox_b: PathBuf,
+    pub comparison: PathBuf,
+}
+
+// Finished is sent once per run; boxing it would only move the allocation
+#[allow(clippy::large_enum_variant)]
+pub enum RunEvent {
+    Log(String),
+    // a stage began; total = number of images when known
+    Stage(String, Option<usize>),
+    // images finished in the current stage
+    Progress(usize),
+    // a "USAGE tokens_in=.. tokens_out=.." line from a script
+    Usage(cost::Usage),
+    // a step performed, for the reproducible run script
+    Command(ScriptStep),
+    // YOLO finished; bbox images can be previewed while Gemini runs
+    BboxReady(PathBuf),
+    // one streamed Gemini verdict
+    Result(WheelOne),
+    Finished(Result<RunOutcome>),
+}
+
+struct Reporter {
+    tx: Sender<RunEvent>,
+    // None in headless mode
+    ctx: Option<egui::Context>,
+}
+
+impl Reporter {
+    fn send(&self, ev: RunEvent) {
+        let _ = self.tx.send(ev);
+        if let Some(ctx) = &self.ctx { ctx.request_repaint(); }
+    }
+
+    fn log(&self, s: &str) {
+        self.send(RunEvent::Log(s.to_string()));
+    }
+}
+
+pub fn spawn(cfg: RunConfig, ctx: egui::Context) -> Receiver<RunEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let rep = Reporter { tx, ctx: Some(ctx) };
+        let res = run(&cfg, &rep);
+        send_webhook(&cfg, &res, &rep);
+        auto_export(&cfg, &res, &rep);
+        prune_old_runs(&cfg, &res, &rep);
+        rep.send(RunEvent::Finished(res));
+    });
+    rx
+}
+
+// YOLO only, regenerating the bbox images of an existing result file (e.g. after its bbox dir was deleted);
+// `source` holds the input images
+pub fn spawn_redetect(cfg: RunConfig, source: PathBuf, json: PathBuf, bbox_dir: PathBuf, ctx: egui::Context) -> Receiver<RunEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let rep = Reporter { tx, ctx: Some(ctx) };
+        let res = (|| -> Result<RunOutcome> {
+            let python = resolve_python(&cfg.python_bin, &cfg.project_root)?;
+            let yolo_script = cfg.project_root.join("yolov8").join("run.py");
+            let weights = cfg.project_root.join(expand_path(&cfg.weights_path));
+            if !weights.exists() { return Err(RunnerError::MissingWeights { path: weights, detail: "not found" }.into()); }
+            let n_images = fs::read_dir(&source)?.flatten().filter(|e| e.path().is_file()).count();
+            let stage = Stage {
+                cfg: &cfg, rep: &rep, python: &python, yolo_script: &yolo_script, run_input: &source,
+                n_images,
+                prompt_file: None,
+            };
+            stage.detect("", &weights, &bbox_dir)?;
+            Ok(RunOutcome { json, bbox_dir, gps: HashMap::new(), meta: HashMap::new(), ab: None, prompt_file: None, gemini: GeminiParams::default(), backend: None, label: cfg.label.clone() })
+        })();
+        rep.send(RunEvent::Finished(res));
+    });
+    rx
+}
+
+// Gemini again for `names` of an existing result (undetermined or missing verdicts), merging the
+// new verdicts into `json` by image name; YOLO is not re-run, the bbox images are reused
+pub fn spawn_rejudge(cfg: RunConfig, json: PathBuf, bbox_dir: PathBuf, names: Vec<String>, ctx: egui::Context) -> Receiver<RunEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let rep = Reporter { tx, ctx: Some(ctx) };
+        let res = rejudge(&cfg, &rep, &json, &bbox_dir, &names)
+            .map(|()| RunOutcome { json, bbox_dir, gps: HashMap::new(), meta: HashMap::new(), ab: None, prompt_file: None, gemini: cfg.gemini.clone(), backend: Some(cfg.backend.id()), label: cfg.label.clone() });
+        rep.send(RunEvent::Finished(res));
+    });
+    rx
+}
+
+fn rejudge(cfg: &RunConfig, rep: &Reporter, json: &Path, bbox_dir: &Path, names: &[String]) -> Result<()> {
+    let work_dir = cfg.project_root.join(".runner_work");
+    let python = resolve_python(&cfg.python_bin, &cfg.project_root)?;
+    let yolo_script = cfg.project_root.join("yolov8").join("run.py");
+    let ts = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let prompt_file = cfg.prompt_template.as_deref().map(|t| prompt::write_prompt_file(&work_dir, &ts, t)).transpose()?;
+    let stage = Stage {
+        cfg, rep, python: &python, yolo_script: &yolo_script, run_input: bbox_dir,
+        n_images: names.len(),
+        prompt_file: prompt_file.as_deref(),
+    };
+    rep.log(&format!("[STEP] asking Gemini again for {} images of {}\n", names.len(), json.display()));
+    rep.log(&format!("[INFO] {}: {}\n", cfg.backend.label(), cfg.gemini.describe()));
+    let out = work_dir.join(format!("rejudge_{}.json", ts));
+    let stream_path = work_dir.join(format!("stream_{}.jsonl", ts));
+    let res = stage.judge("", bbox_dir, &out, &stream_path, Some(names), true).and_then(|()| read_result_entries(&out));
+    let _ = fs::remove_file(&out);
+    let _ = fs::remove_file(&stream_path);
+    let mut new = res?;
+
+    // the rest of the file (other keys, entry order) is kept
+    let data = fs::read_to_string(json).with_context(|| format!("failed to read {}", json.display()))?;
+    let mut root: serde_json::Value = serde_json::from_str(&data).with_context(|| format!("failed to parse {}", json.display()))?;
+    let old = root.get("results").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    let mut order: Vec<String> = old.iter().filter_map(|v| v.get("image")?.as_str().map(str::to_string)).collect();
+    let missing: Vec<String> = names.iter().filter(|n| !order.contains(n)).cloned().collect();
+    order.extend(missing);
+    // a different backend than the file's own is recorded on each entry it judged
+    let identity = cfg.backend.identity(&cfg.gemini);
+    if root.get("backend").is_some_and(|b| *b != identity) {
+        for v in new.iter_mut().filter_map(|v| v.as_object_mut()) { v.insert("backend".to_string(), identity.clone()); }
+    }
+    let still_null = new.iter().filter(|v| v.pointer("/result/accessible").and_then(|a| a.as_bool()).is_none()).count();
+    let n_new = new.len();
+    root["results"] = serde_json::Value::Array(checkpoint::merge_entries(&order, old, new));
+    write_atomic(json, serde_json::to_string_pretty(&root)?.as_bytes())?;
+    rep.log(&format!("[INFO] merged {} new verdicts into {} ({} still undetermined)\n", n_new, json.display(), still_null));
+    Ok(())
+}
+
+// continue an interrupted single-weights run from its checkpoint
+pub fn spawn_resume(cfg: RunConfig, cp: Checkpoint, ctx: egui::Context) -> Receiver<RunEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let rep = Reporter { tx, ctx: Some(ctx) };
+        let res = resume(&cfg, &rep, &cp);
+        if res.is_err() && cfg.cancel.load(Ordering::Relaxed) {
+            Checkpoint::clear(&cfg.project_root.join(".runner_work"));
+        }
+        send_webhook(&cfg, &res, &rep);
+        auto_export(&cfg, &res, &rep);
+        prune_old_runs(&cfg, &res, &rep);
+        rep.send(RunEvent::Finished(res));
+    });
+    rx
+}
+
+fn resume(cfg: &RunConfig, rep: &Reporter, cp: &Checkpoint) -> Result<RunOutcome> {
+    let work_dir = cfg.project_root.join(".runner_work");
+    let python = resolve_python(&cfg.python_bin, &cfg.project_root)?;
+    let yolo_script = cfg.project_root.join("yolov8").join("run.py");
+    // the prompt the run started with, even if the template was edited since
+    let prompt_file = match prompt::prompt_path(&work_dir, &cp.ts) {
+        p if p.is_file() => Some(p),
+        _ => cfg.prompt_template.as_deref().map(|t| prompt::write_prompt_file(&work_dir, &cp.ts, t)).transpose()?,
+    };
+    let stage = Stage {
+        cfg, rep, python: &python, yolo_script: &yolo_script, run_input: &cp.run_input,
+        n_images: cp.images.len(),
+        prompt_file: prompt_file.as_deref(),
+    };
+    rep.log(&format!("[STEP] resuming run {} ({} images)\n", cp.ts, cp.images.len()));
+    rep.log(&format!("[INFO] {}: {}\n", cfg.backend.label(), cfg.gemini.describe()));
+
+    let mut plan = checkpoint::plan_resume(cp, &cp.bbox_names(), cp.judged());
+    if plan.run_yolo {
+        if let Some(missing) = cp.images.iter().find(|n| !cp.run_input.join(n).exists()) {
+            anyhow::bail!("cannot resume: input {} is gone from {}", missing, cp.run_input.display());
+        }
+        rep.log("[INFO] bbox images incomplete, running YOLO again\n");
+        // verdicts were made from the old bbox images; start the stream over
+        let _ = fs::remove_file(&cp.stream_path);
+        let _ = fs::remove_file(checkpoint::chunks_path(&cp.stream_path));
+        stage.detect("", &cp.weights, &cp.bbox_dir)?;
+        let detected = Checkpoint { stage: CheckpointStage::Detected, ..cp.clone() };
+        save_checkpoint(&detected, &work_dir, rep);
+        check_pause(cfg)?;
+        plan = checkpoint::plan_resume(&detected, &detected.bbox_names(), vec![]);
+    } else {
+        rep.log(&format!("[INFO] reusing YOLO output; {} verdicts carried over, {} images left\n",
+            plan.done.len(), plan.remaining.len()));
+    }
+    rep.send(RunEvent::BboxReady(cp.bbox_dir.clone()));
+    for v in &plan.done {
+        if let Ok(one) = serde_json::from_value::<WheelOne>(v.clone()) { rep.send(RunEvent::Result(one)); }
+    }
+
+    let new = if plan.remaining.is_empty() { vec![] } else {
+        let resume_json = work_dir.join(format!("resume_{}.json", cp.ts));
+        stage.judge("", &cp.bbox_dir, &resume_json, &cp.stream_path, Some(&plan.remaining), true)?;
+        let data = fs::read_to_string(&resume_json)?;
+        let _ = fs::remove_file(&resume_json);
+        let root: serde_json::Value = serde_json::from_str(&data).with_context(|| "failed to parse resumed results")?;
+        root.get("results").and_then(|v| v.as_array()).cloned().unwrap_or_default()
+    };
+    let merged = checkpoint::merge_entries(&cp.bbox_names(), plan.done, new);
+    let n = merged.len();
+    let payload = serde_json::json!({ "results": merged, "backend": cfg.backend.identity(&cfg.gemini) });
+    write_atomic(&cp.out_json, serde_json::to_string_pretty(&payload)?.as_bytes())?;
+    rep.log(&format!("[INFO] merged {} results into {}\n", n, cp.out_json.display()));
+    let _ = fs::remove_file(&cp.stream_path);
+    let _ = fs::remove_file(checkpoint::chunks_path(&cp.stream_path));
+    Checkpoint::clear(&work_dir);
+    Ok(RunOutcome {
+        json: cp.out_json.clone(),
+        bbox_dir: cp.bbox_dir.clone(),
+        gps: cp.gps.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+        meta: cp.meta.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        ab: None,
+        prompt_file,
+        gemini: cfg.gemini.clone(),
+        backend: Some(cfg.backend.id()),
+        label: cfg.label.clone(),
+    })
+}
+
+// a failed upload is logged but leaves the run successful
+fn send_webhook(cfg: &RunConfig, res: &Result<RunOutcome>, rep: &Reporter) {
+    let (Some(hook), Ok(out)) = (&cfg.webhook, res) else { return; };
+    rep.log("[STEP] sending results to the webhook...\n");
+    if let Err(e) = webhook::post_results(hook, &out.json, &|s| rep.log(s)) {
+        rep.log(&format!("[ERROR] {:#} (the local result JSON is saved)\n", e));
+    }
+}
+
+// after the run on the worker thread, so the table shows up with the files already written
+fn auto_export(cfg: &RunConfig, res: &Result<RunOutcome>, rep: &Reporter) {
+    let (Some(spec), Ok(out)) = (&cfg.export, res) else { return; };
+    rep.log(&format!("[STEP] exporting results to {}...\n", spec.dir.display()));
+    match spec.write(&out.json, &out.gps, &out.label) {
+        Ok(paths) => for p in paths { rep.log(&format!("[DONE] exported {}\n", p.display())); },
+        Err(e) => rep.log(&format!("[ERROR] export failed: {:#} (the result JSON is saved)\n", e)),
+    }
+}
+
+fn prune_old_runs(cfg: &RunConfig, res: &Result<RunOutcome>, rep: &Reporter) {
+    let Ok(out) = res else { return; };
+    let (removed, bytes) = workdir::prune_runs(&cfg.project_root.join(".runner_work"), cfg.keep_last_runs as usize, &out.bbox_dir);
+    if removed > 0 {
+        rep.log(&format!("[INFO] removed the bbox images of {} older runs (kept the last {}), reclaimed {}\n",
+            removed, cfg.keep_last_runs, preflight::fmt_bytes(bytes)));
+    }
+}
+
+pub const PAUSED: &str = "Paused; use \"Resume last run\" to continue";
+
+fn check_pause(cfg: &RunConfig) -> Result<()> {
+    if cfg.pause.load(Ordering::Relaxed) { anyhow::bail!(PAUSED); }
+    Ok(())
+}
+
+// a checkpoint that can't be written only costs resumability
+fn save_checkpoint(cp: &Checkpoint, work_dir: &Path, rep: &Reporter) {
+    if let Err(e) = cp.save(work_dir) {
+        rep.log(&format!("[WARN] {:#}\n", e));
+    }
+}
+
+// blocking run without a UI; log lines go to stderr
+pub fn run_headless(cfg: &RunConfig) -> Result<RunOutcome> {
+    let (tx, rx) = mpsc::channel();
+    let printer = thread::spawn(move || {
+        for ev in rx {
+            if let RunEvent::Log(s) = ev { eprint!("{}", s); }
+        }
+    });
+    let res = run(cfg, &Reporter { tx, ctx: None });
+    let _ = printer.join();
+    res
+}
+
+fn run(cfg: &RunConfig, rep: &Reporter) -> Result<RunOutcome> {
+    // outputs of the stage in flight; removed if the run is cancelled
+    let mut partial: Vec<PathBuf> = vec![];
+    let res = run_inner(cfg, rep, &mut partial);
+    if res.is_err() && cfg.cancel.load(Ordering::Relaxed) {
+        // cancelled runs are abandoned, not resumable
+        Checkpoint::clear(&cfg.project_root.join(".runner_work"));
+        for p in partial {
+            let removed = if p.is_dir() { fs::remove_dir_all(&p) } else { fs::remove_file(&p) };
+            if removed.is_ok() { rep.log(&format!("[INFO] removed partial output: {}\n", p.display())); }
+        }
+    }
+    res
+}
+
+fn run_inner(cfg: &RunConfig, rep: &Reporter, partial: &mut Vec<PathBuf>) -> Result<RunOutcome> {
+    let project_root = &cfg.project_root;
+    // before anything starts a subprocess
+    fs::create_dir_all(project_root.join("input_images")).ok();
+    let sources = preflight::sources(project_root, &cfg.pending_files)?;
+    let python = preflight::python(&cfg.python_bin, project_root)?;
+    rep.log(&format!("[INFO] using Python: {}\n", python));
+    // informational: YOLO itself reports a missing PyTorch more precisely
+    match preflight::gpu(&python) {
+        Ok(preflight::Accel::Cuda(name)) => rep.log(&format!("[INFO] GPU: CUDA on {}\n", name)),
+        Ok(preflight::Accel::Cpu) => rep.log("[WARN] no CUDA GPU visible to PyTorch; YOLO will run on the CPU and be slow\n"),
+        Err(e) => rep.log(&format!("[WARN] GPU check failed: {:#}\n", e)),
+    }
+
+    let (yolo_script, _) = preflight::scripts(project_root)?;
+    let (weights_abs, _) = preflight::weights(project_root, &cfg.weights_path)?;
+    let weights_b_abs = match &cfg.weights_b {
+        Some(w) => Some(preflight::weights(project_root, w).with_context(|| "weights (B)")?.0),
+        None => None,
+    };
+
+    let source_bytes = sources.iter().filter_map(|p| fs::metadata(p).ok()).map(|m| m.len()).sum();
+    preflight::disk_space(project_root, preflight::space_needed(source_bytes))?;
+    let found = cfg.backend.preflight(project_root).with_context(|| format!("judgment backend {}", cfg.backend.label()))?;
+    rep.log(&format!("[INFO] judgment backend: {} ({})\n", cfg.backend.label(), found));
+    preflight::ffmpeg(&cfg.ffmpeg_bin, &sources)?;
+
+    // run-scoped
+    let work_dir   = project_root.join(".runner_work");
+    let run_input  = work_dir.join("input");
+    let ts         = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let results_dir= project_root.join("results");
+    fs::create_dir_all(&run_input).ok();
+    fs::create_dir_all(&results_dir).ok();
+    let pruned = workdir::prune(&work_dir, cfg.retention_days);
+    if pruned > 0 {
+        rep.log(&format!("[INFO] removed {} work-dir entries older than {} days\n", pruned, cfg.retention_days));
+    }
+    for key in PASSTHROUGH_ENV {
+        if let Ok(v) = std::env::var(key) { rep.send(RunEvent::Command(ScriptStep::Env(key.to_string(), v))); }
+    }
+    rep.send(RunEvent::Command(ScriptStep::MkDir(run_input.clone())));
+    rep.send(RunEvent::Command(ScriptStep::MkDir(results_dir.clone())));
+
+    // a new run replaces the inputs an interrupted one would resume from
+    Checkpoint::clear(&work_dir);
+
+    // clear run_input only
+    for e in fs::read_dir(&run_input)? {
+        let p = e?.path();
+        if p.is_file() { let _ = fs::remove_file(p); }
+    }
+
+    // copy into run_input with unique names
+    rep.log("[STEP] copying into work input dir...\n");
+    rep.send(RunEvent::Stage("copy".to_string(), Some(sources.len())));
+    let mut used_names: HashSet<String> = HashSet::new();
+    let mut gps: HashMap<String, (f64, f64)> = HashMap::new();
+    let mut meta: HashMap<String, ImageMeta> = HashMap::new();
+    // copied name → the photo (or video, for frames) it came from; kept next to the result JSON
+    let mut source_paths: BTreeMap<String, PathBuf> = BTreeMap::new();
+    let names = copy_names(&sources, cfg.prefix_folder_names);
+    for (i, (src, final_name)) in sources.iter().zip(names).enumerate() {
+        rep.send(RunEvent::Progress(i));
+        if !src.exists() {
+            rep.log(&format!("[WARN] source missing, skip: {}\n", src.display()));
+            continue;
+        }
+        if video::is_video(src) {
+            let stem = Path::new(&final_name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            match extract_frames(cfg, rep, src, &stem, &work_dir, &run_input) {
+                Ok(frames) => {
+                    rep.log(&format!("[INFO] extracted {} frames from {} (every {}s)\n", frames.len(), src.display(), cfg.frame_interval_secs.max(1)));
+                    let video_path = fs::canonicalize(src).unwrap_or_else(|_| src.clone());
+                    for f in &frames {
+                        meta.insert(f.clone(), exif_info::image_meta(&run_input.join(f)));
+                        source_paths.insert(f.clone(), video_path.clone());
+                    }
+                    used_names.extend(frames);
+                }
+                Err(e) if cfg.cancel.load(Ordering::Relaxed) => return Err(e),
+                Err(e) => rep.log(&format!("[WARN] frame extraction failed (skip): {} ({:#})\n", src.display(), e)),
+            }
+            continue;
+        }
+        let dst = run_input.join(&final_name);
+        let src_name = src.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if convert::needs_conversion(&src_name) {
+            match convert::to_png(src, &dst) {
+                Ok(tool) => rep.log(&format!("[INFO] converted {} to {} ({})\n", src.display(), final_name, tool)),
+                Err(e) => {
+                    rep.log(&format!("[WARN] {:#} (skip)\n", e));
+                    continue;
+                }
+            }
+        } else if let Err(e) = fs::copy(src, &dst) {
+            rep.log(&format!("[WARN] copy failed (skip): {} -> {} ({})\n", src.display(), dst.display(), e));
+            continue;
+        } else {
+            rep.send(RunEvent::Command(ScriptStep::Copy(src.clone(), dst.clone())));
+        }
+        // read from the original; the copy is what YOLO re-encodes
+        if let Some(g) = exif_info::read_gps(src) { gps.insert(final_name.clone(), g); }
+        meta.insert(final_name.clone(), exif_info::image_meta(src));
+        let original = fs::canonicalize(src).unwrap_or_else(|_| src.clone());
+        source_paths.insert(final_name.clone(), original);
+        used_names.insert(final_name);
+    }
+    if used_names.is_empty() {
+        anyhow::bail!("none of the {} sources could be copied; nothing to run (see the warnings above)", sources.len());
+    }
+    if let Err(e) = sources::write(&results_dir, &ts, &source_paths) {
+        rep.log(&format!("[WARN] {:#} (rows won't link back to their original photos)\n", e));
+    }
+    if !gps.is_empty() {
+        rep.log(&format!("[INFO] GPS EXIF found for {}/{} images\n", gps.len(), used_names.len()));
+    }
+
+    rep.log(&format!("[INFO] {}: {}\n", cfg.backend.label(), cfg.gemini.describe()));
+    let prompt_file = match &cfg.prompt_template {
+        Some(t) => {
+            let p = prompt::write_prompt_file(&work_dir, &ts, t)?;
+            rep.log(&format!("[INFO] prompt template: {}\n", p.display()));
+            Some(p)
+        }
+        None => None,
+    };
+    let stage = Stage {
+        cfg, rep, python: &python, yolo_script: &yolo_script, run_input: &run_input,
+        n_images: used_names.len(),
+        prompt_file: prompt_file.as_deref(),
+    };
+
+    let Some(weights_b_abs) = weights_b_abs else {
+        let run_bbox = work_dir.join("bbox").join(&ts);
+        let out_json = results_dir.join(format!("result_{}.json", ts));
+        let stream_path = work_dir.join(format!("stream_{}.jsonl", ts));
+        let mut images: Vec<String> = used_names.iter().cloned().collect();
+        images.sort();
+        let mut cp = Checkpoint {
+            ts: ts.clone(),
+            weights: weights_abs.clone(),
+            run_input: run_input.clone(),
+            bbox_dir: run_bbox.clone(),
+            out_json: out_json.clone(),
+            stream_path: stream_path.clone(),
+            images,
+            gps: gps.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            meta: meta.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            label: cfg.label.clone(),
+            stage: CheckpointStage::Copied,
+        };
+        save_checkpoint(&cp, &work_dir, rep);
+        check_pause(cfg)?;
+
+        partial.push(run_bbox.clone());
+        stage.detect("", &weights_abs, &run_bbox)?;
+        rep.send(RunEvent::BboxReady(run_bbox.clone()));
+        cp.stage = CheckpointStage::Detected;
+        save_checkpoint(&cp, &work_dir, rep);
+        check_pause(cfg)?;
+
+        partial.push(out_json.clone());
+        partial.push(stream_path.clone());
+        stage.judge_all("", &run_bbox, &out_json, &stream_path, true)?;
+        let _ = fs::remove_file(&stream_path);
+        partial.clear();
+        Checkpoint::clear(&work_dir);
+        return Ok(RunOutcome { json: out_json, bbox_dir: run_bbox, gps, meta, ab: None, prompt_file, gemini: cfg.gemini.clone(), backend: Some(cfg.backend.id()), label: cfg.label.clone() });
+    };
+
+    // A/B: same inputs, two weights, sibling bbox dirs and result files
+    let mut variants = vec![];
+    for (tag, weights, stream) in [("A", &weights_abs, true), ("B", &weights_b_abs, false)] {
+        rep.log(&format!("[STEP] variant {}: {}\n", tag, weights.display()));
+        let run_bbox = work_dir.join("bbox").join(format!("{}_{}", ts, tag));
+        let out_json = results_dir.join(format!("result_{}_{}.json", ts, tag));
+        let stream_path = work_dir.join(format!("stream_{}_{}.jsonl", ts, tag));
+        stage.detect_and_judge(&format!(" ({})", tag), weights, &run_bbox, &out_json, &stream_path, stream, partial)?;
+        variants.push((run_bbox, out_json, weights.clone()));
+    }
+    let (bbox_b, json_b, _) = variants.pop().unwrap();
+    let (bbox_a, json_a, _) = variants.pop().unwrap();
+    let comparison = results_dir.join(format!("comparison_{}.json", ts));
+    let summary = compare::write_summary(&comparison, (&weights_abs, &json_a), (&weights_b_abs, &json_b))?;
+    rep.log(&format!("[INFO] A/B agreement: {}/{} ({:.1}%) → {}\n",
+        summary.agree, summary.total, summary.rate() * 100.0, comparison.display()));
+    Ok(RunOutcome { json: json_a, bbox_dir: bbox_a, gps, meta, ab: Some(AbOutcome { json_b, bbox_b, comparison }), prompt_file, gemini: cfg.gemini.clone(), backend: Some(cfg.backend.id()), label: cfg.label.clone() })
+}
+
+// everything one YOLO + Gemini pass needs
+struct Stage<'a> {
+    cfg: &'a RunConfig,
+    rep: &'a Reporter,
+    python: &'a str,
+    yolo_script: &'a Path,
+    run_input: &'a Path,
+    n_images: usize,
+    prompt_file: Option<&'a Path>,
+}
+
+impl Stage<'_> {
+    // YOLO over `run_input` into `run_bbox`
+    fn detect(&self, tag: &str, weights: &Path, run_bbox: &Path) -> Result<()> {
+        let (cfg, rep) = (self.cfg, self.rep);
+        fs::create_dir_all(run_bbox).ok();
+        rep.send(RunEvent::Command(ScriptStep::MkDir(run_bbox.to_path_buf())));
+
+        rep.send(RunEvent::Stage(format!("YOLO{}", tag), Some(self.n_images)));
+        if cfg.native_inference && native::is_onnx(&weights.to_string_lossy()) {
+            rep.log("[STEP] running YOLO inference (native ONNX)...\n");
+            let done = native::detect(weights, self.run_input, run_bbox, cfg.native_draw_boxes, &cfg.cancel, &|done| rep.send(RunEvent::Progress(done)))?;
+            rep.log(&format!("[OK] {} images through {} → {}\n", done.processed, weights.display(), run_bbox.display()));
+            if !done.skipped.is_empty() {
+                rep.log(&format!("[WARN] {} files could not be decoded and were skipped: {}\n", done.skipped.len(), done.skipped.join(", ")));
+            }
+        } else {
+            rep.log("[STEP] running YOLO inference...\n");
+            let res = self.detect_python(weights, run_bbox);
+            let have_images = !bbox_image_names(run_bbox).is_empty();
+            if let Some(e) = self.continue_after(res)? {
+                if !have_images { return Err(e.context("YOLO wrote no bbox images")); }
+                rep.log(&format!("[ERROR] YOLO {:#}; continuing with the bbox images it wrote\n", e));
+            }
+        }
+        Ok(())
+    }
+
+    fn detect_python(&self, weights: &Path, run_bbox: &Path) -> Result<()> {
+        let (cfg, rep) = (self.cfg, self.rep);
+        let mut cmd = Command::new(self.python);
+        cmd.arg(self.yolo_script)
+           .arg("--weights").arg(weights)
+           .arg("--source").arg(self.run_input)
+           .arg("--outdir").arg(run_bbox)
+           .arg("--classes_out").arg(classes::sidecar_path(run_bbox))
+           .arg("--save_txt")
+           .args(&cfg.extra_yolo_args);
+        exec_and_log_in_dir(cmd, "YOLO", &cfg.project_root, rep, &cfg.cancel, cfg.stage_timeout_secs.yolo, Some(parse_yolo_progress), || false)
+    }
+
+    // Ok(Some(e)) when the script exited non-zero and the run should carry on anyway
+    fn continue_after(&self, res: Result<()>) -> Result<Option<anyhow::Error>> {
+        match res {
+            Ok(()) => Ok(None),
+            Err(e) if self.cfg.stage_failure == StageFailure::Continue
+                && matches!(RunnerError::find(&e), Some(RunnerError::SubprocessFailed { .. })) => Ok(Some(e)),
+            Err(e) => Err(e),
+        }
+    }
+
+    // YOLO into `run_bbox`, then Gemini into `out_json`; `stream` forwards verdicts to the table as they arrive
+    #[allow(clippy::too_many_arguments)]
+    fn detect_and_judge(&self, tag: &str, weights: &Path, run_bbox: &Path, out_json: &Path, stream_path: &Path, stream: bool, partial: &mut Vec<PathBuf>) -> Result<()> {
+        partial.push(run_bbox.to_path_buf());
+        self.detect(tag, weights, run_bbox)?;
+        if stream { self.rep.send(RunEvent::BboxReady(run_bbox.to_path_buf())); }
+        partial.push(out_json.to_path_buf());
+        partial.push(stream_path.to_path_buf());
+        self.judge_all(tag, run_bbox, out_json, stream_path, stream)?;
+        let _ = fs::remove_file(stream_path);
+
+        // this pass is complete; keep its outputs even if a later one is cancelled
+        partial.clear();
+        Ok(())
+    }
+
+    // judgment over every bbox image, with the backend recorded in the result file
+    fn judge_all(&self, tag: &str, run_bbox: &Path, out_json: &Path, stream_path: &Path, stream: bool) -> Result<()> {
+        self.judge_cached(tag, run_bbox, out_json, stream_path, stream)?;
+        let data = fs::read_to_string(out_json).with_context(|| format!("failed to read {}", out_json.display()))?;
+        let mut root: serde_json::Value = serde_json::from_str(&data).map_err(|e| parse_error(out_json, e))?;
+        let Some(obj) = root.as_object_mut() else { anyhow::bail!("{} is not a JSON object", out_json.display()); };
+        obj.insert("backend".to_string(), self.cfg.backend.identity(&self.cfg.gemini));
+        write_atomic(out_json, serde_json::to_string_pretty(&root)?.as_bytes())
+    }
+
+    // Gemini over every bbox image; verdicts in the cache are reused and only the rest is sent
+    fn judge_cached(&self, tag: &str, run_bbox: &Path, out_json: &Path, stream_path: &Path, stream: bool) -> Result<()> {
+        let (cfg, rep) = (self.cfg, self.rep);
+        let names = bbox_image_names(run_bbox);
+        let cache = if cfg.bypass_cache { None } else {
+            let path = cache::cache_path(&cfg.project_root.join(".runner_work"));
+            match JudgeCache::open(&path) {
+                Ok(c) => Some(c),
+                Err(e) => { rep.log(&format!("[WARN] Gemini cache unavailable: {:#}\n", e)); None }
+            }
+        };
+        let Some(cache) = cache else {
+            return self.judge_names(tag, run_bbox, out_json, stream_path, &names, true, stream);
+        };
+        let mut prompt = self.prompt_file.and_then(|p| fs::read_to_string(p).ok()).unwrap_or_default();
+        // the built-in prompt (and how the reply is parsed) lives in the script and judge_common.py
+        let script = cfg.backend.script(&cfg.project_root);
+        for src in [script.with_file_name("judge_common.py"), script] {
+            prompt.push_str(&fs::read_to_string(src).unwrap_or_default());
+        }
+        let mut model = vec![cfg.backend.id().to_string()];
+        model.extend(cfg.backend.args());
+        model.extend(cfg.gemini.args().unwrap_or_default());
+        model.extend(cfg.extra_gemini_args.iter().cloned());
+        let scope = CacheScope::new(&prompt, &model.join(" "));
+        let key_of = |name: &str| fs::read(run_bbox.join(name)).ok().map(|b| scope.key(&b));
+
+        let mut hits = vec![];
+        let mut todo = vec![];
+        for name in &names {
+            match key_of(name).and_then(|k| cache.get(&k)) {
+                Some(result) => hits.push(serde_json::json!({ "image": name, "result": result, "cached": true })),
+                None => todo.push(name.clone()),
+            }
+        }
+        rep.log(&format!("[INFO] Gemini cache: {} hits, {} misses\n", hits.len(), todo.len()));
+        if hits.is_empty() {
+            self.judge_names(tag, run_bbox, out_json, stream_path, &names, true, stream)?;
+        } else {
+            // in the stream too, so a paused run doesn't ask for them again on resume
+            let lines: String = hits.iter().map(|h| format!("{}\n", h)).collect();
+            if let Err(e) = fs::OpenOptions::new().create(true).append(true).open(stream_path).and_then(|mut f| f.write_all(lines.as_bytes())) {
+                rep.log(&format!("[WARN] failed to record cached verdicts in {}: {}\n", stream_path.display(), e));
+            }
+            if stream {
+                for h in &hits {
+                    if let Ok(one) = serde_json::from_value::<WheelOne>(h.clone()) { rep.send(RunEvent::Result(one)); }
+                }
+            }
+            let new = if todo.is_empty() { vec![] } else {
+                self.judge_names(tag, run_bbox, out_json, stream_path, &todo, false, stream)?;
+                read_result_entries(out_json)?
+            };
+            let merged = checkpoint::merge_entries(&names, hits, new);
+            write_atomic(out_json, serde_json::to_string_pretty(&serde_json::json!({ "results": merged }))?.as_bytes())?;
+        }
+        // new verdicts only; an error or an undetermined verdict may well differ next time
+        let fresh: HashSet<&str> = todo.iter().map(|s| s.as_str()).collect();
+        let mut stored = 0;
+        for entry in read_result_entries(out_json)? {
+            let (Some(name), Some(result)) = (entry.get("image").and_then(|v| v.as_str()), entry.get("result")) else { continue; };
+            let undetermined = result.get("accessible").and_then(|a| a.as_bool()).is_none();
+            if !fresh.contains(name) || entry.get("error").is_some() || undetermined { continue; }
+            let Some(key) = key_of(name) else { continue; };
+            match cache.put(&key, result) {
+                Ok(()) => stored += 1,
+                Err(e) => { rep.log(&format!("[WARN] failed to update the Gemini cache: {:#}\n", e)); break; }
+            }
+        }
+        if stored > 0 { rep.log(&format!("[INFO] Gemini cache: stored {} new verdicts\n", stored)); }
+        Ok(())
+    }
+
+    // Gemini over `names` (`all` = every image in `run_bbox`), `gemini_chunk_size` images per call
+    // when set; the chunk results are merged into `out_json` in bbox-name order
+    #[allow(clippy::too_many_arguments)]
+    fn judge_names(&self, tag: &str, run_bbox: &Path, out_json: &Path, stream_path: &Path, names: &[String], all: bool, stream: bool) -> Result<()> {
+        let (cfg, rep) = (self.cfg, self.rep);
+        let size = cfg.gemini_chunk_size;
+        if size == 0 || names.len() <= size {
+            return self.judge(tag, run_bbox, out_json, stream_path, (!all).then_some(names), stream);
+        }
+        let chunks = split_chunks(names, size);
+        let mut entries = vec![];
+        // (chunk #, error, images without a verdict)
+        let mut failed: Vec<(usize, String, Vec<String>)> = vec![];
+        for (i, chunk) in chunks.iter().enumerate() {
+            check_pause(cfg)?;
+            rep.log(&format!("[INFO] Gemini chunk {}/{} ({} images)\n", i + 1, chunks.len(), chunk.len()));
+            let chunk_json = stream_path.with_extension(format!("chunk{}.json", i + 1));
+            let res = self.judge(&format!("{} {}/{}", tag, i + 1, chunks.len()), run_bbox, &chunk_json, stream_path, Some(chunk), stream)
+                .and_then(|()| read_result_entries(&chunk_json));
+            let _ = fs::remove_file(&chunk_json);
+            let e = match res {
+                Ok(new) => {
+                    entries.extend(new);
+                    // a pause or crash in a later chunk resumes from here
+                    if let Err(e) = checkpoint::save_chunks(stream_path, &entries) { rep.log(&format!("[WARN] {:#}\n", e)); }
+                    continue;
+                }
+                Err(e) => e,
+            };
+            let stopped = cfg.cancel.load(Ordering::Relaxed) || cfg.pause.load(Ordering::Relaxed);
+            if stopped || cfg.chunk_failure == ChunkFailure::Abort {
+                return Err(e.context(format!("Gemini chunk {}/{} failed", i + 1, chunks.len())));
+            }
+            // verdicts streamed before the failure are kept
+            let salvaged = streamed_entries(stream_path, chunk);
+            let judged: HashSet<&str> = salvaged.iter().filter_map(|v| v.get("image")?.as_str()).collect();
+            let skipped: Vec<String> = chunk.iter().filter(|n| !judged.contains(n.as_str())).cloned().collect();
+            rep.log(&format!("[ERROR] Gemini chunk {}/{} failed, continuing without its {} images: {:#}\n",
+                i + 1, chunks.len(), skipped.len(), e));
+            entries.extend(salvaged);
+            failed.push((i + 1, format!("{:#}", e), skipped));
+        }
+        if failed.len() == chunks.len() { anyhow::bail!("every Gemini chunk failed"); }
+        let merged = checkpoint::merge_entries(names, vec![], entries);
+        write_atomic(out_json, serde_json::to_string_pretty(&serde_json::json!({ "results": merged }))?.as_bytes())?;
+        let _ = fs::remove_file(checkpoint::chunks_path(stream_path));
+        if !failed.is_empty() {
+            let skipped: usize = failed.iter().map(|f| f.2.len()).sum();
+            let mut summary = format!("[WARN] {} of {} Gemini chunks failed; {} images have no verdict:\n", failed.len(), chunks.len(), skipped);
+            for (n, err, images) in &failed {
+                summary.push_str(&format!("[WARN]   chunk {} ({}): {}\n", n, err, images.join(", ")));
+            }
+            rep.log(&summary);
+        }
+        Ok(())
+    }
+
+    // Gemini over `run_bbox` (only the names in `only`, when given) into `out_json`,
+    // tailing the per-image JSONL stream for progress (and rows, when streaming is on)
+    fn judge(&self, tag: &str, run_bbox: &Path, out_json: &Path, stream_path: &Path, only: Option<&[String]>, stream: bool) -> Result<()> {
+        let (cfg, rep) = (self.cfg, self.rep);
+        let expected = only.map_or(self.n_images, |o| o.len());
+        rep.log(&format!("[STEP] running {} judgment...\n", cfg.backend.label()));
+        rep.send(RunEvent::Stage(format!("Gemini{}", tag), Some(expected)));
+        let gemini_started = SystemTime::now();
+        let mut cmd2 = cfg.backend.command(self.python, &cfg.project_root);
+        cmd2.arg("--images_dir").arg(run_bbox)
+            .arg("--out_json").arg(out_json);
+        // always written: run.py saves out_json only at the end, so the stream is the only progress
+        // signal, and a pause or resume needs to know which images are done
+        cmd2.arg("--stream_out").arg(stream_path);
+        if let Some(p) = self.prompt_file { cmd2.arg("--prompt_file").arg(p); }
+        cmd2.args(cfg.gemini.args()?);
+        let list_path = stream_path.with_extension("list.txt");
+        if let Some(names) = only {
+            fs::write(&list_path, names.join("\n")).with_context(|| format!("failed to write {}", list_path.display()))?;
+            cmd2.arg("--file_list").arg(&list_path);
+        }
+        cmd2.args(&cfg.extra_gemini_args);
+        // a resumed run appends to the existing stream; only new lines count
+        let mut tail = JsonlTail::at_end(stream_path);
+        let mut judged = 0;
+        // returns the number of verdicts so far
+        let mut forward = |tail: &mut JsonlTail| -> usize {
+            let fresh: Vec<WheelOne> = tail.poll().iter().filter_map(|l| parse_result_line(l)).collect();
+            if fresh.is_empty() { return judged; }
+            judged += fresh.len();
+            // with streaming off the rows come from the final load
+            if stream && cfg.stream_results {
+                for one in fresh { rep.send(RunEvent::Result(one)); }
+            }
+            rep.send(RunEvent::Progress(judged));
+            judged
+        };
+        // on pause, Gemini is stopped as soon as the image it is working on has its verdict, or after
+        // PAUSE_GRACE when none arrives (a hung request, a script that writes no stream); whatever
+        // was not judged yet is asked again on resume
+        let mut paused_at = None;
+        let res = exec_and_log_in_dir(cmd2, "Gemini", &cfg.project_root, rep, &cfg.cancel, cfg.stage_timeout_secs.gemini, None, || {
+            let n = forward(&mut tail);
+            if !cfg.pause.load(Ordering::Relaxed) { return false; }
+            let (judged_at_pause, since) = *paused_at.get_or_insert((n, Instant::now()));
+            n > judged_at_pause || since.elapsed() >= PAUSE_GRACE
+        });
+        if only.is_some() { let _ = fs::remove_file(&list_path); }
+        if let Some(e) = self.continue_after(res)? {
+            let names = only.map(|o| o.to_vec()).unwrap_or_else(|| bbox_image_names(run_bbox));
+            let n = mark_missing_as_errors(out_json, stream_path, &names, &format!("{:#}", e))?;
+            rep.log(&format!("[ERROR] {} {:#}; continuing, {} images without a verdict are marked as errors\n", cfg.backend.label(), e, n));
+        }
+        forward(&mut tail);
+        check_result_file(out_json, gemini_started, expected, rep)
+    }
+}
+
+// after a failed judgment script: the verdicts it wrote (out_json, else the stream) plus an
+// `{"image", "error"}` entry for every other image in `names`; returns how many were added
+fn mark_missing_as_errors(out_json: &Path, stream_path: &Path, names: &[String], error: &str) -> Result<usize> {
+    let mut entries = read_result_entries(out_json).unwrap_or_else(|_| streamed_entries(stream_path, names));
+    let done: HashSet<String> = entries.iter().filter_map(|v| v.get("image")?.as_str().map(str::to_string)).collect();
+    let missing: Vec<serde_json::Value> = names.iter()
+        .filter(|n| !done.contains(n.as_str()))
+        .map(|n| serde_json::json!({ "image": n, "error": format!("no verdict: {}", error) }))
+        .collect();
+    let n = missing.len();
+    entries = checkpoint::merge_entries(names, entries, missing);
+    write_atomic(out_json, serde_json::to_string_pretty(&serde_json::json!({ "results": entries }))?.as_bytes())?;
+    Ok(n)
+}
+
+// file names in a bbox dir (YOLO's labels are in a subfolder), sorted
+fn bbox_image_names(run_bbox: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(run_bbox).into_iter().flatten().flatten()
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+fn parse_error(path: &Path, e: impl std::fmt::Display) -> RunnerError {
+    RunnerError::ParseError { path: path.to_path_buf(), detail: e.to_string() }
+}
+
+fn read_result_entries(json: &Path) -> Result<Vec<serde_json::Value>> {
+    let data = fs::read_to_string(json).with_context(|| format!("failed to read {}", json.display()))?;
+    let root: serde_json::Value = serde_json::from_str(&data).map_err(|e| parse_error(json, e))?;
+    Ok(root.get("results").and_then(|v| v.as_array()).cloned().unwrap_or_default())
+}
+
+// the stream lines for `names`
+// `size` images per Gemini call; 0 = one call for all
+fn split_chunks(names: &[String], size: usize) -> Vec<&[String]> {
+    if size == 0 || names.is_empty() { return vec![names]; }
+    names.chunks(size).collect()
+}
+
+fn streamed_entries(stream_path: &Path, names: &[String]) -> Vec<serde_json::Value> {
+    let data = fs::read_to_string(stream_path).unwrap_or_default();
+    data.lines()
+        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l.trim()).ok())
+        .filter(|v| v.get("image").and_then(|i| i.as_str()).is_some_and(|i| names.iter().any(|n| n == i)))
+        .collect()
+}
+
+// longest a pause waits for the image in progress; run.py gives each request 60 s
+const PAUSE_GRACE: Duration = Duration::from_secs(70);
+
+// parse attempts for the result file; Python may still be flushing right after it exits
+const RESULT_READ_ATTEMPTS: u32 = 5;
+// filesystems with coarse mtimes (FAT: 2 s) can stamp a fresh file slightly before the run started
+const MTIME_SLACK: Duration = Duration::from_secs(2);
+
+// make sure `out_json` was written by this run and parses, retrying with backoff;
+// a result count different from the images sent is only a warning
+fn check_result_file(out_json: &Path, not_before: SystemTime, expected: usize, rep: &Reporter) -> Result<()> {
+    let mut delay = Duration::from_millis(100);
+    let mut attempt = 1;
+    loop {
+        let res = (|| -> Result<usize> {
+            let modified = fs::metadata(out_json).and_then(|m| m.modified())
+                .with_context(|| format!("result file not found: {}", out_json.display()))?;
+            if modified + MTIME_SLACK < not_before {
+                anyhow::bail!("{} is older than this run (stale file from an earlier run?)", out_json.display());
+            }
+            let data = fs::read_to_string(out_json).with_context(|| "failed to read result json")?;
+            let parsed = parse_result_file(&data).map_err(|e| parse_error(out_json, e))?;
+            Ok(parsed.results.len())
+        })();
+        match res {
+            Ok(n) => {
+                if n != expected {
+                    rep.log(&format!("[WARN] {} has {} results for {} images sent\n", out_json.display(), n, expected));
+                }
+                return Ok(());
+            }
+            Err(e) if attempt >= RESULT_READ_ATTEMPTS => return Err(e),
+            Err(_) => {
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+// run to completion, forwarding stdout/stderr lines to the log as they arrive; `on_tick` runs while waiting
+// and returning true stops the child there (a pause)
+// `progress` extracts a finished-image count from an output line;
+// the child is killed once it runs longer than `timeout_secs` (0 = no limit)
+#[allow(clippy::too_many_arguments)]
+fn exec_and_log_in_dir(mut cmd: Command, stage: &str, workdir: &Path, rep: &Reporter, cancel: &AtomicBool, timeout_secs: u64,
+                       progress: Option<fn(&str) -> Option<usize>>, mut on_tick: impl FnMut() -> bool) -> Result<()> {
+    let prefix = format!("[{}] ", stage.to_uppercase());
+    let line = CommandLine::from_command(&cmd, workdir);
+    rep.log(&format!("[INFO] $ {}\n", line.display()));
+    rep.send(RunEvent::Command(ScriptStep::Exec(line)));
+    // ask Python for UTF-8 whatever the console code page is; decode_output covers anything that still isn't
+    cmd.env("PYTHONIOENCODING", "utf-8");
+    cmd.current_dir(workdir).stdout(Stdio::piped()).stderr(Stdio::piped());
+    let mut child = cmd.spawn().with_context(|| "failed to spawn process")?;
+    let started = Instant::now();
+    let readers = [
+        pipe_lines(child.stdout.take(), prefix.clone(), rep, progress),
+        pipe_lines(child.stderr.take(), format!("{}[stderr] ", prefix), rep, progress),
+    ];
+    let status = loop {
+        if let Some(st) = child.try_wait()? { break st; }
+        if cancel.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            join_readers(readers);
+            return Err(RunnerError::Cancelled.into());
+        }
+        if timeout_secs > 0 && started.elapsed() >= Duration::from_secs(timeout_secs) {
+            let _ = child.kill();
+            let _ = child.wait();
+            join_readers(readers);
+            rep.log(&format!("[TIMEOUT] {} exceeded {}s\n", stage, timeout_secs));
+            return Err(RunnerError::Timeout { stage: stage.to_string(), secs: timeout_secs }.into());
+        }
+        if on_tick() {
+            let _ = child.kill();
+            let _ = child.wait();
+            join_readers(readers);
+            anyhow::bail!(PAUSED);
+        }
+        thread::sleep(Duration::from_millis(250));
+    };
+    let stderr_tail = join_readers(readers);
+    if !status.success() {
+        return Err(RunnerError::SubprocessFailed { stage: stage.to_string(), code: status.code(), stderr_tail }.into());
+    }
+    Ok(())
+}
+
+// waits for the stdout and stderr readers; returns the last lines of stderr
+fn join_readers(readers: [Option<thread::JoinHandle<Vec<String>>>; 2]) -> Vec<String> {
+    readers.into_iter().map(|r| r.and_then(|h| h.join().ok()).unwrap_or_default()).last().unwrap_or_default()
+}
+
+// forwards lines to the log; the thread returns the last STDERR_TAIL_LINES of them
+fn pipe_lines(src: Option<impl Read + Send + 'static>, prefix: String, rep: &Reporter,
+              progress: Option<fn(&str) -> Option<usize>>) -> Option<thread::JoinHandle<Vec<String>>> {
+    let src = src?;
+    let tx = rep.tx.clone();
+    let ctx = rep.ctx.clone();
+    Some(thread::spawn(move || {
+        let rep = Reporter { tx, ctx };
+        let fallback = console_encoding();
+        let mut warned = false;
+        let mut tail = std::collections::VecDeque::new();
+        for line in BufReader::new(src).split(b'\n').map_while(|l| l.ok()) {
+            let (line, lossy) = decode_output(&line, fallback);
+            if lossy && !warned {
+                rep.log(&format!("[WARN] {}output is not valid UTF-8; undecodable bytes are shown as \u{FFFD}\n", prefix));
+                warned = true;
+            }
+            let line = line.trim_end_matches('\r');
+            if let Some(done) = progress.and_then(|f| f(line)) { rep.send(RunEvent::Progress(done)); }
+            if let Some(u) = cost::parse_usage(line) { rep.send(RunEvent::Usage(u)); }
+            rep.log(&format!("{}{}\n", prefix, line));
+            if tail.len() == STDERR_TAIL_LINES { tail.pop_front(); }
+            tail.push_back(line.to_string());
+        }
+        tail.into()
+    }))
+}
+
+// ffmpeg into a scratch dir, then each frame is moved into run_input as <stem>_t<seconds>.jpg
+fn extract_frames(cfg: &RunConfig, rep: &Reporter, src: &Path, stem: &str, work_dir: &Path, run_input: &Path) -> Result<Vec<String>> {
+    let scratch = work_dir.join("frames").join(stem);
+    let _ = fs::remove_dir_all(&scratch);
+    fs::create_dir_all(&scratch).with_context(|| format!("failed to create {}", scratch.display()))?;
+    rep.send(RunEvent::Command(ScriptStep::MkDir(scratch.clone())));
+    let cmd = video::extract_command(&cfg.ffmpeg_bin, src, &scratch, cfg.frame_interval_secs);
+    exec_and_log_in_dir(cmd, "ffmpeg", &cfg.project_root, rep, &cfg.cancel, cfg.stage_timeout_secs.yolo, None, || false)?;
+    let mut names = vec![];
+    for (frame, name) in video::frames_in(&scratch, stem, cfg.frame_interval_secs) {
+        let dst = run_input.join(&name);
+        fs::rename(&frame, &dst).with_context(|| format!("failed to move {} into {}", frame.display(), run_input.display()))?;
+        rep.send(RunEvent::Command(ScriptStep::Copy(frame, dst)));
+        names.push(name);
+    }
+    let _ = fs::remove_dir_all(&scratch);
+    if names.is_empty() { anyhow::bail!("ffmpeg produced no frames"); }
+    Ok(names)
+}
+
+// UTF-8 first, then the OS code page (Windows Python may print cp949 paths);
+// the flag is set when bytes still had to be replaced with U+FFFD
+fn decode_output<'a>(bytes: &'a [u8], fallback: Option<&'static encoding_rs::Encoding>) -> (std::borrow::Cow<'a, str>, bool) {
+    if let Ok(s) = std::str::from_utf8(bytes) { return (s.into(), false); }
+    match fallback {
+        Some(enc) => {
+            let (s, _, had_errors) = enc.decode(bytes);
+            (s, had_errors)
+        }
+        None => (String::from_utf8_lossy(bytes), true),
+    }
+}
+
+#[cfg(windows)]
+fn console_encoding() -> Option<&'static encoding_rs::Encoding> {
+    extern "system" { fn GetACP() -> u32; }
+    // SAFETY: GetACP takes no arguments and only reads process state
+    let cp = unsafe { GetACP() };
+    let label = match cp {
+        949 => "euc-kr",
+        932 => "shift_jis",
+        936 => "gbk",
+        950 => "big5",
+        874 => "windows-874",
+        1250..=1258 => return encoding_rs::Encoding::for_label(format!("windows-{}", cp).as_bytes()),
+        _ => return None,
+    };
+    encoding_rs::Encoding::for_label(label.as_bytes())
+}
+
+#[cfg(not(windows))]
+fn console_encoding() -> Option<&'static encoding_rs::Encoding> {
+    None
+}
+
+// ultralytics prints "image 3/10 /path/x.jpg: 640x480 1 curb, 12.3ms" per image
+fn parse_yolo_progress(line: &str) -> Option<usize> {
+    let rest = &line[line.find("image ")? + "image ".len()..];
+    let (done, rest) = rest.split_once('/')?;
+    let total_len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    if total_len == 0 { return None; }
+    done.trim().parse().ok()
+}
+
+pub fn resolve_python(python_bin: &str, project_root: &Path) -> Result<String> {
+    let mut candidates: Vec<String> = vec![
+        project_root.join(".venv").join("bin").join("python").to_string_lossy().to_string(),
+        project_root.join(".venv").join("Scripts").join("python.exe").to_string_lossy().to_string(),
+    ];
+    if !python_bin.trim().is_empty() { candidates.push(python_bin.to_string()); }
+    candidates.push("python3".to_string());
+    candidates.push("python".to_string());
+
+    for cand in candidates {
+        if Command::new(&cand).arg("--version").output().is_ok() { return Ok(cand); }
+    }
+    Err(RunnerError::NoPython { project_root: project_root.to_path_buf() }.into())
+}
+
+// one line of the Gemini JSONL stream: {"image": "...", "result": {...}}
+pub fn parse_result_line(line: &str) -> Option<WheelOne> {
+    let line = line.trim();
+    if line.is_empty() { return None; }
+    serde_json::from_str(line).ok()
+}
+
+// reads complete lines appended to a file since the last poll
+struct JsonlTail {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+}
+
+impl JsonlTail {
+    // only lines appended from now on; an unterminated last line is closed first so new lines don't glue onto it
+    fn at_end(path: &Path) -> Self {
+        let mut offset = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if offset > 0 {
+            let ends_with_newline = fs::read(path).ok().and_then(|d| d.last().copied()) == Some(b'\n');
+            if !ends_with_newline {
+                if let Ok(mut f) = fs::OpenOptions::new().append(true).open(path) {
+                    if f.write_all(b"\n").is_ok() { offset += 1; }
+                }
+            }
+        }
+        Self { path: path.to_path_buf(), offset, partial: Vec::new() }
+    }
+
+    fn poll(&mut self) -> Vec<String> {
+        let Ok(mut f) = fs::File::open(&self.path) else { return vec![]; };
+        let mut buf = Vec::new();
+        if f.seek(SeekFrom::Start(self.offset)).is_err() || f.read_to_end(&mut buf).is_err() {
+            return vec![];
+        }
+        self.offset += buf.len() as u64;
+        self.partial.extend_from_slice(&buf);
+        // keep the unfinished last line (split on bytes so multi-byte chars are never cut)
+        let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') else { return vec![]; };
+        let done: Vec<u8> = self.partial.drain(..=end).collect();
+        done.split(|&b| b == b'\n')
+            .map(|l| String::from_utf8_lossy(l).to_string())
+            .filter(|l| !l.trim().is_empty())
+            .collect()
+    }
+}
+
+// unique file names for the copies in run_input, in `sources` order. Clashes get a counter
+// (`a_1.jpg`); with `prefix_folder` every file whose name occurs in more than one folder is
+// named after its folder first (`site1_a.jpg`, `site2_a.jpg`) so the verdict stays traceable.
+pub fn copy_names(sources: &[PathBuf], prefix_folder: bool) -> Vec<String> {
+    let base = |p: &PathBuf| p.file_name().map(|n| convert::copy_name(&n.to_string_lossy())).unwrap_or_default();
+    let mut dirs_per_name: HashMap<String, HashSet<&Path>> = HashMap::new();
+    for p in sources {
+        dirs_per_name.entry(base(p)).or_default().insert(p.parent().unwrap_or(Path::new("")));
+    }
+    let mut used: HashSet<String> = HashSet::new();
+    let mut out = vec![];
+    for p in sources {
+        let name = base(p);
+        let wanted = match p.parent().and_then(|d| d.file_name()) {
+            Some(dir) if prefix_folder && dirs_per_name[&name].len() > 1 => {
+                format!("{}_{}", sanitize_name(&dir.to_string_lossy()), name)
+            }
+            _ => name,
+        };
+        let mut final_name = wanted.clone();
+        let mut counter = 1;
+        while used.contains(&final_name) {
+            let (stem, ext) = split_name_ext(&wanted);
+            final_name = format!("{}_{}{}", stem, counter, ext);
+            counter += 1;
+        }
+        used.insert(final_name.clone());
+        out.push(final_name);
+    }
+    out
+}
+
+// letters (any script), digits, '-' and '_' are kept; anything else becomes '_'
+fn sanitize_name(s: &str) -> String {
+    s.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+pub fn split_name_ext(name: &str) -> (String, String) {
+    let p = Path::new(name);
+    let stem = p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| name.to_string());
+    let ext  = p.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (stem, ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cp949_paths_decode_with_the_code_page() {
+        let (euc, _, _) = encoding_rs::EUC_KR.encode("C:\\사진\\경사로.jpg");
+        assert_eq!(decode_output(&euc, Some(encoding_rs::EUC_KR)), ("C:\\사진\\경사로.jpg".into(), false));
+        assert_eq!(decode_output("경사로".as_bytes(), Some(encoding_rs::EUC_KR)), ("경사로".into(), false));
+        let (s, lossy) = decode_output(&euc, None);
+        assert!(lossy && s.contains('\u{FFFD}'));
+    }
+
+    #[test]
+    fn same_names_from_different_folders() {
+        let sources: Vec<PathBuf> = ["/s/site1/a.jpg", "/s/site 2/a.jpg", "/s/site1/b.jpg", "/t/site1/a.jpg", "/s/site1/a.jpg"]
+            .iter().map(PathBuf::from).collect();
+
+        assert_eq!(copy_names(&sources, false), vec!["a.jpg", "a_1.jpg", "b.jpg", "a_2.jpg", "a_3.jpg"]);
+        // the same folder name under two parents still needs the counter
+        assert_eq!(
+            copy_names(&sources, true),
+            vec!["site1_a.jpg", "site_2_a.jpg", "b.jpg", "site1_a_1.jpg", "site1_a_2.jpg"],
+        );
+    }
+
+    #[test]
+    fn gemini_params_omit_empty_fields_and_reject_out_of_range() {
+        assert!(GeminiParams::default().args().unwrap().is_empty());
+        let p = GeminiParams { model: " gemini-1.5-pro ".to_string(), temperature: String::new(), max_tokens: "2048".to_string() };
+        assert_eq!(p.args().unwrap(), vec!["--model", "gemini-1.5-pro", "--max_tokens", "2048"]);
+        for (t, n) in [("2.5", ""), ("warm", ""), ("", "0"), ("", "1.5")] {
+            let p = GeminiParams { temperature: t.to_string(), max_tokens: n.to_string(), ..Default::default() };
+            assert!(p.check().is_err(), "{:?} {:?}", t, n);
+        }
+    }
+
+    #[test]
+    fn a_failed_script_keeps_its_verdicts_and_marks_the_rest_as_errors() {
+        let dir = std::env::temp_dir().join("wheel_city_stage_failure_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let (out_json, stream) = (dir.join("result.json"), dir.join("stream.jsonl"));
+        // out_json was never written; one verdict made it into the stream
+        fs::write(&stream, "{\"image\": \"b.jpg\", \"result\": {\"accessible\": true, \"reason\": \"ramp\"}}\n").unwrap();
+        let names: Vec<String> = ["a.jpg", "b.jpg", "c.jpg"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(mark_missing_as_errors(&out_json, &stream, &names, "code 1").unwrap(), 2);
+
+        let parsed = parse_result_file(&fs::read_to_string(&out_json).unwrap()).unwrap();
+        let images: Vec<&str> = parsed.results.iter().map(|w| w.image.as_str()).collect();
+        assert_eq!(images, ["a.jpg", "b.jpg", "c.jpg"]);
+        assert_eq!(parsed.results[0].error.as_deref(), Some("no verdict: code 1"));
+        assert_eq!((parsed.results[1].error.is_none(), parsed.results[1].result.accessible), (true, Some(true)));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn chunks_cover_every_image_and_a_resume_skips_the_finished_ones() {
+        let names: Vec<String> = (0..7).map(|i| format!("img{}.jpg", i)).collect();
+        let chunks = split_chunks(&names, 3);
+        assert_eq!(chunks.iter().map(|c| c.len()).collect::<Vec<_>>(), [3, 3, 1]);
+        assert_eq!(chunks.concat(), names);
+        assert_eq!(split_chunks(&names, 0), [names.as_slice()]);
+
+        let verdict = |n: &str| serde_json::json!({ "image": n, "result": { "accessible": true, "reason": "r" } });
+        // each call answers in its own order; the merge is in bbox-name order
+        let mut entries = vec![];
+        for chunk in &chunks[..2] { entries.extend(chunk.iter().rev().map(|n| verdict(n))); }
+        let merged = checkpoint::merge_entries(&names, vec![], entries.clone());
+        let images: Vec<&str> = merged.iter().filter_map(|v| v["image"].as_str()).collect();
+        assert_eq!(images, names[..6].iter().map(String::as_str).collect::<Vec<_>>());
+
+        // the finished chunks are in the checkpoint even though the stream is gone
+        let dir = std::env::temp_dir().join(format!("wheel_city_chunks_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let stream = dir.join("stream.jsonl");
+        checkpoint::save_chunks(&stream, &entries).unwrap();
+        fs::create_dir_all(dir.join("bbox")).unwrap();
+        for n in &names { fs::write(dir.join("bbox").join(n), b"x").unwrap(); }
+        let cp = Checkpoint {
+            ts: "20240101_000000".to_string(), weights: PathBuf::from("best.pt"), run_input: dir.join("in"),
+            bbox_dir: dir.join("bbox"), out_json: dir.join("result.json"), stream_path: stream,
+            images: names.clone(), gps: Default::default(), meta: Default::default(), label: String::new(),
+            stage: CheckpointStage::Detected,
+        };
+        let plan = checkpoint::plan_resume(&cp, &cp.bbox_names(), cp.judged());
+        assert_eq!(plan.remaining, ["img6.jpg"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn heic_sources_are_copied_as_png() {
+        let sources: Vec<PathBuf> = ["/s/IMG_0001.HEIC", "/s/IMG_0001.png", "/s/b.tif"].iter().map(PathBuf::from).collect();
+        assert_eq!(copy_names(&sources, false), vec!["IMG_0001.png", "IMG_0001_1.png", "b.tif"]);
+    }
+}
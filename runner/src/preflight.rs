@@ -0,0 +1,289 @@
+// 실행 전 점검: 프로젝트 루트, Python, GPU(CUDA), 스크립트, 가중치, 입력 파일, ffmpeg(동영상이 있을 때), 디스크 공간, 판정 백엔드(API 키 또는 로컬 서버)
+// Validate 버튼은 모든 점검을 끝까지 돌려 한 번에 보여주고, 실제 실행은 같은 함수를 순서대로 씀
+use anyhow::{Context, Result};
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Mutex,
+};
+
+use crate::{backend::JudgmentBackend, error::RunnerError, expand_path, find_project_root, pipeline::resolve_python, video, IMAGE_EXTS};
+
+// headroom for result JSON, logs and the stream file on top of the image copies
+const DISK_MARGIN_BYTES: u64 = 50 * 1024 * 1024;
+
+pub struct CheckResult {
+    pub name: &'static str,
+    // Ok(detail) on pass, Err(reason) on failure
+    pub outcome: Result<String>,
+    // passed, but worth pointing out (e.g. YOLO will run on the CPU)
+    pub warning: bool,
+}
+
+pub fn project_root(configured: &str) -> Result<PathBuf> {
+    find_project_root(configured)
+        .ok_or_else(|| anyhow::anyhow!("Could not locate project root containing yolov8/run.py and gemini/run.py"))
+}
+
+pub fn python(python_bin: &str, project_root: &Path) -> Result<String> {
+    resolve_python(python_bin, project_root)
+}
+
+// what YOLO will run on, as seen by PyTorch in the run's interpreter
+#[derive(Debug, Clone, PartialEq)]
+pub enum Accel {
+    // device name
+    Cuda(String),
+    Cpu,
+}
+
+impl Accel {
+    pub fn describe(&self) -> String {
+        match self {
+            Accel::Cuda(name) => format!("CUDA on {}", name),
+            Accel::Cpu => "no CUDA GPU; YOLO will run on the CPU and be slow".to_string(),
+        }
+    }
+}
+
+const GPU_PROBE: &str = "import torch; ok = torch.cuda.is_available(); print(ok); print(torch.cuda.get_device_name(0) if ok else '')";
+
+// (interpreter, result) of earlier probes; importing torch takes seconds, so each interpreter
+// is asked once per session
+static GPU_PROBES: Mutex<Vec<(String, Accel)>> = Mutex::new(Vec::new());
+
+pub fn gpu(python: &str) -> Result<Accel> {
+    if let Some(a) = cached_gpu(python) { return Ok(a); }
+    let out = Command::new(python).args(["-c", GPU_PROBE]).output()
+        .with_context(|| format!("failed to run {}", python))?;
+    if !out.status.success() {
+        let err = String::from_utf8_lossy(&out.stderr);
+        anyhow::bail!("PyTorch is not importable by {}: {}", python, err.lines().last().unwrap_or("unknown error"));
+    }
+    let accel = parse_gpu_probe(&String::from_utf8_lossy(&out.stdout));
+    if let Ok(mut probes) = GPU_PROBES.lock() {
+        probes.retain(|(p, _)| p != python);
+        probes.push((python.to_string(), accel.clone()));
+    }
+    Ok(accel)
+}
+
+pub fn cached_gpu(python: &str) -> Option<Accel> {
+    GPU_PROBES.lock().ok()?.iter().find(|(p, _)| p == python).map(|(_, a)| a.clone())
+}
+
+// the most recent probe, for the status line next to the Python setting
+pub fn last_gpu() -> Option<(String, Accel)> {
+    GPU_PROBES.lock().ok()?.last().cloned()
+}
+
+fn parse_gpu_probe(stdout: &str) -> Accel {
+    let mut lines = stdout.lines().map(str::trim);
+    match (lines.next(), lines.next()) {
+        (Some("True"), name) => Accel::Cuda(name.filter(|n| !n.is_empty()).unwrap_or("unknown GPU").to_string()),
+        _ => Accel::Cpu,
+    }
+}
+
+// (yolov8/run.py, gemini/run.py)
+pub fn scripts(project_root: &Path) -> Result<(PathBuf, PathBuf)> {
+    let yolo = project_root.join("yolov8").join("run.py");
+    let gemini = project_root.join("gemini").join("run.py");
+    let missing: Vec<PathBuf> = [&yolo, &gemini].into_iter()
+        .filter(|p| !p.is_file())
+        .cloned()
+        .collect();
+    if !missing.is_empty() { return Err(RunnerError::MissingScript(missing).into()); }
+    Ok((yolo, gemini))
+}
+
+// absolute weights path and its size; an empty file is what an interrupted download leaves behind
+pub fn weights(project_root: &Path, weights_path: &str) -> Result<(PathBuf, u64)> {
+    let missing = |path: PathBuf, detail| -> Result<(PathBuf, u64)> { Err(RunnerError::MissingWeights { path, detail }.into()) };
+    if weights_path.trim().is_empty() { return missing(PathBuf::new(), "not set"); }
+    let abs = project_root.join(expand_path(weights_path));
+    let Ok(meta) = fs::metadata(&abs) else { return missing(abs, "not found"); };
+    if !meta.is_file() { return missing(abs, "is not a file"); }
+    if meta.len() == 0 { return missing(abs, "is empty"); }
+    Ok((abs, meta.len()))
+}
+
+// the picked files, or everything in input_images when nothing was picked
+pub fn sources(project_root: &Path, pending: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let v = if pending.is_empty() {
+        // only media: a README or .DS_Store in input_images is not something to run
+        let mut v = vec![];
+        if let Ok(rd) = fs::read_dir(project_root.join("input_images")) {
+            for e in rd.flatten() {
+                let p = e.path();
+                if p.is_file() && is_media(&p) { v.push(p); }
+            }
+        }
+        v.sort();
+        v
+    } else {
+        pending.to_vec()
+    };
+    if v.is_empty() {
+        anyhow::bail!("no images to run: add files (or drop them on the window), or put images in {}", project_root.join("input_images").display());
+    }
+    Ok(v)
+}
+
+fn is_media(p: &Path) -> bool {
+    video::is_video(p) || p.extension().and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTS.iter().any(|x| x.eq_ignore_ascii_case(e)))
+}
+
+// opens every source and reads its first byte (empty files fail too); returns the total size
+pub fn readable(sources: &[PathBuf]) -> Result<u64> {
+    let mut total = 0;
+    let mut bad = vec![];
+    for p in sources {
+        let res = File::open(p).and_then(|mut f| {
+            let len = f.metadata()?.len();
+            if f.read(&mut [0u8; 1])? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "empty file"));
+            }
+            Ok(len)
+        });
+        match res {
+            Ok(len) => total += len,
+            Err(e) => bad.push(format!("{} ({})", p.display(), e)),
+        }
+    }
+    if !bad.is_empty() {
+        anyhow::bail!("{} of {} unreadable: {}", bad.len(), sources.len(), bad.join(", "));
+    }
+    Ok(total)
+}
+
+// only needed when a video is among the sources, but then it must exist before the run starts
+pub fn ffmpeg(ffmpeg_bin: &str, sources: &[PathBuf]) -> Result<String> {
+    let videos = sources.iter().filter(|p| video::is_video(p)).count();
+    if videos == 0 { return Ok("not needed (no videos)".to_string()); }
+    video::check_ffmpeg(ffmpeg_bin).map(|v| format!("{} ({} videos)", v, videos))
+}
+
+// inputs are copied once and YOLO writes one bbox image per input
+pub fn space_needed(source_bytes: u64) -> u64 {
+    source_bytes.saturating_mul(2).saturating_add(DISK_MARGIN_BYTES)
+}
+
+// free bytes on the volume holding the project
+pub fn disk_space(project_root: &Path, needed: u64) -> Result<u64> {
+    let free = fs2::available_space(project_root)
+        .with_context(|| format!("could not query free space for {}", project_root.display()))?;
+    if free < needed {
+        anyhow::bail!("{} free, about {} needed", fmt_bytes(free), fmt_bytes(needed));
+    }
+    Ok(free)
+}
+
+// where the judgment script will find `key`: the environment, or a .env that python-dotenv
+// finds by walking up from gemini/
+pub fn env_key(project_root: &Path, key: &str) -> Result<String> {
+    if std::env::var(key).is_ok_and(|v| !v.trim().is_empty()) {
+        return Ok(format!("{} from the environment", key));
+    }
+    for dir in project_root.join("gemini").ancestors() {
+        let env_file = dir.join(".env");
+        let Ok(data) = fs::read_to_string(&env_file) else { continue; };
+        if data.lines().any(|l| dotenv_sets_key(l, key)) {
+            return Ok(format!("{} from {}", key, env_file.display()));
+        }
+    }
+    anyhow::bail!("{} is not set (environment or .env)", key)
+}
+
+fn dotenv_sets_key(line: &str, key: &str) -> bool {
+    let line = line.trim();
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let Some((k, v)) = line.split_once('=') else { return false; };
+    k.trim() == key && !v.trim().trim_matches(['"', '\'']).is_empty()
+}
+
+// every check, even after one fails, so all problems show up at once
+pub fn run_all(configured_root: &str, python_bin: &str, ffmpeg_bin: &str, weights_paths: &[&str], pending: &[PathBuf], backend: &dyn JudgmentBackend) -> Vec<CheckResult> {
+    let mut out = vec![];
+    let root = project_root(configured_root);
+    // later checks still run against the configured path when detection fails
+    let root_path = root.as_ref().cloned().unwrap_or_else(|_| expand_path(configured_root));
+    out.push(CheckResult { name: "project root", outcome: root.map(|p| p.display().to_string()), warning: false });
+    let python = python(python_bin, &root_path);
+    let gpu = python.as_ref().ok().map(|py| gpu(py));
+    out.push(CheckResult { name: "python", outcome: python, warning: false });
+    if let Some(gpu) = gpu {
+        let warning = matches!(gpu, Ok(Accel::Cpu));
+        out.push(CheckResult { name: "gpu", outcome: gpu.map(|a| a.describe()), warning });
+    }
+    out.push(CheckResult {
+        name: "scripts",
+        outcome: scripts(&root_path).map(|(y, g)| format!("{}, {}", y.display(), g.display())),
+        warning: false,
+    });
+    for w in weights_paths {
+        out.push(CheckResult {
+            name: "weights",
+            outcome: weights(&root_path, w).map(|(p, len)| format!("{} ({})", p.display(), fmt_bytes(len))),
+            warning: false,
+        });
+    }
+    let found = sources(&root_path, pending);
+    let (images, source_bytes) = match &found {
+        Ok(s) => match readable(s) {
+            Ok(bytes) => (Ok(format!("{} readable ({})", s.len(), fmt_bytes(bytes))), bytes),
+            Err(e) => (Err(e), 0),
+        },
+        Err(e) => (Err(anyhow::anyhow!("{:#}", e)), 0),
+    };
+    out.push(CheckResult { name: "images", outcome: images, warning: false });
+    out.push(CheckResult { name: "ffmpeg", outcome: ffmpeg(ffmpeg_bin, found.as_deref().unwrap_or_default()), warning: false });
+    let needed = space_needed(source_bytes);
+    out.push(CheckResult {
+        name: "disk space",
+        outcome: disk_space(&root_path, needed).map(|free| format!("{} free, about {} needed", fmt_bytes(free), fmt_bytes(needed))),
+        warning: false,
+    });
+    out.push(CheckResult { name: "judgment backend", outcome: backend.preflight(&root_path).map(|s| format!("{}: {}", backend.label(), s)), warning: false });
+    out
+}
+
+pub fn fmt_bytes(n: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut v = n as f64;
+    let mut unit = 0;
+    while v >= 1024.0 && unit < UNITS.len() - 1 {
+        v /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 { format!("{} B", n) } else { format!("{:.1} {}", v, UNITS[unit]) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_pending_files_and_no_images_in_input_images_is_an_error() {
+        let root = std::env::temp_dir().join("wheel_city_sources_test");
+        let _ = fs::remove_dir_all(&root);
+        // missing input_images, then one holding only non-images
+        assert!(sources(&root, &[]).is_err());
+        fs::create_dir_all(root.join("input_images")).unwrap();
+        fs::write(root.join("input_images").join(".DS_Store"), b"x").unwrap();
+        fs::write(root.join("input_images").join("notes.txt"), b"x").unwrap();
+        let err = sources(&root, &[]).unwrap_err().to_string();
+        assert!(err.starts_with("no images to run"), "{}", err);
+
+        fs::write(root.join("input_images").join("b.JPG"), b"x").unwrap();
+        fs::write(root.join("input_images").join("a.mp4"), b"x").unwrap();
+        let found = sources(&root, &[]).unwrap();
+        assert_eq!(found, vec![root.join("input_images").join("a.mp4"), root.join("input_images").join("b.JPG")]);
+        // picked files are taken as given
+        assert_eq!(sources(&root, &[PathBuf::from("/x/c.png")]).unwrap(), vec![PathBuf::from("/x/c.png")]);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,53 @@
+// Gemini 판정 프롬프트 템플릿: 실행마다 .runner_work/prompt_<ts>.txt 로 저장해 --prompt_file 로 넘김
+// {image_name} 같은 자리표시자는 gemini/run.py 가 채우므로 여기서는 손대지 않음
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub fn prompt_path(work_dir: &Path, ts: &str) -> PathBuf {
+    work_dir.join(format!("prompt_{}.txt", ts))
+}
+
+// written as-is, placeholders included
+pub fn write_prompt_file(work_dir: &Path, ts: &str, template: &str) -> Result<PathBuf> {
+    let path = prompt_path(work_dir, ts);
+    fs::create_dir_all(work_dir)?;
+    fs::write(&path, template).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+// (lines added, lines removed) going from `base` to `edited`
+pub fn line_diff(base: &str, edited: &str) -> (usize, usize) {
+    let a: Vec<&str> = base.lines().collect();
+    let b: Vec<&str> = edited.lines().collect();
+    // longest common subsequence of lines; prompts are short enough for the full table
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let common = lcs[0][0];
+    (b.len() - common, a.len() - common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_written_untouched_and_diffs_count_lines() {
+        let dir = std::env::temp_dir().join("wheel_city_prompt_test");
+        let template = "Judge {image_name}.\nAnswer in JSON: {\"accessible\": bool}";
+        let path = write_prompt_file(&dir, "20240101_000000", template).unwrap();
+        assert_eq!(path, dir.join("prompt_20240101_000000.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), template);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(line_diff("a\nb\nc", "a\nb\nc"), (0, 0));
+        assert_eq!(line_diff("a\nb\nc", "a\nB\nc\nd"), (2, 1));
+        assert_eq!(line_diff("", "x"), (1, 0));
+    }
+}
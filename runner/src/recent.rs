@@ -0,0 +1,26 @@
+// 최근에 열었거나 만든 결과 JSON, 최근 사용한 입력 폴더 목록 (settings.json 에 함께 저장)
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// entries kept per list
+pub const MAX_RECENT: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentEntry {
+    pub path: PathBuf,
+    // local time it was last used, "YYYY-MM-DD HH:MM"
+    pub at: String,
+}
+
+// moves `path` to the front (most recent first), dropping the oldest past MAX_RECENT
+pub fn touch(list: &mut Vec<RecentEntry>, path: &Path) {
+    list.retain(|e| e.path != path);
+    list.insert(0, RecentEntry { path: path.to_path_buf(), at: Local::now().format("%Y-%m-%d %H:%M").to_string() });
+    list.truncate(MAX_RECENT);
+}
+
+// drops entries whose file or folder is gone
+pub fn prune_missing(list: &mut Vec<RecentEntry>) {
+    list.retain(|e| e.path.exists());
+}
@@ -0,0 +1,172 @@
+// 결과 JSON 스키마 검사: 관대한 역직렬화가 조용히 null/빈 값으로 읽는 필드(빠진 accessible 등)와 타입 오류를 항목·필드별로 보고
+// Gemini 프롬프트를 다듬을 때 어느 항목의 어느 필드가 틀렸는지 바로 보이게 함
+use serde_json::Value;
+
+use crate::WheelOne;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    // the file doesn't load, or the value is read as something else (usually null)
+    Error,
+    // loads, but is probably not what the prompt meant (unknown fields, confidence outside 0–1)
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    // index into `results`; None for the file as a whole
+    pub entry: Option<usize>,
+    // e.g. "results[3].result.accessible"
+    pub path: String,
+    pub message: String,
+}
+
+const ENTRY_FIELDS: [&str; 7] = ["image", "result", "lat", "lon", "cached", "error", "label"];
+const RESULT_FIELDS: [&str; 4] = ["accessible", "reason", "confidence", "criteria"];
+
+// everything wrong with a result file, in file order; empty when it matches the schema
+pub fn validate(data: &str) -> Vec<Issue> {
+    let file = |path: &str, message: String| vec![Issue { severity: Severity::Error, entry: None, path: path.to_string(), message }];
+    let root: Value = match serde_json::from_str(data) {
+        Ok(v) => v,
+        Err(e) => return file("", format!("invalid JSON: {}", e)),
+    };
+    let Some(obj) = root.as_object() else { return file("", expected("an object", &root)); };
+    let results = match obj.get("results") {
+        Some(Value::Array(a)) => a,
+        Some(other) => return file("results", expected("an array", other)),
+        None => return file("results", "missing".to_string()),
+    };
+    let mut out = vec![];
+    for (i, v) in results.iter().enumerate() {
+        check_entry(i, v, &mut out);
+    }
+    out
+}
+
+fn kind(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn expected(what: &str, found: &Value) -> String {
+    format!("expected {}, found {}", what, kind(found))
+}
+
+fn check_entry(i: usize, v: &Value, out: &mut Vec<Issue>) {
+    let before = out.len();
+    let mut push = |severity, field: &str, message: String| {
+        out.push(Issue { severity, entry: Some(i), path: format!("results[{}]{}", i, field), message });
+    };
+    let Some(obj) = v.as_object() else {
+        push(Severity::Error, "", expected("an object", v));
+        return;
+    };
+    match obj.get("image") {
+        Some(Value::String(_)) => {}
+        Some(other) => push(Severity::Error, ".image", expected("a string", other)),
+        None => push(Severity::Error, ".image", "missing".to_string()),
+    }
+    for k in ["lat", "lon"] {
+        if let Some(v) = obj.get(k).filter(|v| !v.is_null() && !v.is_number()) {
+            push(Severity::Error, &format!(".{}", k), expected("a number", v));
+        }
+    }
+    if let Some(v) = obj.get("label").filter(|v| !v.is_null() && !v.is_string()) {
+        push(Severity::Error, ".label", expected("a string", v));
+    }
+    if let Some(v) = obj.get("cached").filter(|v| !v.is_boolean()) {
+        push(Severity::Error, ".cached", expected("true or false", v));
+    }
+    for k in obj.keys().filter(|k| !ENTRY_FIELDS.contains(&k.as_str())) {
+        push(Severity::Warning, &format!(".{}", k), "unknown field, ignored".to_string());
+    }
+    // an errored entry has no verdict to check
+    match (obj.get("error").filter(|e| !e.is_null()), obj.get("result")) {
+        (Some(Value::String(_)), _) => {}
+        (Some(other), _) => push(Severity::Error, ".error", expected("a string", other)),
+        (None, Some(Value::Object(r))) => {
+            match r.get("accessible") {
+                Some(Value::Bool(_) | Value::Null) => {}
+                Some(other) => push(Severity::Error, ".result.accessible", expected("true, false or null", other)),
+                None => push(Severity::Error, ".result.accessible", "missing; read as null (undetermined)".to_string()),
+            }
+            match r.get("reason") {
+                Some(Value::String(_)) => {}
+                Some(other) => push(Severity::Error, ".result.reason", expected("a string", other)),
+                None => push(Severity::Error, ".result.reason", "missing".to_string()),
+            }
+            match r.get("confidence") {
+                Some(Value::Number(n)) if !n.as_f64().is_some_and(|c| (0.0..=1.0).contains(&c)) => {
+                    push(Severity::Warning, ".result.confidence", format!("{} is outside 0–1", n));
+                }
+                Some(Value::Number(_) | Value::Null) | None => {}
+                Some(other) => push(Severity::Error, ".result.confidence", expected("a number", other)),
+            }
+            match r.get("criteria") {
+                Some(Value::Object(c)) => {
+                    for (k, v) in c.iter().filter(|(_, v)| !v.is_boolean() && !v.is_null()) {
+                        push(Severity::Error, &format!(".result.criteria.{}", k), expected("true, false or null", v));
+                    }
+                }
+                Some(other) => push(Severity::Error, ".result.criteria", expected("an object", other)),
+                None => {}
+            }
+            for k in r.keys().filter(|k| !RESULT_FIELDS.contains(&k.as_str())) {
+                push(Severity::Warning, &format!(".result.{}", k), "unknown field, ignored".to_string());
+            }
+        }
+        (None, Some(other)) => push(Severity::Error, ".result", expected("an object", other)),
+        (None, None) => push(Severity::Error, ".result", "missing; the row shows no verdict".to_string()),
+    }
+    // whatever else keeps the entry from loading
+    if out.len() == before {
+        if let Err(e) = serde_json::from_value::<WheelOne>(v.clone()) {
+            out.push(Issue { severity: Severity::Error, entry: Some(i), path: format!("results[{}]", i), message: e.to_string() });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_each_field_that_would_load_wrong() {
+        let data = r#"{"results": [
+            {"image": "ok.jpg", "result": {"accessible": true, "reason": "ramp", "confidence": 0.9, "criteria": {"ramp": true, "door": null}}},
+            {"image": "typo.jpg", "result": {"accesible": false, "reason": "steps"}},
+            {"image": "str.jpg", "result": {"accessible": "yes", "reason": "ramp", "confidence": 87, "criteria": {"door": "wide"}}},
+            {"image": "err.jpg", "error": "quota exceeded"},
+            {"image": 3},
+            "bare"
+        ]}"#;
+        let issues = validate(data);
+        let found: Vec<(Severity, &str)> = issues.iter().map(|i| (i.severity, i.path.as_str())).collect();
+        assert_eq!(found, [
+            (Severity::Error, "results[1].result.accessible"),
+            (Severity::Warning, "results[1].result.accesible"),
+            (Severity::Error, "results[2].result.accessible"),
+            (Severity::Warning, "results[2].result.confidence"),
+            (Severity::Error, "results[2].result.criteria.door"),
+            (Severity::Error, "results[4].image"),
+            (Severity::Error, "results[4].result"),
+            (Severity::Error, "results[5]"),
+        ]);
+        assert_eq!(issues[2].message, "expected true, false or null, found a string");
+        assert_eq!(issues[7].entry, Some(5));
+
+        assert!(validate(r#"{"results": []}"#).is_empty());
+        assert_eq!(validate(r#"{"result": []}"#)[0].path, "results");
+        let broken = validate("{\"results\": [\n  {\"image\": }\n]}");
+        assert_eq!(broken[0].entry, None);
+        assert!(broken[0].message.contains("line 2"), "{}", broken[0].message);
+    }
+}
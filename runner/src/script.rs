@@ -0,0 +1,177 @@
+// 실행한 명령들을 기록해 GUI 밖에서 같은 실행을 재현하는 셸 스크립트(.sh / .bat)로 변환
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+// variables the Python scripts read, recorded so the script sees the same values
+pub const PASSTHROUGH_ENV: &[&str] = &["GEMINI_MODEL"];
+
+#[derive(Debug, Clone)]
+pub struct CommandLine {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    // variables set explicitly on the Command (not the inherited environment)
+    pub envs: Vec<(String, String)>,
+}
+
+impl CommandLine {
+    pub fn from_command(cmd: &Command, cwd: &Path) -> Self {
+        Self {
+            program: cmd.get_program().to_string_lossy().to_string(),
+            args: cmd.get_args().map(|a| a.to_string_lossy().to_string()).collect(),
+            cwd: cwd.to_path_buf(),
+            envs: cmd.get_envs()
+                .filter_map(|(k, v)| Some((k.to_string_lossy().to_string(), v?.to_string_lossy().to_string())))
+                .collect(),
+        }
+    }
+
+    // one line, POSIX-quoted: `python3 yolov8/run.py --weights '/a b/best.pt' ...`
+    pub fn display(&self) -> String {
+        self.words(ScriptKind::Sh).join(" ")
+    }
+
+    fn words(&self, kind: ScriptKind) -> Vec<String> {
+        std::iter::once(&self.program).chain(&self.args).map(|w| quote(w, kind)).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ScriptStep {
+    Env(String, String),
+    MkDir(PathBuf),
+    Copy(PathBuf, PathBuf),
+    Exec(CommandLine),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptKind {
+    Sh,
+    Bat,
+}
+
+impl ScriptKind {
+    pub fn native() -> Self {
+        if cfg!(windows) { ScriptKind::Bat } else { ScriptKind::Sh }
+    }
+
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(e) if e.eq_ignore_ascii_case("bat") || e.eq_ignore_ascii_case("cmd") => ScriptKind::Bat,
+            _ => ScriptKind::Sh,
+        }
+    }
+}
+
+// the whole run as a script; `title` goes into the header comment
+pub fn render(steps: &[ScriptStep], kind: ScriptKind, title: &str) -> String {
+    let mut out = String::new();
+    let mut cwd: Option<&Path> = None;
+    match kind {
+        ScriptKind::Sh => {
+            out.push_str("#!/bin/sh\n");
+            out.push_str(&format!("# {}\n", title));
+            out.push_str("# gemini/run.py reads GOOGLE_API_KEY from the environment or the project's .env\n");
+            out.push_str("set -e\n\n");
+        }
+        ScriptKind::Bat => {
+            out.push_str("@echo off\r\n");
+            out.push_str(&format!("rem {}\r\n", title));
+            out.push_str("rem gemini/run.py reads GOOGLE_API_KEY from the environment or the project's .env\r\n\r\n");
+        }
+    }
+    let nl = if kind == ScriptKind::Bat { "\r\n" } else { "\n" };
+    for step in steps {
+        let line = match (step, kind) {
+            (ScriptStep::Env(k, v), ScriptKind::Sh) => format!("export {}={}", k, quote(v, kind)),
+            (ScriptStep::Env(k, v), ScriptKind::Bat) => format!("set \"{}={}\"", k, v.replace('%', "%%")),
+            (ScriptStep::MkDir(d), ScriptKind::Sh) => format!("mkdir -p {}", quote(&d.to_string_lossy(), kind)),
+            (ScriptStep::MkDir(d), ScriptKind::Bat) => {
+                let d = quote(&d.to_string_lossy(), kind);
+                format!("if not exist {} mkdir {}", d, d)
+            }
+            (ScriptStep::Copy(a, b), ScriptKind::Sh) => {
+                format!("cp {} {}", quote(&a.to_string_lossy(), kind), quote(&b.to_string_lossy(), kind))
+            }
+            (ScriptStep::Copy(a, b), ScriptKind::Bat) => {
+                format!("copy /y {} {} >nul", quote(&a.to_string_lossy(), kind), quote(&b.to_string_lossy(), kind))
+            }
+            (ScriptStep::Exec(c), _) => {
+                let mut s = String::new();
+                if cwd != Some(c.cwd.as_path()) {
+                    cwd = Some(c.cwd.as_path());
+                    let dir = quote(&c.cwd.to_string_lossy(), kind);
+                    s.push_str(&match kind {
+                        ScriptKind::Sh => format!("cd {}{}", dir, nl),
+                        ScriptKind::Bat => format!("cd /d {}{}", dir, nl),
+                    });
+                }
+                let envs: Vec<String> = c.envs.iter().map(|(k, v)| match kind {
+                    ScriptKind::Sh => format!("{}={} ", k, quote(v, kind)),
+                    ScriptKind::Bat => format!("set \"{}={}\"{}", k, v.replace('%', "%%"), nl),
+                }).collect();
+                let words = c.words(kind).join(" ");
+                match kind {
+                    ScriptKind::Sh => s.push_str(&format!("{}{}", envs.concat(), words)),
+                    ScriptKind::Bat => s.push_str(&format!("{}{} || exit /b 1", envs.concat(), words)),
+                }
+                s
+            }
+        };
+        out.push_str(&line);
+        out.push_str(nl);
+    }
+    out
+}
+
+// splits user-typed arguments like a shell would for quoting purposes: whitespace separates,
+// '..' and ".." group (so `--project "C:\My Data\out"` stays one word), backslashes are kept as-is
+pub fn split_args(s: &str) -> Result<Vec<String>, String> {
+    let mut out = vec![];
+    let mut cur = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    for c in s.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => cur.push(c),
+            (None, '\'' | '"') => { quote = Some(c); in_word = true; }
+            (None, c) if c.is_whitespace() => {
+                if in_word { out.push(std::mem::take(&mut cur)); }
+                in_word = false;
+            }
+            (None, c) => { cur.push(c); in_word = true; }
+        }
+    }
+    if let Some(q) = quote { return Err(format!("unterminated {} quote", q)); }
+    if in_word { out.push(cur); }
+    Ok(out)
+}
+
+fn quote(word: &str, kind: ScriptKind) -> String {
+    let safe = !word.is_empty()
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || "_-./:=@+,".contains(c) || (kind == ScriptKind::Bat && c == '\\'));
+    if safe { return word.to_string(); }
+    match kind {
+        ScriptKind::Sh => format!("'{}'", word.replace('\'', "'\\''")),
+        ScriptKind::Bat => format!("\"{}\"", word.replace('%', "%%").replace('"', "\"\"")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_args_keeps_quoted_paths_with_spaces() {
+        assert_eq!(
+            split_args(r#"--imgsz 1280 --project "C:\My Data\runs" --name 'a b' --x="" "#).unwrap(),
+            vec!["--imgsz", "1280", "--project", r"C:\My Data\runs", "--name", "a b", "--x="],
+        );
+        assert_eq!(split_args(r#"--device cpu"#).unwrap(), vec!["--device", "cpu"]);
+        assert!(split_args("  ").unwrap().is_empty());
+        assert!(split_args(r#"--project "unterminated"#).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+// 헤드리스 HTTP 서버 모드: POST /judge 로 이미지를 받아 파이프라인을 돌리고 판정 JSON을 반환
+// 실행: wheel_city_ai2_app --serve [addr] [--weights <pt>] [--python <bin>] [--project-root <dir>]
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::{
+    fs,
+    io::Read,
+    path::Path,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+    thread,
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{backend, error::RunnerError, find_project_root, parse_result_file, pipeline};
+
+const MAX_UPLOAD_BYTES: usize = 30 * 1024 * 1024;
+// requests waiting behind the one being processed; more than this gets 503
+const QUEUE_CAPACITY: usize = 16;
+
+pub struct ServeArgs {
+    pub addr: String,
+    pub weights_path: String,
+    pub python_bin: String,
+    pub project_root: String,
+}
+
+impl ServeArgs {
+    // Some(..) when `--serve` is present
+    pub fn from_env() -> Option<Self> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let pos = args.iter().position(|a| a == "--serve")?;
+        let value_of = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned();
+        let addr = args.get(pos + 1).filter(|a| !a.starts_with("--")).cloned()
+            .unwrap_or_else(|| "127.0.0.1:8787".to_string());
+        Some(Self {
+            addr,
+            weights_path: value_of("--weights").unwrap_or_else(|| "yolov8/train_result/ver14/weights/best.pt".to_string()),
+            python_bin: value_of("--python").unwrap_or_else(|| "python3".to_string()),
+            project_root: value_of("--project-root").unwrap_or_else(|| ".".to_string()),
+        })
+    }
+}
+
+pub fn serve(args: ServeArgs) -> Result<()> {
+    let project_root = find_project_root(&args.project_root)
+        .with_context(|| "Could not locate project root containing yolov8/run.py and gemini/run.py")?;
+    let server = Server::http(&args.addr).map_err(|e| anyhow::anyhow!("failed to bind {}: {}", args.addr, e))?;
+    eprintln!("[INFO] serving POST /judge on http://{} (project root: {})", args.addr, project_root.display());
+
+    // one worker drains the queue, so runs never share the work dir concurrently
+    let (tx, rx) = mpsc::sync_channel::<Request>(QUEUE_CAPACITY);
+    let worker = thread::spawn(move || {
+        for (n, req) in rx.into_iter().enumerate() {
+            handle_judge(req, n, &args, &project_root);
+        }
+    });
+
+    for req in server.incoming_requests() {
+        if !(req.method() == &Method::Post && req.url().split('?').next() == Some("/judge")) {
+            let _ = req.respond(json_response(404, r#"{"error":"use POST /judge with the image as the request body"}"#));
+            continue;
+        }
+        if let Err(mpsc::TrySendError::Full(req)) = tx.try_send(req) {
+            let _ = req.respond(json_response(503, r#"{"error":"queue is full, retry later"}"#));
+        }
+    }
+    drop(tx);
+    let _ = worker.join();
+    Ok(())
+}
+
+fn handle_judge(mut req: Request, n: usize, args: &ServeArgs, project_root: &Path) {
+    let res = (|| -> Result<String> {
+        let mut body = Vec::new();
+        req.as_reader().take(MAX_UPLOAD_BYTES as u64 + 1).read_to_end(&mut body)?;
+        if body.len() > MAX_UPLOAD_BYTES { anyhow::bail!("image larger than {} bytes", MAX_UPLOAD_BYTES); }
+        let format = image::guess_format(&body).with_context(|| "request body is not a recognized image")?;
+        let ext = format.extensions_str().first().copied().unwrap_or("img");
+
+        let upload_dir = project_root.join(".runner_work").join("server_uploads");
+        fs::create_dir_all(&upload_dir)?;
+        let upload = upload_dir.join(format!("upload_{}_{}.{}", Local::now().format("%Y%m%d_%H%M%S"), n, ext));
+        fs::write(&upload, &body)?;
+
+        let cfg = pipeline::RunConfig {
+            project_root: project_root.to_path_buf(),
+            python_bin: args.python_bin.clone(),
+            weights_path: args.weights_path.clone(),
+            pending_files: vec![upload.clone()],
+            weights_b: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            pause: Arc::new(AtomicBool::new(false)),
+            stage_timeout_secs: pipeline::StageTimeouts::default(),
+            stage_failure: pipeline::StageFailure::Abort,
+            retention_days: 0,
+            keep_last_runs: 0,
+            extra_yolo_args: vec![],
+            extra_gemini_args: vec![],
+            stream_results: false,
+            prefix_folder_names: false,
+            webhook: None,
+            export: None,
+            ffmpeg_bin: "ffmpeg".to_string(),
+            frame_interval_secs: 2,
+            gemini_chunk_size: 0,
+            chunk_failure: pipeline::ChunkFailure::Abort,
+            prompt_template: None,
+            bypass_cache: false,
+            gemini: pipeline::GeminiParams::default(),
+            backend: backend::find("gemini"),
+            native_inference: false,
+            native_draw_boxes: true,
+            label: String::new(),
+        };
+        let outcome = pipeline::run_headless(&cfg);
+        let _ = fs::remove_file(&upload);
+        let outcome = outcome?;
+
+        let parsed = parse_result_file(&fs::read_to_string(&outcome.json)?)
+            .with_context(|| "failed to parse result json")?;
+        let one = parsed.results.into_iter().next().with_context(|| "pipeline returned no result")?;
+        Ok(serde_json::to_string(&one.result)?)
+    })();
+    let resp = match res {
+        Ok(body) => json_response(200, &body),
+        Err(e) => {
+            let mut body = serde_json::json!({ "error": format!("{:#}", e) });
+            // the script's own complaint is usually more useful to the caller than the exit code
+            if let Some(RunnerError::SubprocessFailed { stderr_tail, .. }) = RunnerError::find(&e) {
+                body["stderr"] = stderr_tail.join("\n").into();
+            }
+            json_response(500, &body.to_string())
+        }
+    };
+    let _ = req.respond(resp);
+}
+
+fn json_response(code: u16, body: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(body)
+        .with_status_code(code)
+        .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json; charset=utf-8"[..]).unwrap())
+}
@@ -0,0 +1,219 @@
+// 앱 설정(경로, 팔레트, 타임아웃, 추가 인자, 최근 파일 등)을 플랫폼 설정 폴더의 settings.json 에 저장
+// 포터블 모드(실행 파일 옆 portable.flag 또는 --portable)에서는 실행 파일 옆 data/ 에 저장하고 플랫폼 폴더는 건드리지 않음
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{cost::{self, ModelPrice}, i18n::Lang, imgexport::ExportFormat, pipeline::{ChunkFailure, GeminiParams, StageFailure, StageTimeouts}, recent::RecentEntry, write_atomic};
+
+const APP_DIR: &str = "wheel_city_ai2";
+// next to the executable, turns on portable mode
+const PORTABLE_FLAG: &str = "portable.flag";
+
+// dimensions / file size / capture time; off until turned on
+pub const IMAGE_INFO_COLUMNS: [&str; 3] = ["size", "file", "taken"];
+
+pub const DEFAULT_MAP_URL: &str = "https://www.openstreetmap.org/?mlat={lat}&mlon={lon}#map=19/{lat}/{lon}";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub python_bin: String,
+    pub weights_path: String,
+    pub project_root: String,
+    pub colorblind_palette: bool,
+    pub stage_timeouts: StageTimeouts,
+    // whether a script exiting non-zero stops the run
+    pub stage_failure: StageFailure,
+    // images per Gemini call (0 = one call) and whether a failed call stops the run
+    pub gemini_chunk_size: usize,
+    pub chunk_failure: ChunkFailure,
+    // Gemini prompt sent with each run (empty = the script's own) and the version saved as default
+    pub prompt_template: String,
+    pub gemini: GeminiParams,
+    // id of the judgment backend (see backend::BACKENDS)
+    pub backend: String,
+    // run .onnx weights inside the runner instead of yolov8/run.py, optionally drawing the boxes
+    pub native_inference: bool,
+    pub native_draw_boxes: bool,
+    // USD per model for the cost estimate and the post-run cost
+    pub model_prices: Vec<ModelPrice>,
+    // ask Gemini again even for images with a cached verdict
+    pub bypass_cache: bool,
+    pub prompt_template_default: String,
+    pub retention_days: u32,
+    // bbox dirs of older runs are deleted after a successful run past this many (0 = keep all)
+    pub keep_last_n_runs: u32,
+    // appended to the yolov8/run.py / gemini/run.py command lines
+    pub extra_yolo_args: String,
+    pub extra_gemini_args: String,
+    // show verdicts in the table while Gemini is still running
+    pub stream_results: bool,
+    // start a run as soon as dropped files have all arrived
+    pub auto_run_on_drop: bool,
+    // see `pipeline::copy_names`
+    pub prefix_folder_names: bool,
+    // "Show on map" target; {lat}/{lon} are substituted (swap for Kakao/Naver maps)
+    pub map_url_template: String,
+    // results are POSTed here after each successful run when set
+    pub webhook_url: String,
+    pub webhook_token: String,
+    // "Run and export": target folder (may use ~ or $VARS) and the formats written there
+    pub run_export_dir: String,
+    pub run_export_csv: bool,
+    pub run_export_json: bool,
+    pub run_export_xlsx: bool,
+    // port of the LAN share server
+    pub share_port: u16,
+    // "Export images": re-encode target and JPEG quality
+    pub image_export_format: ExportFormat,
+    pub image_export_quality: u8,
+    // "Add from URL" refuses anything larger
+    pub download_max_mb: u32,
+    // videos in the inputs: ffmpeg to run and seconds between extracted frames
+    pub ffmpeg_bin: String,
+    pub frame_interval_secs: u32,
+    // results table columns turned off in "Columns ▾" (keys as in `column_widths`)
+    pub hidden_columns: BTreeSet<String>,
+    // replaced by `hidden_columns`; only read, to carry an old "Image info columns" choice over
+    #[serde(skip_serializing)]
+    pub show_image_info: bool,
+    // results as a thumbnail grid instead of the table
+    pub results_grid: bool,
+    // one dropped image runs at once and is shown alone, without the table
+    pub quick_mode: bool,
+    // results table columns the user resized: column key → width in points
+    pub column_widths: BTreeMap<String, f32>,
+    // UI language (log files and exports stay English)
+    pub lang: Lang,
+    // load result files that appear in results/ (see `watch`)
+    pub watch_results: bool,
+    // draw YOLO label boxes over the original in the preview (runs with label files only)
+    pub show_boxes: bool,
+    // display-only rule: verdicts with a stored confidence below `min_confidence` show as null
+    pub confidence_rule: bool,
+    pub min_confidence: f64,
+    // most recent first
+    pub recent_results: Vec<RecentEntry>,
+    pub recent_input_dirs: Vec<RecentEntry>,
+    // save the History tab's run list in the config dir
+    pub persist_history: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            python_bin: "python3".to_string(),
+            weights_path: "yolov8/train_result/ver14/weights/best.pt".to_string(),  // 학습한 모델중 가장 성능이 좋은 ver14 사용
+            project_root: ".".to_string(),
+            colorblind_palette: false,
+            stage_timeouts: StageTimeouts::default(),
+            stage_failure: StageFailure::Abort,
+            gemini_chunk_size: 0,
+            chunk_failure: ChunkFailure::Abort,
+            prompt_template: String::new(),
+            gemini: GeminiParams::default(),
+            backend: "gemini".to_string(),
+            native_inference: false,
+            native_draw_boxes: true,
+            model_prices: cost::default_prices(),
+            bypass_cache: false,
+            prompt_template_default: String::new(),
+            retention_days: 0,
+            keep_last_n_runs: 0,
+            extra_yolo_args: String::new(),
+            extra_gemini_args: String::new(),
+            stream_results: true,
+            auto_run_on_drop: false,
+            prefix_folder_names: false,
+            map_url_template: DEFAULT_MAP_URL.to_string(),
+            webhook_url: String::new(),
+            webhook_token: String::new(),
+            run_export_dir: String::new(),
+            run_export_csv: true,
+            run_export_json: true,
+            run_export_xlsx: false,
+            share_port: 8790,
+            image_export_format: ExportFormat::Original,
+            image_export_quality: 85,
+            download_max_mb: 30,
+            ffmpeg_bin: "ffmpeg".to_string(),
+            frame_interval_secs: 2,
+            hidden_columns: IMAGE_INFO_COLUMNS.iter().map(|k| k.to_string()).collect(),
+            show_image_info: false,
+            results_grid: false,
+            quick_mode: false,
+            column_widths: BTreeMap::new(),
+            lang: Lang::En,
+            watch_results: false,
+            show_boxes: true,
+            confidence_rule: false,
+            min_confidence: 0.7,
+            recent_results: vec![],
+            recent_input_dirs: vec![],
+            persist_history: false,
+        }
+    }
+}
+
+impl Settings {
+    // defaults when the file is missing or unreadable
+    pub fn load() -> Self {
+        let mut s: Self = settings_path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        if std::mem::take(&mut s.show_image_info) {
+            for k in IMAGE_INFO_COLUMNS { s.hidden_columns.remove(k); }
+        }
+        s
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = settings_path().with_context(|| "no config directory on this platform")?;
+        if let Some(dir) = path.parent() { fs::create_dir_all(dir)?; }
+        write_atomic(&path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+}
+
+// <exe dir>/data when the executable has a portable.flag beside it or was started with
+// --portable; decided once per process, so the two locations are never mixed within a session
+pub fn portable_dir() -> Option<&'static Path> {
+    static DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let exe_dir = env::current_exe().ok()?.parent()?.to_path_buf();
+        let flagged = env::args().skip(1).any(|a| a == "--portable") || exe_dir.join(PORTABLE_FLAG).is_file();
+        flagged.then(|| exe_dir.join("data"))
+    }).as_deref()
+}
+
+// the portable data dir, else %APPDATA%\wheel_city_ai2, ~/Library/Application Support/wheel_city_ai2,
+// or $XDG_CONFIG_HOME/wheel_city_ai2
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = portable_dir() { return Some(dir.to_path_buf()); }
+    let base = if cfg!(target_os = "windows") {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library").join("Application Support")
+    } else {
+        match env::var_os("XDG_CONFIG_HOME") {
+            Some(d) if !d.is_empty() => PathBuf::from(d),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        }
+    };
+    Some(base.join(APP_DIR))
+}
+
+// false on first launch (or when the file was deleted)
+pub fn exists() -> bool {
+    settings_path().is_some_and(|p| p.is_file())
+}
+
+fn settings_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("settings.json"))
+}
@@ -0,0 +1,167 @@
+// 같은 LAN의 팀원이 브라우저로 결과를 볼 수 있게 하는 읽기 전용 HTTP 서버
+// 결과 목록 페이지(/)와 bbox 이미지(/bbox/<name>)만 제공하고, bbox 폴더 밖의 파일은 내주지 않음
+use anyhow::Result;
+use std::{
+    fs,
+    net::{SocketAddr, UdpSocket},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+use tiny_http::{Header, Response, Server};
+
+use crate::{find_image_in_dir, percent_decode, WheelOne};
+
+#[derive(Default)]
+struct Snapshot {
+    results: Vec<WheelOne>,
+    bbox_dir: Option<PathBuf>,
+    title: String,
+}
+
+pub struct ShareServer {
+    server: Arc<Server>,
+    snapshot: Arc<Mutex<Snapshot>>,
+    worker: Option<JoinHandle<()>>,
+    pub url: String,
+}
+
+impl ShareServer {
+    // listens on every interface so other machines can connect
+    pub fn start(port: u16) -> Result<Self> {
+        let server = Arc::new(Server::http(("0.0.0.0", port)).map_err(|e| anyhow::anyhow!("failed to bind port {}: {}", port, e))?);
+        let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+        let worker = {
+            let (server, snapshot) = (server.clone(), snapshot.clone());
+            thread::spawn(move || {
+                for req in server.incoming_requests() {
+                    let resp = respond(req.url(), &snapshot.lock().unwrap());
+                    let _ = req.respond(resp);
+                }
+            })
+        };
+        let port = server.server_addr().to_ip().map(|a| a.port()).unwrap_or(port);
+        Ok(Self { server, snapshot, worker: Some(worker), url: format!("http://{}:{}/", lan_ip(), port) })
+    }
+
+    // what the page shows from now on
+    pub fn update(&self, results: &[WheelOne], bbox_dir: Option<&Path>, title: &str) {
+        let mut s = self.snapshot.lock().unwrap();
+        s.results = results.to_vec();
+        s.bbox_dir = bbox_dir.map(|d| d.to_path_buf());
+        s.title = title.to_string();
+    }
+
+    pub fn stop(&mut self) {
+        self.server.unblock();
+        if let Some(w) = self.worker.take() { let _ = w.join(); }
+    }
+}
+
+impl Drop for ShareServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// the address other machines reach us on; connecting a UDP socket sends nothing
+fn lan_ip() -> String {
+    UdpSocket::bind("0.0.0.0:0")
+        .and_then(|s| { s.connect("8.8.8.8:80")?; s.local_addr() })
+        .map(|a: SocketAddr| a.ip().to_string())
+        .unwrap_or_else(|_| "127.0.0.1".to_string())
+}
+
+fn respond(url: &str, snap: &Snapshot) -> Response<std::io::Cursor<Vec<u8>>> {
+    let path = url.split('?').next().unwrap_or("/");
+    if path == "/" || path == "/index.html" {
+        let page = results_page(&snap.results, &snap.title, |r| Some(format!("/bbox/{}", percent_encode(&r.image))));
+        return with_type(Response::from_string(page), "text/html; charset=utf-8");
+    }
+    if let Some(name) = path.strip_prefix("/bbox/") {
+        if let Some((data, mime)) = bbox_file(snap, &percent_decode(name)) {
+            return with_type(Response::from_data(data), mime);
+        }
+    }
+    Response::from_string("not found").with_status_code(404)
+}
+
+// only a plain file name, resolved inside the bbox dir; anything that escapes it is refused
+fn bbox_file(snap: &Snapshot, name: &str) -> Option<(Vec<u8>, &'static str)> {
+    if name.is_empty() || name.contains(['/', '\\']) || name == ".." || name == "." { return None; }
+    let dir = fs::canonicalize(snap.bbox_dir.as_ref()?).ok()?;
+    let file = fs::canonicalize(find_image_in_dir(&dir, name)?).ok()?;
+    if !file.starts_with(&dir) || !file.is_file() { return None; }
+    let mime = match file.extension()?.to_string_lossy().to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        _ => return None,
+    };
+    Some((fs::read(&file).ok()?, mime))
+}
+
+// one table row per result; `src` is the bbox image's URL (None: no image). Also the page of
+// the "Export starred" zip, with images next to it
+pub fn results_page(results: &[WheelOne], title: &str, src: impl Fn(&WheelOne) -> Option<String>) -> String {
+    let mut rows = String::new();
+    for r in results {
+        let verdict = match r.result.accessible {
+            Some(true) => "✔ accessible",
+            Some(false) => "✖ not accessible",
+            None => "? undetermined",
+        };
+        let img = src(r).map(|src| format!("<a href=\"{src}\"><img src=\"{src}\" loading=\"lazy\"></a>")).unwrap_or_default();
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            img, html_escape(&r.image), verdict, html_escape(&r.result.reason),
+        ));
+    }
+    format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\"><title>{title}</title><style>\
+         body{{font-family:sans-serif;margin:1em}}table{{border-collapse:collapse}}\
+         td,th{{border:1px solid #ccc;padding:4px 8px;vertical-align:top;text-align:left}}img{{max-width:160px}}\
+         </style></head><body><h2>{title}</h2><p>{n} results</p>\
+         <table><tr><th>BBox</th><th>Image</th><th>Accessible</th><th>Reason</th></tr>\n{rows}</table></body></html>",
+        title = html_escape(if title.is_empty() { "Wheel City AI 2 results" } else { title }),
+        n = results.len(),
+    )
+}
+
+fn with_type(resp: Response<std::io::Cursor<Vec<u8>>>, mime: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    resp.with_header(Header::from_bytes(&b"Content-Type"[..], mime.as_bytes()).unwrap())
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn percent_encode(s: &str) -> String {
+    s.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bbox_requests_stay_inside_the_dir() {
+        let root = std::env::temp_dir().join("wheel_city_share_test");
+        let bbox = root.join("bbox");
+        fs::create_dir_all(&bbox).unwrap();
+        fs::write(bbox.join("경사로 1.jpg"), b"jpg").unwrap();
+        fs::write(root.join("secret.jpg"), b"secret").unwrap();
+        let snap = Snapshot { bbox_dir: Some(bbox.clone()), ..Default::default() };
+
+        let name = percent_decode(&percent_encode("경사로 1.jpg"));
+        assert_eq!(bbox_file(&snap, &name).map(|(d, _)| d), Some(b"jpg".to_vec()));
+        for bad in ["../secret.jpg", "..%2Fsecret.jpg", "..", "%2E%2E%5Csecret.jpg", ""] {
+            assert!(bbox_file(&snap, &percent_decode(bad)).is_none(), "{}", bad);
+        }
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,97 @@
+// 결과 JSON 옆에 저장되는 사용자 부가 정보 (메모, 위치, 원본 이미지 정보 등)
+// result_<ts>.json → result_<ts>.sidecar.json
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::{cost::RunCost, exif_info::ImageMeta, pipeline::GeminiParams};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Sidecar {
+    // "Run label" given when the run started, e.g. "Gangnam station survey"; editable afterwards
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    // free-text notes about the run as a whole
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub run_notes: String,
+    // image name → free-text note
+    #[serde(default)]
+    pub notes: BTreeMap<String, String>,
+    // image name → (lat, lon) read from the original photo's EXIF
+    #[serde(default)]
+    pub gps: BTreeMap<String, (f64, f64)>,
+    // image name → size and capture time of the copied original
+    #[serde(default)]
+    pub meta: BTreeMap<String, ImageMeta>,
+    // images the user has checked while auditing this result
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub reviewed: BTreeSet<String>,
+    // images flagged as examples for the report ("Export starred")
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub starred: BTreeSet<String>,
+    // the prompt template file the run's Gemini pass used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_file: Option<PathBuf>,
+    // id of the judgment backend (gemini, openai, ollama); None for results from before backends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    // model settings given to gemini/run.py; None when all were left to the script
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini: Option<GeminiParams>,
+    // Gemini token usage and its cost, when the script reported usage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<RunCost>,
+}
+
+impl Sidecar {
+    pub fn path_for(result_json: &Path) -> PathBuf {
+        let stem = result_json.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        result_json.with_file_name(format!("{}.sidecar.json", stem))
+    }
+
+    // missing or unreadable sidecar → empty state
+    pub fn load(result_json: &Path) -> Self {
+        fs::read_to_string(Self::path_for(result_json))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&mut self, result_json: &Path) -> Result<()> {
+        self.notes.retain(|_, v| !v.trim().is_empty());
+        let path = Self::path_for(result_json);
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&path, data).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn gps(&self, image: &str) -> Option<(f64, f64)> {
+        self.gps.get(image).copied()
+    }
+
+    // result names may differ in extension from the copied input (x.webp → x.jpg)
+    pub fn meta(&self, image: &str) -> Option<&ImageMeta> {
+        self.meta.get(image).or_else(|| {
+            let stem = Path::new(image).file_stem()?;
+            self.meta.iter().find(|(k, _)| Path::new(k).file_stem() == Some(stem)).map(|(_, v)| v)
+        })
+    }
+
+    pub fn is_reviewed(&self, image: &str) -> bool {
+        self.reviewed.contains(image)
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        Some(self.label.trim()).filter(|s| !s.is_empty())
+    }
+
+    pub fn is_starred(&self, image: &str) -> bool {
+        self.starred.contains(image)
+    }
+
+    pub fn note(&self, image: &str) -> Option<&str> {
+        self.notes.get(image).map(|s| s.as_str()).filter(|s| !s.trim().is_empty())
+    }
+}
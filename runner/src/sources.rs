@@ -0,0 +1,75 @@
+// 실행마다 결과 이미지 이름 → 원본 사진의 절대 경로 (results/sources_<ts>.json)
+// 복사 단계에서 이름이 바뀌어도(중복 접미사, 변환) 결과 행에서 원본을 다시 찾을 수 있게 함
+use anyhow::Result;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::write_atomic;
+
+// `result_<ts>.json` and the A/B `result_<ts>_a.json` share the run's `sources_<ts>.json`
+pub fn path_for(result_json: &Path) -> Option<PathBuf> {
+    let stem = result_json.file_stem()?.to_string_lossy().to_string();
+    let rest = stem.strip_prefix("result_")?;
+    // YYYYMMDD_HHMMSS
+    let ts = rest.get(..15).filter(|ts| ts.chars().all(|c| c.is_ascii_digit() || c == '_'))?;
+    if !(rest.len() == 15 || rest[15..].starts_with('_')) { return None; }
+    Some(result_json.with_file_name(format!("sources_{}.json", ts)))
+}
+
+pub fn write(results_dir: &Path, ts: &str, sources: &BTreeMap<String, PathBuf>) -> Result<PathBuf> {
+    let path = results_dir.join(format!("sources_{}.json", ts));
+    write_atomic(&path, serde_json::to_string_pretty(sources)?.as_bytes())?;
+    Ok(path)
+}
+
+// empty for results from before the file was written, or not from a run
+pub fn load(result_json: &Path) -> BTreeMap<String, PathBuf> {
+    path_for(result_json)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+// the copied name, else the same stem (YOLO may re-encode x.webp as x.jpg); `img_1` never
+// matches `img`, so renamed duplicates keep their own original
+pub fn original<'a>(sources: &'a BTreeMap<String, PathBuf>, image: &str) -> Option<&'a Path> {
+    if let Some(p) = sources.get(image) { return Some(p); }
+    let stem = Path::new(image).file_stem()?;
+    sources.iter()
+        .find(|(k, _)| Path::new(k).file_stem() == Some(stem))
+        .map(|(_, p)| p.as_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::copy_names;
+
+    #[test]
+    fn renamed_duplicates_map_back_to_their_own_original() {
+        let inputs = [PathBuf::from("/photos/a/IMG_1.jpg"), PathBuf::from("/photos/b/IMG_1.jpg"), PathBuf::from("/photos/c/door.heic")];
+        let names = copy_names(&inputs, false);
+        assert_ne!(names[0], names[1]);
+        let map: BTreeMap<String, PathBuf> = names.iter().cloned().zip(inputs.iter().cloned()).collect();
+
+        let dir = std::env::temp_dir().join(format!("wheel_city_sources_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let written = write(&dir, "20240501_101500", &map).unwrap();
+        let json = dir.join("result_20240501_101500.json");
+        assert_eq!(path_for(&json), Some(written.clone()));
+        assert_eq!(path_for(&dir.join("result_20240501_101500_b.json")), Some(written));
+        assert_eq!(path_for(&dir.join("result_merged_20240501_101500.json")), None);
+
+        let loaded = load(&json);
+        assert_eq!(original(&loaded, &names[0]), Some(inputs[0].as_path()));
+        assert_eq!(original(&loaded, &names[1]), Some(inputs[1].as_path()));
+        // the bbox image of the converted HEIC is a .jpg
+        let door = Path::new(&names[2]).with_extension("jpg");
+        assert_eq!(original(&loaded, &door.to_string_lossy()), Some(inputs[2].as_path()));
+        assert_eq!(original(&loaded, "other.jpg"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
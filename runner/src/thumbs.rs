@@ -0,0 +1,133 @@
+// 표 썸네일: JPEG 는 DCT 축소 디코딩(1/2~1/8)으로 바로 작게 읽고, 결과를
+// .runner_work/thumbs/<run>/ 에 PNG 로 저장해 같은 실행을 다시 열 때는 디코딩을 건너뜀
+use image::{codecs::jpeg::JpegDecoder, DynamicImage, RgbaImage};
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+// .runner_work/bbox/<ts> → .runner_work/thumbs/<ts>; None for bbox dirs outside the work dir
+pub fn cache_dir(bbox_dir: &Path) -> Option<PathBuf> {
+    let ts = bbox_dir.file_name()?;
+    let parent = bbox_dir.parent()?;
+    if parent.file_name()? != "bbox" { return None; }
+    Some(parent.parent()?.join("thumbs").join(ts))
+}
+
+fn cache_path(cache_dir: &Path, image: &Path, max_w: u32, max_h: u32) -> Option<PathBuf> {
+    let stem = image.file_stem()?.to_string_lossy();
+    let ext = image.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+    // the extension is kept in the name so x.jpg and x.png don't share a thumbnail
+    Some(cache_dir.join(format!("{}_{}_{}x{}.png", stem, ext, max_w, max_h)))
+}
+
+// fits `max_w` x `max_h` pixels (never enlarged); `cached` tells whether it came from disk
+pub struct Thumb {
+    pub image: RgbaImage,
+    pub cached: bool,
+    pub took: Duration,
+}
+
+pub fn load(path: &Path, max_w: u32, max_h: u32, cache_dir: Option<&Path>) -> Option<Thumb> {
+    let start = Instant::now();
+    let cached = cache_dir.and_then(|d| cache_path(d, path, max_w, max_h));
+    if let Some(c) = cached.as_deref().filter(|c| is_fresh(c, path)) {
+        if let Ok(img) = image::open(c) {
+            return Some(Thumb { image: img.to_rgba8(), cached: true, took: start.elapsed() });
+        }
+    }
+    let img = decode_scaled(path, max_w, max_h)?;
+    let scale = (max_w as f32 / img.width() as f32).min(max_h as f32 / img.height() as f32);
+    let rgba = if scale >= 1.0 {
+        img.to_rgba8()
+    } else {
+        let (w, h) = (((img.width() as f32 * scale).round() as u32).max(1), ((img.height() as f32 * scale).round() as u32).max(1));
+        image::imageops::thumbnail(&img, w, h)
+    };
+    // best effort: a read-only work dir just means decoding again next time
+    if let Some(c) = &cached {
+        if c.parent().is_some_and(|d| fs::create_dir_all(d).is_ok()) { let _ = rgba.save(c); }
+    }
+    Some(Thumb { image: rgba, cached: false, took: start.elapsed() })
+}
+
+// decode timings of a burst of thumbnail loads, logged once the table stops loading
+#[derive(Debug, Default)]
+pub struct Stats {
+    decoded: u32,
+    decode_time: Duration,
+    cached: u32,
+    cache_time: Duration,
+}
+
+impl Stats {
+    pub fn add(&mut self, t: &Thumb) {
+        if t.cached {
+            self.cached += 1;
+            self.cache_time += t.took;
+        } else {
+            self.decoded += 1;
+            self.decode_time += t.took;
+        }
+    }
+
+    // "12 thumbnails: 9 decoded (avg 31 ms), 3 from cache (avg 2 ms)"; None when nothing was loaded
+    pub fn take_summary(&mut self) -> Option<String> {
+        let s = std::mem::take(self);
+        if s.decoded + s.cached == 0 { return None; }
+        let avg = |t: Duration, n: u32| t.as_millis() / u128::from(n.max(1));
+        Some(format!("{} thumbnails: {} decoded (avg {} ms), {} from cache (avg {} ms)",
+            s.decoded + s.cached, s.decoded, avg(s.decode_time, s.decoded), s.cached, avg(s.cache_time, s.cached)))
+    }
+}
+
+// the cached file is newer than the image (a regenerated bbox image invalidates it)
+fn is_fresh(cached: &Path, source: &Path) -> bool {
+    let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+    matches!((modified(cached), modified(source)), (Some(c), Some(s)) if c >= s)
+}
+
+// JPEGs are decoded at the smallest DCT scale (1/8..1) that still covers the target size
+fn decode_scaled(path: &Path, max_w: u32, max_h: u32) -> Option<DynamicImage> {
+    let is_jpeg = path.extension().and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("jpg") || e.eq_ignore_ascii_case("jpeg"));
+    if is_jpeg {
+        let scaled = File::open(path).ok()
+            .and_then(|f| JpegDecoder::new(BufReader::new(f)).ok())
+            .and_then(|mut d| {
+                d.scale(max_w.min(u16::MAX.into()) as u16, max_h.min(u16::MAX.into()) as u16).ok()?;
+                DynamicImage::from_decoder(d).ok()
+            });
+        // e.g. a PNG saved with a .jpg name
+        if scaled.is_some() { return scaled; }
+    }
+    image::open(path).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jpeg_thumbnails_are_small_and_cached() {
+        let root = std::env::temp_dir().join("wheel_city_thumbs_test");
+        let _ = fs::remove_dir_all(&root);
+        let bbox = root.join(".runner_work").join("bbox").join("20240101_000000");
+        fs::create_dir_all(&bbox).unwrap();
+        let src = bbox.join("street.jpg");
+        image::RgbImage::from_fn(1600, 1200, |x, y| image::Rgb([(x % 256) as u8, (y % 256) as u8, 90])).save(&src).unwrap();
+        let dir = cache_dir(&bbox).unwrap();
+        assert_eq!(dir, root.join(".runner_work").join("thumbs").join("20240101_000000"));
+
+        let first = load(&src, 144, 108, Some(&dir)).unwrap();
+        assert!(!first.cached);
+        assert_eq!(first.image.dimensions(), (144, 108));
+        let again = load(&src, 144, 108, Some(&dir)).unwrap();
+        assert!(again.cached);
+        assert_eq!(again.image.dimensions(), (144, 108));
+        assert!(cache_dir(&root.join("elsewhere")).is_none());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,85 @@
+// 실행 중 단계별 경과 시간과 남은 시간(ETA) 추정
+use std::time::{Duration, Instant};
+
+// images done before the per-image average is trusted
+const MIN_SAMPLES_FOR_ETA: usize = 2;
+
+struct StageTime {
+    name: String,
+    elapsed: Duration,
+    items: usize,
+}
+
+pub struct RunTiming {
+    stage: String,
+    started: Instant,
+    total: Option<usize>,
+    done: usize,
+    finished: Vec<StageTime>,
+}
+
+impl RunTiming {
+    pub fn new() -> Self {
+        Self { stage: "prepare".to_string(), started: Instant::now(), total: None, done: 0, finished: vec![] }
+    }
+
+    pub fn start_stage(&mut self, name: &str, total: Option<usize>) {
+        self.close_stage();
+        self.stage = name.to_string();
+        self.started = Instant::now();
+        self.total = total;
+        self.done = 0;
+    }
+
+    pub fn progress(&mut self, done: usize) {
+        self.done = done;
+    }
+
+    // "YOLO: 00:42 elapsed · 3/10 · ETA 01:10"
+    pub fn status_line(&self) -> String {
+        let elapsed = self.started.elapsed();
+        let mut s = format!("{}: {} elapsed", self.stage, fmt_mmss(elapsed));
+        if let Some(total) = self.total {
+            s.push_str(&format!(" · {}/{}", self.done, total));
+            if self.done < MIN_SAMPLES_FOR_ETA.min(total) {
+                s.push_str(" · ETA estimating…");
+            } else if self.done < total {
+                let per_item = elapsed.as_secs_f64() / self.done as f64;
+                let remaining = Duration::from_secs_f64(per_item * (total - self.done) as f64);
+                s.push_str(&format!(" · ETA {}", fmt_mmss(remaining)));
+            }
+        }
+        s
+    }
+
+    // "copy 3.1s, YOLO 94.2s (2.5 s/img), Gemini 181.0s (4.8 s/img)"
+    pub fn finish(mut self) -> String {
+        self.close_stage();
+        self.finished.iter()
+            .filter(|st| st.name != "prepare")
+            .map(|st| {
+                let secs = st.elapsed.as_secs_f64();
+                if st.items > 0 && st.name != "copy" {
+                    format!("{} {:.1}s ({:.1} s/img)", st.name, secs, secs / st.items as f64)
+                } else {
+                    format!("{} {:.1}s", st.name, secs)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn close_stage(&mut self) {
+        let items = if self.done > 0 { self.done } else { self.total.unwrap_or(0) };
+        self.finished.push(StageTime { name: self.stage.clone(), elapsed: self.started.elapsed(), items });
+    }
+}
+
+pub fn fmt_mmss(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
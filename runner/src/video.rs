@@ -0,0 +1,68 @@
+// 거리 동영상(.mp4/.mov)에서 ffmpeg로 N초마다 프레임을 뽑아 이미지 입력으로 사용
+// 프레임 이름은 <stem>_t000012.jpg (영상 시작부터의 초)
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+pub const VIDEO_EXTS: &[&str] = &["mp4", "mov"];
+
+pub fn is_video(p: &Path) -> bool {
+    p.extension().and_then(|e| e.to_str())
+        .is_some_and(|e| VIDEO_EXTS.iter().any(|x| x.eq_ignore_ascii_case(e)))
+}
+
+// the ffmpeg command that answers `-version`; its first output line on success
+pub fn check_ffmpeg(ffmpeg_bin: &str) -> Result<String> {
+    let bin = if ffmpeg_bin.trim().is_empty() { "ffmpeg" } else { ffmpeg_bin.trim() };
+    let out = Command::new(bin).arg("-version").output()
+        .with_context(|| format!("ffmpeg not found ({}); install it or set its path in the Video section", bin))?;
+    if !out.status.success() { anyhow::bail!("{} -version failed; set a working ffmpeg path in the Video section", bin); }
+    let first = String::from_utf8_lossy(&out.stdout).lines().next().unwrap_or_default().to_string();
+    Ok(first)
+}
+
+// one JPEG every `interval_secs` seconds, numbered from 1 by ffmpeg
+pub fn extract_command(ffmpeg_bin: &str, video: &Path, out_dir: &Path, interval_secs: u32) -> Command {
+    let mut cmd = Command::new(if ffmpeg_bin.trim().is_empty() { "ffmpeg" } else { ffmpeg_bin.trim() });
+    cmd.args(["-hide_banner", "-loglevel", "error", "-nostdin", "-y", "-i"]).arg(video)
+       .arg("-vf").arg(format!("fps=1/{}", interval_secs.max(1)))
+       .args(["-q:v", "2"])
+       .arg(out_dir.join("frame_%06d.jpg"));
+    cmd
+}
+
+// ffmpeg's frame_000003.jpg → <stem>_t000004.jpg with a 2 s interval (frame n is taken at (n - 1) * interval)
+pub fn frame_name(ffmpeg_name: &str, stem: &str, interval_secs: u32) -> Option<String> {
+    let n: u64 = ffmpeg_name.strip_prefix("frame_")?.strip_suffix(".jpg")?.parse().ok()?;
+    let t = n.checked_sub(1)? * u64::from(interval_secs.max(1));
+    Some(format!("{}_t{:06}.jpg", stem, t))
+}
+
+// what ffmpeg wrote into `dir`, as (file, final name), in frame order
+pub fn frames_in(dir: &Path, stem: &str, interval_secs: u32) -> Vec<(PathBuf, String)> {
+    let mut v: Vec<(PathBuf, String)> = fs::read_dir(dir).into_iter().flatten().flatten()
+        .filter_map(|e| {
+            let name = frame_name(&e.file_name().to_string_lossy(), stem, interval_secs)?;
+            Some((e.path(), name))
+        })
+        .collect();
+    v.sort_by(|a, b| a.1.cmp(&b.1));
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_names_carry_the_timestamp() {
+        assert_eq!(frame_name("frame_000001.jpg", "street", 2).as_deref(), Some("street_t000000.jpg"));
+        assert_eq!(frame_name("frame_000007.jpg", "street", 2).as_deref(), Some("street_t000012.jpg"));
+        assert_eq!(frame_name("frame_000000.jpg", "street", 2), None);
+        assert_eq!(frame_name("other.jpg", "street", 2), None);
+        assert!(is_video(Path::new("a/B.MOV")) && !is_video(Path::new("a/b.jpg")));
+    }
+}
@@ -0,0 +1,85 @@
+// results/ 폴더 감시: 외부(헤드리스 모드, 다른 터미널)에서 만든 result_*.json 이 다 쓰이면 자동으로 불러옴
+// 파일 이벤트는 몰려서 오므로 크기가 SETTLE 동안 그대로일 때만 완성된 것으로 봄
+use anyhow::{Context, Result};
+use eframe::egui;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::{Duration, Instant},
+};
+
+// how long a result file's size must stay the same before it is loaded
+const SETTLE: Duration = Duration::from_millis(1500);
+
+pub struct ResultsWatcher {
+    pub dir: PathBuf,
+    // dropping it stops the OS watch
+    _watcher: RecommendedWatcher,
+    rx: Receiver<PathBuf>,
+    // result file → (last seen size, since when)
+    pending: HashMap<PathBuf, (u64, Instant)>,
+}
+
+// result_<ts>.json, not one of its result_<ts>.<kind>.json sidecars (saved on every review or note)
+pub fn is_result_file(p: &Path) -> bool {
+    p.file_name().and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with("result_") && n.ends_with(".json") && n.matches('.').count() == 1)
+}
+
+impl ResultsWatcher {
+    pub fn start(dir: &Path, ctx: &egui::Context) -> Result<Self> {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(ev) = res else { return; };
+            for p in ev.paths.into_iter().filter(|p| is_result_file(p)) {
+                let _ = tx.send(p);
+            }
+            ctx.request_repaint();
+        }).with_context(|| "failed to create a file watcher")?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("failed to watch {}", dir.display()))?;
+        Ok(Self { dir: dir.to_path_buf(), _watcher: watcher, rx, pending: HashMap::new() })
+    }
+
+    // the newest result file that has stopped growing, if any; call every frame
+    pub fn poll(&mut self, ctx: &egui::Context) -> Option<PathBuf> {
+        let now = Instant::now();
+        for p in self.rx.try_iter() {
+            let size = fs::metadata(&p).map(|m| m.len()).unwrap_or(0);
+            self.pending.insert(p, (size, now));
+        }
+        let mut settled = vec![];
+        self.pending.retain(|p, (size, since)| {
+            let Ok(meta) = fs::metadata(p) else { return false; };
+            if meta.len() != *size {
+                *size = meta.len();
+                *since = now;
+                return true;
+            }
+            if now.duration_since(*since) < SETTLE || *size == 0 { return true; }
+            settled.push(p.clone());
+            false
+        });
+        if !self.pending.is_empty() { ctx.request_repaint_after(SETTLE / 3); }
+        // result_<ts>.json names sort by time
+        settled.into_iter().max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_result_files_are_watched() {
+        assert!(is_result_file(Path::new("/p/results/result_20240101_120000.json")));
+        assert!(!is_result_file(Path::new("/p/results/result_20240101_120000.sidecar.json")));
+        assert!(!is_result_file(Path::new("/p/results/result_20240101_120000.json.tmp")));
+        assert!(!is_result_file(Path::new("/p/results/sources_20240101_120000.json")));
+    }
+}
@@ -0,0 +1,76 @@
+// 실행이 끝난 결과 JSON을 백엔드로 POST (설정에 Webhook URL이 있을 때만)
+// 전송 실패는 로그에만 남기고 실행 자체는 성공으로 둠 (로컬 JSON은 이미 저장됨)
+use anyhow::{Context, Result};
+use eframe::egui;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+// retries after the first attempt, on 5xx and network errors
+const MAX_RETRIES: u32 = 3;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub url: String,
+    // sent as `Authorization: Bearer <token>` when not empty
+    pub token: String,
+}
+
+impl Webhook {
+    // None when no URL is configured
+    pub fn from_settings(url: &str, token: &str) -> Option<Self> {
+        let url = url.trim();
+        (!url.is_empty()).then(|| Self { url: url.to_string(), token: token.trim().to_string() })
+    }
+}
+
+// POSTs the file as application/json; every attempt is logged. Returns the final HTTP status.
+pub fn post_results(hook: &Webhook, json: &Path, log: &dyn Fn(&str)) -> Result<u16> {
+    let body = fs::read(json).with_context(|| format!("failed to read {}", json.display()))?;
+    let client = reqwest::blocking::Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+    let mut attempt = 0;
+    loop {
+        let mut req = client.post(&hook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        if !hook.token.is_empty() { req = req.bearer_auth(&hook.token); }
+        let retry_reason = match req.send() {
+            Ok(resp) => {
+                let status = resp.status();
+                log(&format!("[INFO] webhook: POST {} → {}\n", hook.url, status));
+                if status.is_success() { return Ok(status.as_u16()); }
+                if !status.is_server_error() { anyhow::bail!("webhook rejected the results ({})", status); }
+                status.to_string()
+            }
+            Err(e) => format!("{}", e),
+        };
+        if attempt == MAX_RETRIES {
+            anyhow::bail!("webhook failed after {} attempts: {}", attempt + 1, retry_reason);
+        }
+        let wait = Duration::from_secs(2u64.pow(attempt));
+        log(&format!("[WARN] webhook: {}; retrying in {}s\n", retry_reason, wait.as_secs()));
+        thread::sleep(wait);
+        attempt += 1;
+    }
+}
+
+// manual re-send from the UI; log lines arrive on the returned channel
+pub fn spawn_send(hook: Webhook, json: PathBuf, ctx: egui::Context) -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let log = |s: &str| {
+            let _ = tx.send(s.to_string());
+            ctx.request_repaint();
+        };
+        match post_results(&hook, &json, &log) {
+            Ok(_) => log(&format!("[DONE] webhook: sent {}\n", json.display())),
+            Err(e) => log(&format!("[ERROR] {:#}\n", e)),
+        }
+    });
+    rx
+}
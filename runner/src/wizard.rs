@@ -0,0 +1,429 @@
+// 첫 실행 안내 (설정 파일이 없을 때): 프로젝트 루트 → Python 환경 → YOLO 가중치 → Gemini API 키
+// 단계마다 확인이 통과해야 다음으로 넘어가고, 값은 그때 설정에 들어감. "건너뛰기"는 기본 설정 그대로 둠
+use anyhow::{Context, Result};
+use eframe::egui::{self, Color32};
+use rfd::FileDialog;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    io::{BufRead, BufReader},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use crate::{
+    error::RunnerError, expand_path, find_project_root, i18n::tr, looks_like_repo_root, pipeline, preflight,
+    settings::Settings, write_atomic,
+};
+
+// the key gemini/run.py reads (environment or a .env above gemini/)
+const API_KEY: &str = "GOOGLE_API_KEY";
+// how deep the weights scan looks under the project root
+const WEIGHTS_SCAN_DEPTH: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Root,
+    Python,
+    Weights,
+    ApiKey,
+}
+
+pub enum Outcome {
+    // `settings` hold what the steps confirmed
+    Finished,
+    // `settings` are back to what they were when the wizard opened
+    Skipped,
+}
+
+enum VenvEvent {
+    Line(String),
+    Done(Result<()>),
+}
+
+pub struct Wizard {
+    step: Step,
+    // settings as they were on launch, restored by Skip
+    initial: Settings,
+    error: Option<String>,
+    // Python step
+    python_found: Option<Result<String, String>>,
+    venv_rx: Option<Receiver<VenvEvent>>,
+    venv_status: String,
+    // Weights step: found under the project root, relative to it when inside
+    weights_found: Vec<String>,
+    api_key: String,
+    api_key_set: Option<String>,
+    // .env is committed in this checkout, so a key typed here could be pushed with it
+    env_tracked: bool,
+}
+
+impl Wizard {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            step: Step::Root,
+            initial: settings.clone(),
+            error: None,
+            python_found: None,
+            venv_rx: None,
+            venv_status: String::new(),
+            weights_found: vec![],
+            api_key: String::new(),
+            api_key_set: None,
+            env_tracked: false,
+        }
+    }
+
+    fn root(settings: &Settings) -> PathBuf {
+        expand_path(&settings.project_root)
+    }
+
+    // None while open
+    pub fn show(&mut self, ctx: &egui::Context, settings: &mut Settings) -> Option<Outcome> {
+        self.poll_venv();
+        let mut outcome = None;
+        let (n, title) = match self.step {
+            Step::Root => (1, tr("Project root")),
+            Step::Python => (2, tr("Python environment")),
+            Step::Weights => (3, tr("YOLO weights")),
+            Step::ApiKey => (4, tr("Gemini API key")),
+        };
+        // modal: a full-screen backdrop takes every click meant for the panels behind it
+        egui::Area::new(egui::Id::new("wizard_backdrop"))
+            .order(egui::Order::Middle)
+            .fixed_pos(egui::Pos2::ZERO)
+            .interactable(true)
+            .show(ctx, |ui| {
+                let screen = ctx.screen_rect();
+                ui.allocate_rect(screen, egui::Sense::click_and_drag());
+                ui.painter().rect_filled(screen, 0.0, Color32::from_black_alpha(120));
+            });
+        let window = egui::Window::new(tr("Welcome"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.set_width(460.0);
+                ui.heading(format!("{}/4  {}", n, title));
+                ui.add_space(6.0);
+                match self.step {
+                    Step::Root => self.root_step(ui, settings),
+                    Step::Python => self.python_step(ui, settings),
+                    Step::Weights => self.weights_step(ui, settings),
+                    Step::ApiKey => self.api_key_step(ui),
+                }
+                if let Some(e) = &self.error {
+                    ui.colored_label(Color32::from_rgb(220, 50, 50), e);
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let busy = self.venv_rx.is_some();
+                    if self.step != Step::Root && ui.add_enabled(!busy, egui::Button::new(tr("Back"))).clicked() {
+                        self.error = None;
+                        self.step = match self.step {
+                            Step::Root | Step::Python => Step::Root,
+                            Step::Weights => Step::Python,
+                            Step::ApiKey => Step::Weights,
+                        };
+                    }
+                    let next = if self.step == Step::ApiKey { tr("Finish") } else { tr("Next") };
+                    if ui.add_enabled(!busy, egui::Button::new(next)).clicked() {
+                        match self.advance(settings) {
+                            Ok(true) => outcome = Some(Outcome::Finished),
+                            Ok(false) => self.error = None,
+                            Err(e) => self.error = Some(format!("{:#}", e)),
+                        }
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.add_enabled(!busy, egui::Button::new(tr("Skip"))).on_hover_text(tr("Start with the default settings; everything can be set in the left panel later")).clicked() {
+                            *settings = self.initial.clone();
+                            outcome = Some(Outcome::Skipped);
+                        }
+                    });
+                });
+            });
+        // clicking the backdrop raises it; keep the window above
+        if let Some(w) = window {
+            ctx.move_to_top(w.response.layer_id);
+        }
+        outcome
+    }
+
+    fn root_step(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        ui.label(tr("The folder that contains yolov8/run.py and gemini/run.py."));
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut settings.project_root);
+            if ui.button(tr("Browse...")).clicked() {
+                if let Some(p) = FileDialog::new().pick_folder() {
+                    settings.project_root = p.to_string_lossy().to_string();
+                }
+            }
+        });
+        if !looks_like_repo_root(&Self::root(settings)) {
+            if let Some(found) = find_project_root("") {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("{} {}", tr("Found a project at"), found.display()));
+                    if ui.small_button(tr("Use it")).clicked() {
+                        settings.project_root = found.to_string_lossy().to_string();
+                    }
+                });
+            }
+        }
+    }
+
+    fn python_step(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        ui.label(tr("The Python that runs the scripts. A .venv in the project root is used first when it exists."));
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut settings.python_bin);
+            if ui.button(tr("Check")).clicked() { self.python_found = None; }
+        });
+        let root = Self::root(settings);
+        let found = self.python_found.get_or_insert_with(|| {
+            pipeline::resolve_python(&settings.python_bin, &root).map_err(|e| e.to_string())
+        });
+        match found {
+            Ok(py) => { ui.colored_label(Color32::from_rgb(0, 160, 0), format!("✔ {}", py)); }
+            Err(e) => { ui.colored_label(Color32::from_rgb(230, 160, 0), format!("✖ {}", e)); }
+        }
+        ui.horizontal(|ui| {
+            let has_venv = root.join(".venv").is_dir();
+            let label = if has_venv { tr("Reinstall packages into .venv") } else { tr("Create .venv and install packages") };
+            if ui.add_enabled(self.venv_rx.is_none(), egui::Button::new(label))
+                .on_hover_text(tr("python -m venv .venv, then pip install -r requirements.txt; this can take a few minutes"))
+                .clicked()
+            {
+                self.error = None;
+                self.venv_status.clear();
+                self.venv_rx = Some(spawn_venv_bootstrap(settings.python_bin.clone(), root.clone(), ui.ctx().clone()));
+            }
+            if self.venv_rx.is_some() { ui.spinner(); }
+        });
+        if !self.venv_status.is_empty() {
+            ui.add(egui::Label::new(egui::RichText::new(&self.venv_status).weak().monospace()).truncate(true));
+        }
+    }
+
+    fn weights_step(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        ui.label(tr("The trained YOLO model (.pt, or .onnx for native inference)."));
+        let selected = if settings.weights_path.is_empty() { tr("(none)").to_string() } else { settings.weights_path.clone() };
+        egui::ComboBox::from_id_source("wizard_weights")
+            .selected_text(selected)
+            .width(360.0)
+            .show_ui(ui, |ui| {
+                for w in &self.weights_found {
+                    ui.selectable_value(&mut settings.weights_path, w.clone(), w);
+                }
+            });
+        ui.horizontal(|ui| {
+            if ui.button(tr("Select file")).clicked() {
+                if let Some(p) = FileDialog::new().add_filter("weights", &["pt", "onnx"]).pick_file() {
+                    settings.weights_path = p.to_string_lossy().to_string();
+                }
+            }
+            ui.weak(format!("{} {}", self.weights_found.len(), tr("found in the project")));
+        });
+    }
+
+    fn api_key_step(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("Optional: the key gemini/run.py uses. It is saved to .env in the project root, not to the app settings."));
+        if let Some(src) = &self.api_key_set {
+            ui.colored_label(Color32::from_rgb(0, 160, 0), format!("✔ {}", src));
+        }
+        if self.env_tracked {
+            ui.colored_label(Color32::from_rgb(220, 140, 0), tr("⚠ .env is tracked by git in this project; a key saved to it can end up in a commit. Run `git rm --cached .env` first."));
+        }
+        ui.add(egui::TextEdit::singleline(&mut self.api_key).password(true).hint_text(API_KEY).desired_width(360.0));
+    }
+
+    // validates the current step; Ok(true) when the last one is done
+    fn advance(&mut self, settings: &mut Settings) -> Result<bool> {
+        let root = Self::root(settings);
+        match self.step {
+            Step::Root => {
+                preflight::scripts(&root).map_err(|e| {
+                    let hint = RunnerError::find(&e).map(|r| r.guidance()).unwrap_or_default();
+                    anyhow::anyhow!("{}\n{}", e, tr(hint))
+                })?;
+                self.python_found = None;
+                self.step = Step::Python;
+            }
+            Step::Python => {
+                match self.python_found.get_or_insert_with(|| pipeline::resolve_python(&settings.python_bin, &root).map_err(|e| e.to_string())) {
+                    Ok(_) => {}
+                    Err(e) => anyhow::bail!("{}", e),
+                }
+                self.weights_found = scan_weights(&root);
+                if settings.weights_path.is_empty() || preflight::weights(&root, &settings.weights_path).is_err() {
+                    if let Some(w) = self.weights_found.first() { settings.weights_path = w.clone(); }
+                }
+                self.step = Step::Weights;
+            }
+            Step::Weights => {
+                preflight::weights(&root, &settings.weights_path)?;
+                self.api_key_set = preflight::env_key(&root, API_KEY).ok();
+                self.env_tracked = git_tracks(&root, ".env");
+                self.step = Step::ApiKey;
+            }
+            Step::ApiKey => {
+                let key = self.api_key.trim();
+                if !key.is_empty() {
+                    if key.contains(char::is_whitespace) { anyhow::bail!("the key can't contain spaces"); }
+                    set_dotenv_key(&root.join(".env"), API_KEY, key)?;
+                }
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn poll_venv(&mut self) {
+        let Some(rx) = &self.venv_rx else { return; };
+        let events: Vec<VenvEvent> = rx.try_iter().collect();
+        for ev in events {
+            match ev {
+                VenvEvent::Line(l) => self.venv_status = l,
+                VenvEvent::Done(res) => {
+                    self.venv_rx = None;
+                    self.python_found = None;
+                    match res {
+                        Ok(()) => self.venv_status = tr("✔ .venv is ready").to_string(),
+                        Err(e) => self.error = Some(format!("{:#}", e)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+// .pt / .onnx files under the project root, best.pt files first; hidden folders (.venv, .git,
+// .runner_work) are not searched
+pub fn scan_weights(root: &Path) -> Vec<String> {
+    let mut found = vec![];
+    let mut stack = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue; };
+        for e in entries.flatten() {
+            let p = e.path();
+            let name = e.file_name().to_string_lossy().to_string();
+            if p.is_dir() {
+                if depth < WEIGHTS_SCAN_DEPTH && !name.starts_with('.') && name != "__pycache__" && name != "node_modules" {
+                    stack.push((p, depth + 1));
+                }
+            } else if p.extension().is_some_and(|x| x == "pt" || x == "onnx") {
+                let rel = p.strip_prefix(root).unwrap_or(&p);
+                found.push(rel.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect::<Vec<_>>().join("/"));
+            }
+        }
+    }
+    found.sort_by_key(|w| (!w.ends_with("/best.pt"), w.clone()));
+    found
+}
+
+// false when git is missing or the root is not a checkout
+fn git_tracks(root: &Path, file: &str) -> bool {
+    Command::new("git").arg("-C").arg(root).args(["ls-files", "--error-unmatch", file])
+        .stdout(Stdio::null()).stderr(Stdio::null())
+        .status().is_ok_and(|s| s.success())
+}
+
+// replaces `key=...` in a .env file where it stands (or appends it), keeping every other line
+fn set_dotenv_key(path: &Path, key: &str, value: &str) -> Result<()> {
+    let old = fs::read_to_string(path).unwrap_or_default();
+    let mut replaced = false;
+    let mut lines: Vec<String> = old.lines()
+        .filter_map(|l| {
+            let t = l.trim();
+            let t = t.strip_prefix("export ").unwrap_or(t);
+            if t.split_once('=').is_none_or(|(k, _)| k.trim() != key) {
+                return Some(l.to_string());
+            }
+            // later duplicates would override the new value
+            (!std::mem::replace(&mut replaced, true)).then(|| format!("{}={}", key, value))
+        })
+        .collect();
+    if !replaced {
+        lines.push(format!("{}={}", key, value));
+    }
+    write_atomic(path, (lines.join("\n") + "\n").as_bytes()).with_context(|| format!("failed to save the key to {}", path.display()))
+}
+
+// python -m venv <root>/.venv, then pip install -r requirements.txt with the new interpreter
+fn spawn_venv_bootstrap(python_bin: String, root: PathBuf, ctx: egui::Context) -> Receiver<VenvEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let line = |s: String| {
+            let _ = tx.send(VenvEvent::Line(s));
+            ctx.request_repaint();
+        };
+        let res = (|| -> Result<()> {
+            let venv = root.join(".venv");
+            if !venv.is_dir() {
+                // the configured interpreter, else whatever is on PATH (not the venv that doesn't exist yet)
+                let base = [python_bin.as_str(), "python3", "python"].into_iter()
+                    .filter(|c| !c.trim().is_empty())
+                    .find(|c| Command::new(c).arg("--version").output().is_ok())
+                    .with_context(|| "no Python found to create the environment with")?;
+                line(format!("{} -m venv {}", base, venv.display()));
+                run_streaming(Command::new(base).arg("-m").arg("venv").arg(&venv), &line)?;
+            }
+            let py = pipeline::resolve_python("", &root)?;
+            let reqs = root.join("requirements.txt");
+            if reqs.is_file() {
+                line(format!("{} -m pip install -r {}", py, reqs.display()));
+                run_streaming(Command::new(&py).args(["-m", "pip", "install", "-r"]).arg(&reqs), &line)?;
+            }
+            Ok(())
+        })();
+        let _ = tx.send(VenvEvent::Done(res));
+        ctx.request_repaint();
+    });
+    rx
+}
+
+// stdout lines go to `line` as they come; stderr is kept for the error
+fn run_streaming(cmd: &mut Command, line: &dyn Fn(String)) -> Result<()> {
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()
+        .with_context(|| format!("failed to start {:?}", cmd.get_program()))?;
+    let stderr = child.stderr.take().map(|e| thread::spawn(move || {
+        BufReader::new(e).lines().map_while(Result::ok).collect::<Vec<_>>()
+    }));
+    if let Some(out) = child.stdout.take() {
+        for l in BufReader::new(out).lines().map_while(Result::ok) { line(l); }
+    }
+    let status = child.wait()?;
+    let err = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+    if !status.success() {
+        let tail = err[err.len().saturating_sub(3)..].join("\n");
+        anyhow::bail!("{:?} failed ({}): {}", cmd.get_program(), status, tail);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_weights_and_rewrites_only_the_api_key_line() {
+        let root = std::env::temp_dir().join(format!("wheel_city_wizard_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let weights = root.join("yolov8").join("train_result").join("ver14").join("weights");
+        fs::create_dir_all(&weights).unwrap();
+        fs::create_dir_all(root.join(".venv").join("lib")).unwrap();
+        for p in [weights.join("best.pt"), weights.join("last.pt"), root.join("yolov8n.pt"), root.join(".venv").join("lib").join("x.pt")] {
+            fs::write(p, b"w").unwrap();
+        }
+        assert_eq!(scan_weights(&root), ["yolov8/train_result/ver14/weights/best.pt", "yolov8/train_result/ver14/weights/last.pt", "yolov8n.pt"]);
+
+        let env = root.join(".env");
+        fs::write(&env, "OPENAI_API_KEY=a\nexport GOOGLE_API_KEY=old\n# note\nGOOGLE_API_KEY=dup\n").unwrap();
+        set_dotenv_key(&env, API_KEY, "new").unwrap();
+        assert_eq!(fs::read_to_string(&env).unwrap(), "OPENAI_API_KEY=a\nGOOGLE_API_KEY=new\n# note\n");
+        set_dotenv_key(&env, "OLLAMA_HOST", "gpu-box").unwrap();
+        assert_eq!(fs::read_to_string(&env).unwrap(), "OPENAI_API_KEY=a\nGOOGLE_API_KEY=new\n# note\nOLLAMA_HOST=gpu-box\n");
+        assert!(!git_tracks(&root, ".env"));
+        assert!(preflight::env_key(&root, API_KEY).is_ok());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,127 @@
+// .runner_work 정리: 보존 기간이 지난 실행 로그, bbox 이미지와 썸네일, 붙여넣거나 내려받은 입력 이미지를 삭제
+// 실행 횟수 기준으로 최근 N 개 실행의 bbox 폴더만 남기는 정리도 포함
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+// removes run logs, bbox dirs and their thumbnails, pasted/downloaded inputs, leftover stream files and prompt files older than `keep_days` (0 = keep everything);
+// returns how many entries were deleted
+pub fn prune(work_dir: &Path, keep_days: u32) -> usize {
+    if keep_days == 0 { return 0; }
+    let cutoff = SystemTime::now() - Duration::from_secs(u64::from(keep_days) * 24 * 60 * 60);
+    let is_old = |p: &Path| {
+        fs::metadata(p).and_then(|m| m.modified()).map(|t| t < cutoff).unwrap_or(false)
+    };
+    let mut removed = 0;
+    for sub in ["logs", "bbox", "thumbs", "clipboard", "downloads"] {
+        let Ok(rd) = fs::read_dir(work_dir.join(sub)) else { continue; };
+        for e in rd.flatten() {
+            let p = e.path();
+            if !is_old(&p) { continue; }
+            let res = if p.is_dir() { fs::remove_dir_all(&p) } else { fs::remove_file(&p) };
+            if res.is_ok() { removed += 1; }
+        }
+    }
+    if let Ok(rd) = fs::read_dir(work_dir) {
+        for e in rd.flatten() {
+            let p = e.path();
+            let is_run_file = p.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                (n.starts_with("stream_") && n.ends_with(".jsonl")) || (n.starts_with("prompt_") && n.ends_with(".txt"))
+            });
+            if is_run_file && is_old(&p) && fs::remove_file(&p).is_ok() { removed += 1; }
+        }
+    }
+    removed
+}
+
+// bbox/<ts> and the A/B pair bbox/<ts>_a, bbox/<ts>_b belong to one run
+fn run_key(dir_name: &str) -> &str {
+    match dir_name.get(..15) {
+        Some(ts) if ts.chars().all(|c| c.is_ascii_digit() || c == '_') => ts,
+        _ => dir_name,
+    }
+}
+
+fn dir_size(p: &Path) -> u64 {
+    let Ok(rd) = fs::read_dir(p) else { return fs::metadata(p).map(|m| m.len()).unwrap_or(0); };
+    rd.flatten().map(|e| {
+        let p = e.path();
+        if p.is_dir() { dir_size(&p) } else { e.metadata().map(|m| m.len()).unwrap_or(0) }
+    }).sum()
+}
+
+// keeps the bbox dirs (and their thumbnail caches) of the `keep` newest runs, oldest deleted first;
+// the run of `current` is always kept. Returns (runs removed, bytes reclaimed).
+pub fn prune_runs(work_dir: &Path, keep: usize, current: &Path) -> (usize, u64) {
+    if keep == 0 { return (0, 0); }
+    let current_key = current.file_name().map(|n| run_key(&n.to_string_lossy()).to_string());
+    // run → (newest mtime of its dirs, dir names)
+    let mut runs: BTreeMap<String, (SystemTime, Vec<String>)> = BTreeMap::new();
+    let Ok(rd) = fs::read_dir(work_dir.join("bbox")) else { return (0, 0); };
+    for e in rd.flatten().filter(|e| e.path().is_dir()) {
+        let name = e.file_name().to_string_lossy().to_string();
+        let modified = e.metadata().and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+        let run = runs.entry(run_key(&name).to_string()).or_insert((SystemTime::UNIX_EPOCH, vec![]));
+        run.0 = run.0.max(modified);
+        run.1.push(name);
+    }
+    let mut order: Vec<(String, SystemTime, Vec<String>)> = runs.into_iter().map(|(k, (t, dirs))| (k, t, dirs)).collect();
+    // newest first; the timestamp in the name breaks ties
+    order.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+    let mut kept = 0;
+    let (mut removed, mut bytes) = (0, 0);
+    for (key, _, dirs) in order {
+        if Some(&key) == current_key.as_ref() { continue; }
+        // the current run takes one of the `keep` slots
+        if kept + 1 < keep {
+            kept += 1;
+            continue;
+        }
+        let mut ok = false;
+        for name in dirs {
+            for p in [work_dir.join("bbox").join(&name), work_dir.join("thumbs").join(&name)] {
+                if !p.exists() { continue; }
+                let size = dir_size(&p);
+                if fs::remove_dir_all(&p).is_ok() {
+                    bytes += size;
+                    ok = true;
+                }
+            }
+        }
+        if ok { removed += 1; }
+    }
+    (removed, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_runs_keeps_the_newest_and_the_current_run() {
+        let work = std::env::temp_dir().join(format!("wheel_city_prune_runs_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&work);
+        for name in ["20240101_090000", "20240102_090000_a", "20240102_090000_b", "20240103_090000", "20240104_090000"] {
+            let dir = work.join("bbox").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("x.jpg"), [0u8; 100]).unwrap();
+        }
+        fs::create_dir_all(work.join("thumbs").join("20240101_090000")).unwrap();
+        fs::write(work.join("thumbs").join("20240101_090000").join("x.png"), [0u8; 10]).unwrap();
+
+        assert_eq!(prune_runs(&work, 0, &work.join("bbox").join("20240104_090000")), (0, 0));
+        // the oldest run is the current one (a redetect into an old dir), so it stays
+        let (removed, bytes) = prune_runs(&work, 2, &work.join("bbox").join("20240101_090000"));
+        assert_eq!(removed, 2);
+        assert_eq!(bytes, 300);
+        let mut left: Vec<String> = fs::read_dir(work.join("bbox")).unwrap().flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string()).collect();
+        left.sort();
+        assert_eq!(left, ["20240101_090000", "20240104_090000"]);
+        assert!(work.join("thumbs").join("20240101_090000").exists());
+        let _ = fs::remove_dir_all(&work);
+    }
+}
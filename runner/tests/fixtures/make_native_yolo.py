@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+# runner/tests/fixtures/make_native_yolo.py
+# 네이티브 추론(native.rs)과 비교할 기준 라벨을 ultralytics 로 만듦:
+# best.pt 를 .onnx 로 내보내고, 같은 .onnx 로 yolov8/run.py --save_txt 를 몇 장의 입력 이미지에 돌려
+# labels/<stem>.txt 를 native_yolo_ultralytics.json 에 기록 (프로젝트 루트에서 실행, ultralytics 필요)
+import argparse
+import json
+import shutil
+import subprocess
+import sys
+import tempfile
+from pathlib import Path
+
+from ultralytics import YOLO
+
+HERE = Path(__file__).resolve().parent
+ROOT = HERE.parents[2]
+
+def main():
+    ap = argparse.ArgumentParser()
+    ap.add_argument("--weights", default="yolov8/train_result/ver14/weights/best.pt")
+    ap.add_argument("--images",  nargs="+", default=["input_images/data1.jpg", "input_images/data2.jpg", "input_images/data3.jpg"])
+    ap.add_argument("--imgsz",   type=int, default=640)
+    ap.add_argument("--out",     default=str(HERE / "native_yolo_ultralytics.json"))
+    args = ap.parse_args()
+
+    weights = ROOT / args.weights
+    # 고정 입력 크기로 내보냄 (native.rs 는 동적 축이면 640 으로 가정)
+    onnx = Path(YOLO(str(weights)).export(format="onnx", imgsz=args.imgsz, dynamic=False))
+
+    with tempfile.TemporaryDirectory() as tmp:
+        source, outdir = Path(tmp) / "source", Path(tmp) / "out"
+        source.mkdir()
+        for img in args.images:
+            shutil.copy(ROOT / img, source)
+        subprocess.run([sys.executable, str(ROOT / "yolov8" / "run.py"),
+                        "--weights", str(onnx), "--source", str(source), "--outdir", str(outdir),
+                        "--imgsz", str(args.imgsz), "--save_txt"], check=True)
+        labels = {}
+        for img in args.images:
+            txt = outdir / "labels" / f"{Path(img).stem}.txt"
+            # 검출이 없으면 ultralytics 는 파일을 만들지 않음
+            labels[Path(img).name] = txt.read_text(encoding="utf-8") if txt.exists() else ""
+
+    fixture = {
+        "note": "generated by make_native_yolo.py: yolov8/run.py --save_txt with the exported .onnx",
+        "weights": str(onnx.resolve().relative_to(ROOT)),
+        "labels": labels,
+    }
+    Path(args.out).write_text(json.dumps(fixture, ensure_ascii=False, indent=2) + "\n", encoding="utf-8")
+    print(f"[OK] Saved: {Path(args.out).resolve()}")
+
+if __name__ == "__main__":
+    main()
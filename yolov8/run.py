@@ -1,6 +1,7 @@
 #!/usr/bin/env python3
 # yolov8/run.py
 import argparse
+import json
 from pathlib import Path
 from ultralytics import YOLO
 
@@ -11,11 +12,13 @@ def main():
     ap.add_argument("--outdir",  default="bbox_images")
     ap.add_argument("--imgsz",   type=int, default=640)
     ap.add_argument("--conf",    type=float, default=0.25)
+    ap.add_argument("--classes_out", default=None, help="write {image name: [detected class names]} JSON here")
+    ap.add_argument("--save_txt", action="store_true", help="<outdir>/labels/<stem>.txt 에 YOLO 라벨(class cx cy w h conf)도 저장")
     args = ap.parse_args()
 
     Path(args.outdir).mkdir(parents=True, exist_ok=True)
     model = YOLO(args.weights)
-    model.predict(
+    results = model.predict(
         source=args.source,
         imgsz=args.imgsz,
         conf=args.conf,
@@ -23,9 +26,27 @@ def main():
         project=args.outdir,
         name=".",
         exist_ok=True,
-        line_width=2
+        line_width=2,
+        save_txt=args.save_txt,
+        save_conf=args.save_txt,
     )
     print(f"[OK] Saved bbox images to: {Path(args.outdir).resolve()}")
 
+    if args.save_txt:
+        # 라벨 파일의 클래스 번호 → 이름 (runner가 박스에 이름을 붙일 때 사용)
+        labels_dir = Path(args.outdir) / "labels"
+        labels_dir.mkdir(parents=True, exist_ok=True)
+        (labels_dir / "names.json").write_text(json.dumps({str(k): v for k, v in model.names.items()}, ensure_ascii=False, indent=2), encoding="utf-8")
+        print(f"[OK] Saved YOLO labels to: {labels_dir.resolve()}")
+
+    if args.classes_out:
+        # 이미지별로 검출된 클래스 이름 (중복 제거, 검출 없으면 빈 목록)
+        classes = {}
+        for r in results:
+            names = sorted({r.names[int(c)] for c in r.boxes.cls.tolist()}) if r.boxes is not None else []
+            classes[Path(r.path).name] = names
+        Path(args.classes_out).write_text(json.dumps(classes, ensure_ascii=False, indent=2), encoding="utf-8")
+        print(f"[OK] Saved detected classes to: {Path(args.classes_out).resolve()}")
+
 if __name__ == "__main__":
     main()